@@ -0,0 +1,389 @@
+use std::collections::HashSet;
+
+use types::{
+    QPDFAnnotationFlags, QPDFAnnotationFlattenScope, QPDFAnnotationInfo, QPDFAnnotationKind,
+    QPDFAnnotationParams,
+};
+
+use super::{
+    QPDF, QPDFErrors,
+    content::builder::QPDFContentBuilder,
+    geometry::{Matrix, Rect},
+    object::{
+        QPDFObjectHandler,
+        types::{QPDFIsObjectType, QPDFModifyObjectTypes},
+    },
+    outline::destination_array,
+    page::QPDFPage,
+};
+
+pub struct QPDFAnnotation {
+    info: QPDFAnnotationInfo,
+    object: QPDFObjectHandler,
+}
+
+// Construction
+impl QPDFAnnotation {
+    pub fn from_object(object: QPDFObjectHandler) -> Result<Self, QPDFErrors> {
+        if !object.is(QPDFIsObjectType::Dictionary) {
+            return Err(QPDFErrors::InvalidObject);
+        }
+
+        let key = |key: &str| object.dict_get_key(key.to_string());
+        let text = |key: &str| -> Option<String> {
+            let value = object.dict_get_key(key.to_string());
+            match value.is(QPDFIsObjectType::String) {
+                true => value.try_into().ok(),
+                _ => None,
+            }
+        };
+
+        let info = QPDFAnnotationInfo {
+            object_id: object.object_id(),
+            generation: object.generation(),
+            subtype: key("/Subtype").name().unwrap_or_default(),
+            rect: key("/Rect").try_into().ok(),
+            contents: text("/Contents"),
+            name: text("/NM"),
+            modified: text("/M"),
+            appearance_state: key("/AS").name().ok(),
+            flags: QPDFAnnotationFlags::from(key("/F").try_into().unwrap_or(0)),
+        };
+
+        Ok(Self { info, object })
+    }
+
+    pub fn info(&self) -> &QPDFAnnotationInfo {
+        &self.info
+    }
+
+    pub fn object(&self) -> &QPDFObjectHandler {
+        &self.object
+    }
+}
+
+// Modification
+impl QPDFAnnotation {
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.replace("/Rect", Some(rect.to_object(&self.object)));
+    }
+
+    pub fn set_contents(&mut self, contents: Option<&str>) {
+        let value = contents.map(|v| {
+            self.object
+                .set(QPDFModifyObjectTypes::String(v.to_string()))
+        });
+        self.replace("/Contents", value);
+    }
+
+    pub fn set_name(&mut self, name: Option<&str>) {
+        let value = name.map(|v| {
+            self.object
+                .set(QPDFModifyObjectTypes::String(v.to_string()))
+        });
+        self.replace("/NM", value);
+    }
+
+    pub fn set_modified(&mut self, modified: Option<&str>) {
+        let value = modified.map(|v| {
+            self.object
+                .set(QPDFModifyObjectTypes::String(v.to_string()))
+        });
+        self.replace("/M", value);
+    }
+
+    pub fn set_color(&mut self, color: Option<[f64; 3]>) {
+        let value = color.map(|color| color_array(&self.object, color));
+        self.replace("/C", value);
+    }
+
+    pub fn set_flags(&mut self, flags: QPDFAnnotationFlags) {
+        let value = self
+            .object
+            .set(QPDFModifyObjectTypes::Integer(flags.into()));
+        self.replace("/F", Some(value));
+    }
+
+    fn replace(&mut self, key: &str, value: Option<QPDFObjectHandler>) {
+        match value {
+            Some(value) => self.object.dict_replace_key(key.to_string(), value),
+            None => self.object.dict_remove_key(key.to_string()),
+        }
+
+        if let Ok(updated) = QPDFAnnotation::from_object(self.object.clone()) {
+            self.info = updated.info;
+        }
+    }
+}
+
+pub(crate) fn create_annotation(
+    qpdf: &QPDF,
+    page: &QPDFPage,
+    params: &QPDFAnnotationParams,
+) -> Result<QPDFAnnotation, QPDFErrors> {
+    let factory = page.object();
+    let annot = factory
+        .set(QPDFModifyObjectTypes::Dictionary)
+        .make_indirect()
+        .ok_or(QPDFErrors::InvalidObject)?;
+
+    let set = |key: &str, value: QPDFObjectHandler| annot.dict_replace_key(key.to_string(), value);
+    let name = |name: &str| factory.set(QPDFModifyObjectTypes::Name(name.to_string()));
+    let string = |text: &str| factory.set(QPDFModifyObjectTypes::String(text.to_string()));
+
+    set("/Type", name("/Annot"));
+    set("/Subtype", name(params.kind.subtype()));
+    set("/Rect", params.rect.to_object(factory));
+    set("/P", factory.clone());
+    set(
+        "/F",
+        factory.set(QPDFModifyObjectTypes::Integer(params.flags.into())),
+    );
+
+    if let Some(contents) = &params.contents {
+        set("/Contents", string(contents));
+    }
+
+    if let Some(nm) = &params.name {
+        set("/NM", string(nm));
+    }
+
+    if let Some(modified) = &params.modified {
+        set("/M", string(modified));
+    }
+
+    if let Some(color) = params.color {
+        set("/C", color_array(factory, color));
+    }
+
+    match &params.kind {
+        QPDFAnnotationKind::LinkUri(uri) => {
+            let action = factory.set(QPDFModifyObjectTypes::Dictionary);
+            action.dict_replace_key("/S".to_string(), name("/URI"));
+            action.dict_replace_key("/URI".to_string(), string(uri));
+
+            set("/A", action);
+            set("/Border", border(factory));
+        }
+        QPDFAnnotationKind::LinkPage(page_index, fit) => {
+            set("/Dest", destination_array(qpdf, factory, *page_index, fit)?);
+            set("/Border", border(factory));
+        }
+        QPDFAnnotationKind::Text(icon) => {
+            set("/Name", name(icon));
+        }
+        QPDFAnnotationKind::FreeText(size) => {
+            // Viewers build the appearance from the default appearance string
+            set("/DA", string(&format!("/Helv {size} Tf 0 g")));
+        }
+        QPDFAnnotationKind::Highlight(areas)
+        | QPDFAnnotationKind::Underline(areas)
+        | QPDFAnnotationKind::StrikeOut(areas) => {
+            let quads = factory.set(QPDFModifyObjectTypes::Array);
+
+            // Each area is given as its upper left, upper right, lower left and lower right corners
+            for area in areas {
+                for v in [
+                    area.llx, area.ury, area.urx, area.ury, area.llx, area.lly, area.urx, area.lly,
+                ] {
+                    quads.array_append(factory.set(QPDFModifyObjectTypes::Real(v, 4)));
+                }
+            }
+
+            set("/QuadPoints", quads);
+        }
+        QPDFAnnotationKind::Square | QPDFAnnotationKind::Circle => (),
+        QPDFAnnotationKind::Stamp(stamp) => {
+            set("/Name", name(stamp));
+        }
+    }
+
+    let annots = factory.dict_get_key("/Annots".to_string());
+    if annots.is(QPDFIsObjectType::Array) {
+        annots.array_append(annot.clone());
+    } else {
+        let annots = factory.set(QPDFModifyObjectTypes::Array);
+        annots.array_append(annot.clone());
+        factory.dict_replace_key("/Annots".to_string(), annots);
+    }
+
+    QPDFAnnotation::from_object(annot)
+}
+
+// Removes matching annotations, along with the pop-ups that belong to them
+pub(crate) fn remove_annotations<F: Fn(&QPDFAnnotation) -> bool>(
+    page: &QPDFObjectHandler,
+    predicate: F,
+) -> usize {
+    let annots = page.dict_get_key("/Annots".to_string());
+    let mut removed = HashSet::new();
+
+    for at in (0..annots.array_len()).rev() {
+        let Ok(annot) = QPDFAnnotation::from_object(annots.array_get_at(at)) else {
+            continue;
+        };
+
+        if predicate(&annot) {
+            removed.insert((annot.info.object_id, annot.info.generation));
+            annots.array_erase_at(at);
+        }
+    }
+
+    for at in (0..annots.array_len()).rev() {
+        let annot = annots.array_get_at(at);
+        let parent = annot.dict_get_key("/Parent".to_string());
+
+        if annot
+            .dict_get_key("/Subtype".to_string())
+            .is(QPDFIsObjectType::NameEquals("/Popup".to_string()))
+            && removed.contains(&(parent.object_id(), parent.generation()))
+        {
+            annots.array_erase_at(at);
+        }
+    }
+
+    removed.len()
+}
+
+// Draws appearance streams into the page content and drops their annotations
+pub(crate) fn flatten_annotations(page: &QPDFPage, scope: QPDFAnnotationFlattenScope) -> usize {
+    let mut builder = QPDFContentBuilder::for_page(page);
+    let mut flattened = HashSet::new();
+
+    for annot in collect_annotations(page.object()) {
+        // Widgets are left to form flattening, and annotations without an appearance are kept
+        if annot.info().subtype == "/Widget" || !draw_appearance(&mut builder, &annot, scope) {
+            continue;
+        }
+
+        flattened.insert((annot.info.object_id, annot.info.generation));
+    }
+
+    if !builder.data().is_empty() {
+        page.append_content(builder.data());
+    }
+
+    remove_annotations(page.object(), |annot| {
+        flattened.contains(&(annot.info.object_id, annot.info.generation))
+    });
+
+    if page
+        .object()
+        .dict_get_key("/Annots".to_string())
+        .array_len()
+        == 0
+    {
+        page.object().dict_remove_key("/Annots".to_string());
+    }
+
+    flattened.len()
+}
+
+// Returns false when there is no appearance, annotations hidden in this scope are not drawn
+pub(crate) fn draw_appearance(
+    builder: &mut QPDFContentBuilder,
+    annot: &QPDFAnnotation,
+    scope: QPDFAnnotationFlattenScope,
+) -> bool {
+    let Some(appearance) = normal_appearance(annot.object()) else {
+        return false;
+    };
+
+    let info = annot.info();
+    let placement = info.rect.and_then(|rect| placement(&appearance, &rect));
+    if let (true, Some(matrix)) = (info.flags.is_shown(scope), placement) {
+        // Appearance streams sometimes leave out the subtype form XObjects need
+        let dict = appearance.dict();
+        if !dict.dict_has_key("/Subtype".to_string()) {
+            dict.dict_replace_key(
+                "/Subtype".to_string(),
+                dict.set(QPDFModifyObjectTypes::Name("/Form".to_string())),
+            );
+        }
+
+        builder
+            .save()
+            .transform(matrix)
+            .draw_xobject(&appearance)
+            .restore();
+    }
+
+    true
+}
+
+// Picks the normal appearance, or the one selected by /AS when there are several states
+fn normal_appearance(annot: &QPDFObjectHandler) -> Option<QPDFObjectHandler> {
+    let normal = annot
+        .dict_get_key("/AP".to_string())
+        .dict_get_key("/N".to_string());
+    if normal.is(QPDFIsObjectType::Stream) {
+        return Some(normal);
+    }
+
+    let state = annot.dict_get_key("/AS".to_string()).name().ok()?;
+    let selected = normal.dict_get_key(state);
+
+    selected.is(QPDFIsObjectType::Stream).then_some(selected)
+}
+
+// Maps the transformed appearance box onto the annotation rectangle
+fn placement(appearance: &QPDFObjectHandler, rect: &Rect) -> Option<Matrix> {
+    let dict = appearance.dict();
+    let bbox: Rect = dict.dict_get_key("/BBox".to_string()).try_into().ok()?;
+    let matrix: Matrix = dict
+        .dict_get_key("/Matrix".to_string())
+        .try_into()
+        .unwrap_or_default();
+
+    let bounds = bbox.transform(&matrix);
+    if bounds.width() == 0.0 || bounds.height() == 0.0 {
+        return None;
+    }
+
+    let (sx, sy) = (
+        rect.width() / bounds.width(),
+        rect.height() / bounds.height(),
+    );
+
+    Some(Matrix::new(
+        sx,
+        0.0,
+        0.0,
+        sy,
+        rect.llx - sx * bounds.llx,
+        rect.lly - sy * bounds.lly,
+    ))
+}
+
+fn color_array(factory: &QPDFObjectHandler, color: [f64; 3]) -> QPDFObjectHandler {
+    let array = factory.set(QPDFModifyObjectTypes::Array);
+    for v in color {
+        array.array_append(factory.set(QPDFModifyObjectTypes::Real(v, 4)));
+    }
+
+    array
+}
+
+// Links are drawn without a border
+fn border(factory: &QPDFObjectHandler) -> QPDFObjectHandler {
+    let array = factory.set(QPDFModifyObjectTypes::Array);
+    for _ in 0..3 {
+        array.array_append(factory.set(QPDFModifyObjectTypes::Integer(0)));
+    }
+
+    array
+}
+
+pub(crate) fn collect_annotations(page: &QPDFObjectHandler) -> Vec<QPDFAnnotation> {
+    let annots = page.dict_get_key("/Annots".to_string());
+
+    // Entries that are not dictionaries are invalid and skipped
+    (0..annots.array_len())
+        .filter_map(|at| QPDFAnnotation::from_object(annots.array_get_at(at)).ok())
+        .collect()
+}
+
+pub mod types;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,252 @@
+use std::path::PathBuf;
+
+use super::{
+    QPDFAnnotation,
+    types::{
+        QPDFAnnotationFlags, QPDFAnnotationFlattenScope, QPDFAnnotationKind, QPDFAnnotationParams,
+    },
+};
+use crate::qpdf::{
+    QPDF,
+    geometry::Rect,
+    object::{QPDFObjectHandler, types::QPDFModifyObjectTypes},
+    outline::types::QPDFDestinationFit,
+    page::QPDFPage,
+    read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+#[test]
+fn decode_annotation_flags() {
+    let flags = QPDFAnnotationFlags::from(4 | 2 | 512);
+    assert!(flags.print);
+    assert!(flags.hidden);
+    assert!(flags.locked_contents);
+    assert!(!flags.invisible && !flags.read_only && !flags.no_view);
+
+    for flags in [0, 1, 4, 0x3ff, 0x128] {
+        assert_eq!(flags, i64::from(QPDFAnnotationFlags::from(flags)));
+    }
+}
+
+#[test]
+fn enumerate_annotations() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+    assert!(
+        qpdf.annotations()
+            .unwrap()
+            .iter()
+            .all(|page| page.is_empty())
+    );
+
+    let page = QPDFPage::from(qpdf.get_page(1).unwrap());
+    let object = page.object();
+    let set = |dict: &QPDFObjectHandler, key: &str, value| {
+        dict.dict_replace_key(key.to_string(), object.set(value))
+    };
+
+    let annot = object.set(QPDFModifyObjectTypes::Dictionary);
+    set(
+        &annot,
+        "/Subtype",
+        QPDFModifyObjectTypes::Name("/Text".to_string()),
+    );
+    set(
+        &annot,
+        "/Contents",
+        QPDFModifyObjectTypes::String("Check figures".to_string()),
+    );
+    set(
+        &annot,
+        "/NM",
+        QPDFModifyObjectTypes::String("note-1".to_string()),
+    );
+    set(
+        &annot,
+        "/M",
+        QPDFModifyObjectTypes::String("D:20240101120000Z".to_string()),
+    );
+    set(
+        &annot,
+        "/AS",
+        QPDFModifyObjectTypes::Name("/Off".to_string()),
+    );
+    set(&annot, "/F", QPDFModifyObjectTypes::Integer(6));
+    annot.dict_replace_key(
+        "/Rect".to_string(),
+        Rect::new(10.0, 20.0, 30.0, 40.0).to_object(object),
+    );
+
+    let annots = object.set(QPDFModifyObjectTypes::Array);
+    annots.array_append(annot.make_indirect().unwrap());
+    annots.array_append(object.set(QPDFModifyObjectTypes::Null));
+    object.dict_replace_key("/Annots".to_string(), annots);
+
+    let found = page.annotations();
+    assert_eq!(1, found.len());
+
+    let info = found[0].info();
+    assert_eq!("/Text", info.subtype);
+    assert_eq!(Some(Rect::new(10.0, 20.0, 30.0, 40.0)), info.rect);
+    assert_eq!(Some("Check figures".to_string()), info.contents);
+    assert_eq!(Some("note-1".to_string()), info.name);
+    assert_eq!(Some("D:20240101120000Z".to_string()), info.modified);
+    assert_eq!(Some("/Off".to_string()), info.appearance_state);
+    assert!(info.flags.print && info.flags.hidden);
+
+    assert_eq!(1, qpdf.annotations().unwrap()[1].len());
+    assert!(QPDFAnnotation::from_object(object.set(QPDFModifyObjectTypes::Null)).is_err());
+}
+
+#[test]
+fn create_and_remove_annotations() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let rect = Rect::new(50.0, 700.0, 200.0, 720.0);
+    let link = QPDFAnnotationParams::new(
+        QPDFAnnotationKind::LinkUri("https://example.com".to_string()),
+        rect,
+    );
+    let goto = QPDFAnnotationParams::new(
+        QPDFAnnotationKind::LinkPage(2, QPDFDestinationFit::Fit),
+        rect,
+    );
+    let note = QPDFAnnotationParams::new(QPDFAnnotationKind::Text("/Comment".to_string()), rect)
+        .with_contents("Please confirm")
+        .with_name("review-1")
+        .with_color([1.0, 1.0, 0.0]);
+    let highlight = QPDFAnnotationParams::new(
+        QPDFAnnotationKind::Highlight(vec![Rect::new(56.0, 764.0, 282.0, 785.0)]),
+        Rect::new(56.0, 764.0, 282.0, 785.0),
+    );
+
+    let created = qpdf.add_annotation(0, &link).unwrap();
+    assert_eq!("/Link", created.info().subtype);
+    assert!(created.info().flags.print);
+    assert_eq!(
+        0,
+        qpdf.find_page_by_handler(created.object().dict_get_key("/P".to_string()))
+    );
+
+    let goto = qpdf.add_annotation(0, &goto).unwrap();
+    let dest = goto.object().dict_get_key("/Dest".to_string());
+    assert_eq!(2, qpdf.find_page_by_handler(dest.array_get_at(0)));
+
+    let mut note = qpdf.add_annotation(0, &note).unwrap();
+    assert_eq!(Some("Please confirm".to_string()), note.info().contents);
+
+    note.set_contents(Some("Confirmed"));
+    note.set_flags(QPDFAnnotationFlags {
+        hidden: true,
+        ..Default::default()
+    });
+    assert_eq!(Some("Confirmed".to_string()), note.info().contents);
+    assert!(note.info().flags.hidden && !note.info().flags.print);
+
+    let highlight = qpdf.add_annotation(0, &highlight).unwrap();
+    let quads = highlight.object().dict_get_key("/QuadPoints".to_string());
+    assert_eq!(8, quads.array_len());
+
+    assert!(
+        qpdf.add_annotation(
+            0,
+            &QPDFAnnotationParams::new(
+                QPDFAnnotationKind::LinkPage(7, QPDFDestinationFit::Fit),
+                rect
+            )
+        )
+        .is_err()
+    );
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    assert_eq!(4, page.annotations().len());
+
+    let removed = qpdf
+        .remove_annotations(|annot| annot.info().subtype == "/Link")
+        .unwrap();
+    assert_eq!(2, removed);
+
+    let left: Vec<String> = page
+        .annotations()
+        .iter()
+        .map(|annot| annot.info().subtype.clone())
+        .collect();
+    assert_eq!(vec!["/Text", "/Highlight"], left);
+}
+
+#[test]
+fn flatten_scope_flags() {
+    let print = QPDFAnnotationFlags::from(4);
+    let screen_only = QPDFAnnotationFlags::from(0);
+    let no_view = QPDFAnnotationFlags::from(4 | 32);
+    let hidden = QPDFAnnotationFlags::from(4 | 2);
+
+    assert!(print.is_shown(QPDFAnnotationFlattenScope::Print));
+    assert!(!screen_only.is_shown(QPDFAnnotationFlattenScope::Print));
+    assert!(screen_only.is_shown(QPDFAnnotationFlattenScope::Screen));
+    assert!(!no_view.is_shown(QPDFAnnotationFlattenScope::Screen));
+    assert!(no_view.is_shown(QPDFAnnotationFlattenScope::All));
+    assert!(!hidden.is_shown(QPDFAnnotationFlattenScope::All));
+}
+
+#[test]
+fn flatten_annotation_appearances() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let with_appearance = |rect: Rect, flags: i64| {
+        let params = QPDFAnnotationParams::new(QPDFAnnotationKind::Square, rect)
+            .with_flags(QPDFAnnotationFlags::from(flags));
+        let annot = qpdf.add_annotation(0, &params).unwrap();
+
+        let stream = page.new_content_stream(b"0 0 10 10 re f");
+        stream.dict().dict_replace_key(
+            "/BBox".to_string(),
+            Rect::new(0.0, 0.0, 10.0, 10.0).to_object(page.object()),
+        );
+
+        let ap = page.object().set(QPDFModifyObjectTypes::Dictionary);
+        ap.dict_replace_key("/N".to_string(), stream);
+        annot.object().dict_replace_key("/AP".to_string(), ap);
+    };
+
+    with_appearance(Rect::new(100.0, 100.0, 120.0, 140.0), 4);
+    with_appearance(Rect::new(200.0, 200.0, 210.0, 210.0), 0);
+    with_appearance(Rect::new(300.0, 300.0, 310.0, 310.0), 4 | 2);
+    qpdf.add_annotation(
+        0,
+        &QPDFAnnotationParams::new(
+            QPDFAnnotationKind::Text("/Note".to_string()),
+            Rect::new(0.0, 0.0, 20.0, 20.0),
+        ),
+    )
+    .unwrap();
+
+    let flattened = qpdf
+        .flatten_annotations(QPDFAnnotationFlattenScope::Print)
+        .unwrap();
+    assert_eq!(3, flattened);
+
+    let left = page.annotations();
+    assert_eq!(1, left.len());
+    assert_eq!("/Text", left[0].info().subtype);
+
+    let content = String::from_utf8(page.content_data().unwrap()).unwrap();
+    assert!(content.contains("q\n2 0 0 4 100 100 cm\n/X1 Do\nQ\n"));
+    assert!(!content.contains("/X2"));
+
+    let xobjects = page
+        .resources()
+        .unwrap()
+        .dict_get_key("/XObject".to_string());
+    assert!(xobjects.dict_has_key("/X1".to_string()));
+}
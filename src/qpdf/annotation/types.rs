@@ -0,0 +1,183 @@
+use crate::qpdf::{
+    geometry::Rect,
+    object::types::{Generation, ObjectId},
+    outline::types::QPDFDestinationFit,
+};
+
+// Decoded /F entry, one field per pdf_annotation_flag_e bit
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QPDFAnnotationFlags {
+    pub invisible: bool,
+    pub hidden: bool,
+    pub print: bool,
+    pub no_zoom: bool,
+    pub no_rotate: bool,
+    pub no_view: bool,
+    pub read_only: bool,
+    pub locked: bool,
+    pub toggle_no_view: bool,
+    pub locked_contents: bool,
+}
+
+// Which annotations are drawn when flattening, mirroring qpdf's flatten-annotations. Form field
+// widgets are only flattened document-wide, where their fields can be removed from the form.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum QPDFAnnotationFlattenScope {
+    #[default]
+    All,
+    Print,
+    Screen,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFAnnotationInfo {
+    pub object_id: ObjectId,
+    pub generation: Generation,
+    pub subtype: String,
+    pub rect: Option<Rect>,
+    pub contents: Option<String>,
+    pub name: Option<String>,
+    pub modified: Option<String>,
+    pub appearance_state: Option<String>,
+    pub flags: QPDFAnnotationFlags,
+}
+
+impl From<i64> for QPDFAnnotationFlags {
+    fn from(flags: i64) -> Self {
+        let bit = |n: u32| flags & (1 << n) != 0;
+
+        Self {
+            invisible: bit(0),
+            hidden: bit(1),
+            print: bit(2),
+            no_zoom: bit(3),
+            no_rotate: bit(4),
+            no_view: bit(5),
+            read_only: bit(6),
+            locked: bit(7),
+            toggle_no_view: bit(8),
+            locked_contents: bit(9),
+        }
+    }
+}
+
+impl QPDFAnnotationFlags {
+    pub fn is_shown(&self, scope: QPDFAnnotationFlattenScope) -> bool {
+        if self.invisible || self.hidden {
+            return false;
+        }
+
+        match scope {
+            QPDFAnnotationFlattenScope::All => true,
+            QPDFAnnotationFlattenScope::Print => self.print,
+            QPDFAnnotationFlattenScope::Screen => !self.no_view,
+        }
+    }
+}
+
+impl From<QPDFAnnotationFlags> for i64 {
+    fn from(flags: QPDFAnnotationFlags) -> Self {
+        [
+            flags.invisible,
+            flags.hidden,
+            flags.print,
+            flags.no_zoom,
+            flags.no_rotate,
+            flags.no_view,
+            flags.read_only,
+            flags.locked,
+            flags.toggle_no_view,
+            flags.locked_contents,
+        ]
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (n, set)| acc | (*set as i64) << n)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QPDFAnnotationKind {
+    LinkUri(String),
+    LinkPage(usize, QPDFDestinationFit),
+    // Sticky note with its icon name, such as /Note or /Comment
+    Text(String),
+    FreeText(f64),
+    // Markup annotations take the rectangles of the text they cover
+    Highlight(Vec<Rect>),
+    Underline(Vec<Rect>),
+    StrikeOut(Vec<Rect>),
+    Square,
+    Circle,
+    Stamp(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct QPDFAnnotationParams {
+    pub(crate) kind: QPDFAnnotationKind,
+    pub(crate) rect: Rect,
+    pub(crate) contents: Option<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) modified: Option<String>,
+    pub(crate) color: Option<[f64; 3]>,
+    pub(crate) flags: QPDFAnnotationFlags,
+}
+
+impl QPDFAnnotationKind {
+    pub(crate) fn subtype(&self) -> &'static str {
+        match self {
+            QPDFAnnotationKind::LinkUri(_) | QPDFAnnotationKind::LinkPage(..) => "/Link",
+            QPDFAnnotationKind::Text(_) => "/Text",
+            QPDFAnnotationKind::FreeText(_) => "/FreeText",
+            QPDFAnnotationKind::Highlight(_) => "/Highlight",
+            QPDFAnnotationKind::Underline(_) => "/Underline",
+            QPDFAnnotationKind::StrikeOut(_) => "/StrikeOut",
+            QPDFAnnotationKind::Square => "/Square",
+            QPDFAnnotationKind::Circle => "/Circle",
+            QPDFAnnotationKind::Stamp(_) => "/Stamp",
+        }
+    }
+}
+
+// Annotation Params Construction
+impl QPDFAnnotationParams {
+    // Annotations are printable unless other flags are given
+    pub fn new(kind: QPDFAnnotationKind, rect: Rect) -> Self {
+        Self {
+            kind,
+            rect,
+            contents: None,
+            name: None,
+            modified: None,
+            color: None,
+            flags: QPDFAnnotationFlags {
+                print: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn with_contents(mut self, contents: &str) -> Self {
+        self.contents = Some(contents.to_string());
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn with_modified(mut self, modified: &str) -> Self {
+        self.modified = Some(modified.to_string());
+        self
+    }
+
+    pub fn with_color(mut self, color: [f64; 3]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_flags(mut self, flags: QPDFAnnotationFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+}
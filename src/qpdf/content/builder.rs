@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+
+use crate::qpdf::{
+    font::encoding::WIN_ANSI_ENCODING,
+    geometry::{Matrix, Rect, format_number},
+    object::{
+        QPDFObjectHandler,
+        types::{QPDFIsObjectType, QPDFModifyObjectTypes},
+    },
+    page::QPDFPage,
+};
+
+pub struct QPDFContentBuilder {
+    data: Vec<u8>,
+    resources: BuilderResources,
+    standard_fonts: HashMap<String, String>,
+}
+
+// Page resources are only made writable once something has to be registered in them
+enum BuilderResources {
+    Direct(QPDFObjectHandler),
+    Page(QPDFObjectHandler),
+}
+
+// Construction
+impl QPDFContentBuilder {
+    pub fn new(resources: QPDFObjectHandler) -> Self {
+        Self {
+            data: Vec::new(),
+            resources: BuilderResources::Direct(resources),
+            standard_fonts: HashMap::new(),
+        }
+    }
+
+    pub fn for_page(page: &QPDFPage) -> Self {
+        Self {
+            data: Vec::new(),
+            resources: BuilderResources::Page(page.object().clone()),
+            standard_fonts: HashMap::new(),
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+// Resource Registration
+impl QPDFContentBuilder {
+    fn factory(&self) -> &QPDFObjectHandler {
+        match &self.resources {
+            BuilderResources::Direct(resources) => resources,
+            BuilderResources::Page(page) => page,
+        }
+    }
+
+    fn current_category(&self, category: &str) -> Option<QPDFObjectHandler> {
+        let resources = match &self.resources {
+            BuilderResources::Direct(resources) => Some(resources.clone()),
+            BuilderResources::Page(page) => QPDFPage::from(page.clone()).resources(),
+        };
+
+        resources
+            .map(|resources| resources.dict_get_key(category.to_string()))
+            .filter(|dict| dict.is(QPDFIsObjectType::Dictionary))
+    }
+
+    fn resource_category(&mut self, category: &str) -> QPDFObjectHandler {
+        if let BuilderResources::Page(page) = &self.resources {
+            let resources = QPDFPage::from(page.clone()).resources_mut();
+            self.resources = BuilderResources::Direct(resources);
+        }
+
+        let resources = self.factory();
+        let dict = resources.dict_get_key(category.to_string());
+
+        if dict.is(QPDFIsObjectType::Dictionary) {
+            return dict;
+        }
+
+        resources.dict_replace_key(
+            category.to_string(),
+            resources.set(QPDFModifyObjectTypes::Dictionary),
+        );
+        resources.dict_get_key(category.to_string())
+    }
+
+    pub fn register_resource(
+        &mut self,
+        category: &str,
+        prefix: &str,
+        object: &QPDFObjectHandler,
+    ) -> String {
+        if object.object_id() != 0 {
+            let existing = self.current_category(category).and_then(|dict| {
+                dict.dict_keys().into_iter().find(|key| {
+                    let item = dict.dict_get_key(key.to_string());
+                    item.object_id() == object.object_id()
+                        && item.generation() == object.generation()
+                })
+            });
+
+            if let Some(key) = existing {
+                return key;
+            }
+        }
+
+        let dict = self.resource_category(category);
+        let keys = dict.dict_keys();
+
+        let name = (1..)
+            .map(|n| format!("/{prefix}{n}"))
+            .find(|name| !keys.contains(name))
+            .expect("An unused resource name");
+
+        dict.dict_replace_key(name.clone(), object.clone());
+        name
+    }
+
+    // A font the resources already hold for the same base font is used instead of adding one
+    fn existing_standard_font(&self, base_font: &str) -> Option<String> {
+        let fonts = self.current_category("/Font")?;
+        let base_font = format!("/{base_font}");
+
+        fonts.dict_keys().into_iter().find(|key| {
+            let font = fonts.dict_get_key(key.to_string());
+            let name = |key: &str, value: &str| {
+                font.dict_get_key(key.to_string())
+                    .is(QPDFIsObjectType::NameEquals(value.to_string()))
+            };
+
+            name("/BaseFont", &base_font) && name("/Encoding", "/WinAnsiEncoding")
+        })
+    }
+}
+
+// Graphics State
+impl QPDFContentBuilder {
+    fn op(&mut self, operands: &[f64], operator: &str) -> &mut Self {
+        for v in operands {
+            self.data.extend(format_number(*v).as_bytes());
+            self.data.push(b' ');
+        }
+
+        self.data.extend(operator.as_bytes());
+        self.data.push(b'\n');
+        self
+    }
+
+    pub fn raw(&mut self, data: &[u8]) -> &mut Self {
+        self.data.extend(data);
+        if !data.ends_with(b"\n") {
+            self.data.push(b'\n');
+        }
+        self
+    }
+
+    pub fn save(&mut self) -> &mut Self {
+        self.op(&[], "q")
+    }
+
+    pub fn restore(&mut self) -> &mut Self {
+        self.op(&[], "Q")
+    }
+
+    pub fn transform(&mut self, m: Matrix) -> &mut Self {
+        self.op(&[m.a, m.b, m.c, m.d, m.e, m.f], "cm")
+    }
+
+    pub fn line_width(&mut self, width: f64) -> &mut Self {
+        self.op(&[width], "w")
+    }
+
+    pub fn graphics_state(&mut self, ext_gstate: &QPDFObjectHandler) -> &mut Self {
+        let name = self.register_resource("/ExtGState", "GS", ext_gstate);
+        self.raw(format!("{name} gs").as_bytes())
+    }
+}
+
+// Paths
+impl QPDFContentBuilder {
+    pub fn move_to(&mut self, x: f64, y: f64) -> &mut Self {
+        self.op(&[x, y], "m")
+    }
+
+    pub fn line_to(&mut self, x: f64, y: f64) -> &mut Self {
+        self.op(&[x, y], "l")
+    }
+
+    pub fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x3: f64, y3: f64) -> &mut Self {
+        self.op(&[x1, y1, x2, y2, x3, y3], "c")
+    }
+
+    pub fn rect(&mut self, r: Rect) -> &mut Self {
+        self.op(&[r.llx, r.lly, r.width(), r.height()], "re")
+    }
+
+    pub fn close_path(&mut self) -> &mut Self {
+        self.op(&[], "h")
+    }
+
+    pub fn fill(&mut self) -> &mut Self {
+        self.op(&[], "f")
+    }
+
+    pub fn fill_even_odd(&mut self) -> &mut Self {
+        self.op(&[], "f*")
+    }
+
+    pub fn stroke(&mut self) -> &mut Self {
+        self.op(&[], "S")
+    }
+
+    pub fn fill_and_stroke(&mut self) -> &mut Self {
+        self.op(&[], "B")
+    }
+
+    // Clipping takes effect with the next painting operator, end_path() when nothing is drawn
+    pub fn clip(&mut self) -> &mut Self {
+        self.op(&[], "W")
+    }
+
+    pub fn clip_even_odd(&mut self) -> &mut Self {
+        self.op(&[], "W*")
+    }
+
+    pub fn end_path(&mut self) -> &mut Self {
+        self.op(&[], "n")
+    }
+}
+
+// Colours
+impl QPDFContentBuilder {
+    pub fn fill_gray(&mut self, g: f64) -> &mut Self {
+        self.op(&[g], "g")
+    }
+
+    pub fn stroke_gray(&mut self, g: f64) -> &mut Self {
+        self.op(&[g], "G")
+    }
+
+    pub fn fill_rgb(&mut self, r: f64, g: f64, b: f64) -> &mut Self {
+        self.op(&[r, g, b], "rg")
+    }
+
+    pub fn stroke_rgb(&mut self, r: f64, g: f64, b: f64) -> &mut Self {
+        self.op(&[r, g, b], "RG")
+    }
+
+    pub fn fill_cmyk(&mut self, c: f64, m: f64, y: f64, k: f64) -> &mut Self {
+        self.op(&[c, m, y, k], "k")
+    }
+
+    pub fn stroke_cmyk(&mut self, c: f64, m: f64, y: f64, k: f64) -> &mut Self {
+        self.op(&[c, m, y, k], "K")
+    }
+}
+
+// Text
+impl QPDFContentBuilder {
+    pub fn begin_text(&mut self) -> &mut Self {
+        self.op(&[], "BT")
+    }
+
+    pub fn end_text(&mut self) -> &mut Self {
+        self.op(&[], "ET")
+    }
+
+    pub fn font(&mut self, font: &QPDFObjectHandler, size: f64) -> &mut Self {
+        let name = self.register_resource("/Font", "F", font);
+        self.raw(format!("{name} {} Tf", format_number(size)).as_bytes())
+    }
+
+    pub fn standard_font(&mut self, base_font: &str, size: f64) -> &mut Self {
+        if let Some(name) = self.standard_fonts.get(base_font) {
+            let op = format!("{name} {} Tf", format_number(size));
+            return self.raw(op.as_bytes());
+        }
+
+        if let Some(name) = self.existing_standard_font(base_font) {
+            self.standard_fonts
+                .insert(base_font.to_string(), name.clone());
+            return self.raw(format!("{name} {} Tf", format_number(size)).as_bytes());
+        }
+
+        let font = self.factory().set(QPDFModifyObjectTypes::Dictionary);
+        for (key, value) in [
+            ("/Type", "/Font"),
+            ("/Subtype", "/Type1"),
+            ("/BaseFont", &format!("/{base_font}")),
+            ("/Encoding", "/WinAnsiEncoding"),
+        ] {
+            let value = font.set(QPDFModifyObjectTypes::Name(value.to_string()));
+            font.dict_replace_key(key.to_string(), value);
+        }
+
+        let font = font.make_indirect().unwrap_or(font);
+        let name = self.register_resource("/Font", "F", &font);
+        self.standard_fonts
+            .insert(base_font.to_string(), name.clone());
+
+        self.raw(format!("{name} {} Tf", format_number(size)).as_bytes())
+    }
+
+    pub fn text_position(&mut self, x: f64, y: f64) -> &mut Self {
+        self.op(&[x, y], "Td")
+    }
+
+    pub fn text_matrix(&mut self, m: Matrix) -> &mut Self {
+        self.op(&[m.a, m.b, m.c, m.d, m.e, m.f], "Tm")
+    }
+
+    pub fn text_leading(&mut self, leading: f64) -> &mut Self {
+        self.op(&[leading], "TL")
+    }
+
+    pub fn next_line(&mut self) -> &mut Self {
+        self.op(&[], "T*")
+    }
+
+    // Text is encoded for /WinAnsiEncoding fonts such as the ones standard_font() adds
+    pub fn show_text(&mut self, text: &str) -> &mut Self {
+        let bytes: Vec<u8> = text
+            .chars()
+            .map(|c| {
+                WIN_ANSI_ENCODING
+                    .iter()
+                    .position(|code| *code == Some(c))
+                    .map_or(b'?', |code| code as u8)
+            })
+            .collect();
+
+        self.show_bytes(&bytes)
+    }
+
+    pub fn show_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        let mut op = escape_string(bytes);
+        op.extend(b" Tj");
+        self.raw(&op)
+    }
+}
+
+// External Objects
+impl QPDFContentBuilder {
+    pub fn draw_xobject(&mut self, xobject: &QPDFObjectHandler) -> &mut Self {
+        let name = self.register_resource("/XObject", "X", xobject);
+        self.raw(format!("{name} Do").as_bytes())
+    }
+}
+
+pub(crate) fn escape_string(bytes: &[u8]) -> Vec<u8> {
+    let mut out = vec![b'('];
+
+    for &c in bytes {
+        match c {
+            b'(' | b')' | b'\\' => out.extend([b'\\', c]),
+            b'\n' => out.extend(b"\\n"),
+            b'\r' => out.extend(b"\\r"),
+            0x20..=0x7e => out.push(c),
+            _ => out.extend(format!("\\{c:03o}").as_bytes()),
+        }
+    }
+
+    out.push(b')');
+    out
+}
@@ -0,0 +1,447 @@
+use std::collections::BTreeMap;
+
+use types::{QPDFContentOperation, QPDFContentValue};
+
+use super::QPDFErrors;
+
+// Guards against stack exhaustion on deeply nested arrays and dictionaries
+const MAX_NESTING: usize = 256;
+
+pub struct QPDFContentParser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    pending: Option<QPDFContentOperation>,
+}
+
+enum Token {
+    Value(QPDFContentValue),
+    Keyword(String),
+    ArrayStart,
+    ArrayEnd,
+    DictStart,
+    DictEnd,
+}
+
+impl<'a> QPDFContentParser<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            pending: None,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl Iterator for QPDFContentParser<'_> {
+    type Item = Result<QPDFContentOperation, QPDFErrors>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(operation) = self.pending.take() {
+            return Some(Ok(operation));
+        }
+
+        let mut operands = Vec::new();
+
+        loop {
+            let result = match self.next_token()? {
+                // Inline images take no operands, stray ones are reported before the image itself
+                // and parsing carries on
+                Ok(Token::Keyword(k)) if k == "BI" => match self.read_inline_image() {
+                    Ok(image) if !operands.is_empty() => {
+                        self.pending = Some(image);
+                        return Some(Err(QPDFErrors::InvalidContent));
+                    }
+                    result => result,
+                },
+                Ok(Token::Keyword(k)) => {
+                    return Some(Ok(QPDFContentOperation::Operator(k, operands)));
+                }
+                Ok(token) => match self.read_value(token, 0) {
+                    Ok(value) => {
+                        operands.push(value);
+                        continue;
+                    }
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            };
+
+            if result.is_err() {
+                self.pos = self.data.len();
+            }
+
+            return Some(result);
+        }
+    }
+}
+
+// Lexer
+impl QPDFContentParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if is_whitespace(c) {
+                self.pos += 1;
+            } else if c == b'%' {
+                while let Some(c) = self.peek() {
+                    if c == b'\r' || c == b'\n' {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn read_regular(&mut self) -> &[u8] {
+        let start = self.pos;
+
+        while let Some(c) = self.peek() {
+            if is_whitespace(c) || is_delimiter(c) {
+                break;
+            }
+            self.pos += 1;
+        }
+
+        &self.data[start..self.pos]
+    }
+
+    fn next_token(&mut self) -> Option<Result<Token, QPDFErrors>> {
+        self.skip_whitespace();
+        let c = self.peek()?;
+
+        let token = match c {
+            b'[' => {
+                self.pos += 1;
+                Ok(Token::ArrayStart)
+            }
+            b']' => {
+                self.pos += 1;
+                Ok(Token::ArrayEnd)
+            }
+            b'<' if self.data.get(self.pos + 1) == Some(&b'<') => {
+                self.pos += 2;
+                Ok(Token::DictStart)
+            }
+            b'>' if self.data.get(self.pos + 1) == Some(&b'>') => {
+                self.pos += 2;
+                Ok(Token::DictEnd)
+            }
+            b'<' => {
+                self.pos += 1;
+                self.read_hex_string()
+                    .map(|s| Token::Value(QPDFContentValue::String(s)))
+            }
+            b'(' => {
+                self.pos += 1;
+                self.read_literal_string()
+                    .map(|s| Token::Value(QPDFContentValue::String(s)))
+            }
+            b'/' => {
+                self.pos += 1;
+                Ok(Token::Value(QPDFContentValue::Name(self.read_name())))
+            }
+            b'{' | b'}' => {
+                self.pos += 1;
+                Ok(Token::Keyword((c as char).to_string()))
+            }
+            b')' | b'>' => Err(QPDFErrors::InvalidContent),
+            _ => {
+                let word = String::from_utf8_lossy(self.read_regular()).to_string();
+                Ok(keyword_or_number(word))
+            }
+        };
+
+        Some(token)
+    }
+
+    fn read_name(&mut self) -> String {
+        let raw = self.read_regular().to_vec();
+        let mut name = vec![b'/'];
+
+        let mut i = 0;
+        while i < raw.len() {
+            let decoded = match raw[i] {
+                b'#' => raw
+                    .get(i + 1..i + 3)
+                    .and_then(|h| std::str::from_utf8(h).ok())
+                    .and_then(|h| u8::from_str_radix(h, 16).ok()),
+                _ => None,
+            };
+
+            match decoded {
+                Some(c) => {
+                    name.push(c);
+                    i += 3;
+                }
+                None => {
+                    name.push(raw[i]);
+                    i += 1;
+                }
+            }
+        }
+
+        String::from_utf8_lossy(&name).to_string()
+    }
+
+    fn read_literal_string(&mut self) -> Result<Vec<u8>, QPDFErrors> {
+        let mut out = Vec::new();
+        let mut depth = 1;
+
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+
+            match c {
+                b'\\' => {
+                    let Some(e) = self.peek() else { break };
+                    self.pos += 1;
+
+                    match e {
+                        b'n' => out.push(b'\n'),
+                        b'r' => out.push(b'\r'),
+                        b't' => out.push(b'\t'),
+                        b'b' => out.push(0x08),
+                        b'f' => out.push(0x0c),
+                        b'\r' => {
+                            if self.peek() == Some(b'\n') {
+                                self.pos += 1;
+                            }
+                        }
+                        b'\n' => (),
+                        b'0'..=b'7' => {
+                            let mut v = (e - b'0') as u32;
+                            for _ in 0..2 {
+                                match self.peek() {
+                                    Some(d @ b'0'..=b'7') => {
+                                        v = v * 8 + (d - b'0') as u32;
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            out.push(v as u8);
+                        }
+                        _ => out.push(e),
+                    }
+                }
+                b'(' => {
+                    depth += 1;
+                    out.push(c);
+                }
+                b')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(out);
+                    }
+                    out.push(c);
+                }
+                b'\r' => {
+                    if self.peek() == Some(b'\n') {
+                        self.pos += 1;
+                    }
+                    out.push(b'\n');
+                }
+                _ => out.push(c),
+            }
+        }
+
+        Err(QPDFErrors::InvalidContent)
+    }
+
+    fn read_hex_string(&mut self) -> Result<Vec<u8>, QPDFErrors> {
+        let mut digits = Vec::new();
+
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+
+            match c {
+                b'>' => {
+                    if digits.len() % 2 == 1 {
+                        digits.push(0);
+                    }
+
+                    return Ok(digits.chunks(2).map(|d| d[0] << 4 | d[1]).collect());
+                }
+                _ if is_whitespace(c) => (),
+                _ => match (c as char).to_digit(16) {
+                    Some(d) => digits.push(d as u8),
+                    None => return Err(QPDFErrors::InvalidContent),
+                },
+            }
+        }
+
+        Err(QPDFErrors::InvalidContent)
+    }
+}
+
+// Parser
+impl QPDFContentParser<'_> {
+    fn read_value(&mut self, token: Token, depth: usize) -> Result<QPDFContentValue, QPDFErrors> {
+        if depth > MAX_NESTING {
+            return Err(QPDFErrors::InvalidContent);
+        }
+
+        match token {
+            Token::Value(v) => Ok(v),
+            Token::ArrayStart => {
+                let mut items = Vec::new();
+
+                loop {
+                    match self.next_token() {
+                        Some(Ok(Token::ArrayEnd)) => return Ok(QPDFContentValue::Array(items)),
+                        Some(Ok(token)) => items.push(self.read_value(token, depth + 1)?),
+                        Some(Err(e)) => return Err(e),
+                        None => return Err(QPDFErrors::InvalidContent),
+                    }
+                }
+            }
+            Token::DictStart => {
+                let mut dict = BTreeMap::new();
+
+                loop {
+                    let key = match self.next_token() {
+                        Some(Ok(Token::DictEnd)) => return Ok(QPDFContentValue::Dictionary(dict)),
+                        Some(Ok(Token::Value(QPDFContentValue::Name(key)))) => key,
+                        Some(Err(e)) => return Err(e),
+                        _ => return Err(QPDFErrors::InvalidContent),
+                    };
+
+                    let value = match self.next_token() {
+                        Some(Ok(token)) => self.read_value(token, depth + 1)?,
+                        Some(Err(e)) => return Err(e),
+                        None => return Err(QPDFErrors::InvalidContent),
+                    };
+
+                    dict.insert(key, value);
+                }
+            }
+            _ => Err(QPDFErrors::InvalidContent),
+        }
+    }
+
+    fn read_inline_image(&mut self) -> Result<QPDFContentOperation, QPDFErrors> {
+        let mut dict = BTreeMap::new();
+
+        loop {
+            match self.next_token() {
+                Some(Ok(Token::Keyword(k))) if k == "ID" => break,
+                Some(Ok(Token::Value(QPDFContentValue::Name(key)))) => {
+                    let value = match self.next_token() {
+                        Some(Ok(token)) => self.read_value(token, 0)?,
+                        Some(Err(e)) => return Err(e),
+                        None => return Err(QPDFErrors::InvalidContent),
+                    };
+
+                    dict.insert(key, value);
+                }
+                Some(Err(e)) => return Err(e),
+                _ => return Err(QPDFErrors::InvalidContent),
+            }
+        }
+
+        // A single whitespace character separates ID from the image data
+        if self.peek().is_some_and(is_whitespace) {
+            self.pos += 1;
+        }
+
+        let length = dict
+            .get("/L")
+            .or_else(|| dict.get("/Length"))
+            .and_then(|v| v.as_number());
+
+        let data = match length {
+            Some(length) => {
+                let end = (self.pos + length.max(0.0) as usize).min(self.data.len());
+                let data = self.data[self.pos..end].to_vec();
+                self.pos = end;
+
+                match self.next_token() {
+                    Some(Ok(Token::Keyword(k))) if k == "EI" => data,
+                    _ => return Err(QPDFErrors::InvalidContent),
+                }
+            }
+            None => self.read_until_ei()?,
+        };
+
+        Ok(QPDFContentOperation::InlineImage(dict, data))
+    }
+
+    fn read_until_ei(&mut self) -> Result<Vec<u8>, QPDFErrors> {
+        let start = self.pos;
+        let mut i = start;
+
+        while i + 1 < self.data.len() {
+            let bounded_before = i == start || is_whitespace(self.data[i - 1]);
+            let bounded_after = self
+                .data
+                .get(i + 2)
+                .is_none_or(|&c| is_whitespace(c) || is_delimiter(c));
+
+            if &self.data[i..i + 2] == b"EI" && bounded_before && bounded_after {
+                let end = if i > start { i - 1 } else { i };
+                self.pos = i + 2;
+
+                return Ok(self.data[start..end].to_vec());
+            }
+
+            i += 1;
+        }
+
+        Err(QPDFErrors::InvalidContent)
+    }
+}
+
+// Malformed numbers such as `--5` are kept as keywords, as qpdf's tokenizer does
+fn keyword_or_number(word: String) -> Token {
+    let numeric = word
+        .bytes()
+        .all(|c| c.is_ascii_digit() || matches!(c, b'+' | b'-' | b'.'));
+
+    if !numeric {
+        let value = match word.as_str() {
+            "true" => QPDFContentValue::Bool(true),
+            "false" => QPDFContentValue::Bool(false),
+            "null" => QPDFContentValue::Null,
+            _ => return Token::Keyword(word),
+        };
+
+        return Token::Value(value);
+    }
+
+    if !word.contains('.')
+        && let Ok(v) = word.parse::<i64>()
+    {
+        return Token::Value(QPDFContentValue::Integer(v));
+    }
+
+    match word.parse::<f64>() {
+        Ok(v) => Token::Value(QPDFContentValue::Real(v)),
+        Err(_) => Token::Keyword(word),
+    }
+}
+
+pub(crate) fn is_whitespace(c: u8) -> bool {
+    matches!(c, b'\0' | b'\t' | b'\n' | b'\x0c' | b'\r' | b' ')
+}
+
+pub(crate) fn is_delimiter(c: u8) -> bool {
+    matches!(
+        c,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
+
+pub mod builder;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,223 @@
+use std::path::PathBuf;
+
+use super::{
+    QPDFContentParser,
+    builder::{QPDFContentBuilder, escape_string},
+    types::{QPDFContentOperation, QPDFContentValue},
+};
+use crate::qpdf::{
+    QPDF,
+    geometry::{Matrix, Rect},
+    page::QPDFPage,
+    read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+fn parse(data: &[u8]) -> Vec<QPDFContentOperation> {
+    QPDFContentParser::new(data)
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn parse_operators_with_operands() {
+    let ops = parse(b"q 1 0 0 1 56.5 -7 cm /F1 12 Tf Q");
+
+    assert_eq!(4, ops.len());
+    assert_eq!(
+        QPDFContentOperation::Operator(
+            "cm".to_string(),
+            vec![
+                QPDFContentValue::Integer(1),
+                QPDFContentValue::Integer(0),
+                QPDFContentValue::Integer(0),
+                QPDFContentValue::Integer(1),
+                QPDFContentValue::Real(56.5),
+                QPDFContentValue::Integer(-7),
+            ]
+        ),
+        ops[1]
+    );
+    assert_eq!(
+        QPDFContentOperation::Operator(
+            "Tf".to_string(),
+            vec![
+                QPDFContentValue::Name("/F1".to_string()),
+                QPDFContentValue::Integer(12)
+            ]
+        ),
+        ops[2]
+    );
+}
+
+#[test]
+fn parse_strings_and_arrays() {
+    let ops = parse(b"[(a\\(b\\)) -120 <48 656C6C6F> (\\101\\n)] TJ % comment\n");
+
+    let QPDFContentOperation::Operator(op, operands) = &ops[0] else {
+        panic!("Expected operator")
+    };
+
+    assert_eq!("TJ", op);
+    assert_eq!(
+        vec![QPDFContentValue::Array(vec![
+            QPDFContentValue::String(b"a(b)".to_vec()),
+            QPDFContentValue::Integer(-120),
+            QPDFContentValue::String(b"Hello".to_vec()),
+            QPDFContentValue::String(b"A\n".to_vec()),
+        ])],
+        *operands
+    );
+}
+
+#[test]
+fn parse_dictionary_and_names() {
+    let ops = parse(b"/Span << /ActualText (x) /Lang /en#2DUS >> BDC EMC");
+
+    let QPDFContentOperation::Operator(_, operands) = &ops[0] else {
+        panic!("Expected operator")
+    };
+
+    let QPDFContentValue::Dictionary(dict) = &operands[1] else {
+        panic!("Expected dictionary")
+    };
+
+    assert_eq!(Some("/en-US"), dict["/Lang"].as_name());
+    assert_eq!(2, ops.len());
+}
+
+#[test]
+fn parse_inline_image() {
+    let ops = parse(b"q BI /W 2 /H 1 /BPC 8 /CS /G ID \x00\xff EI Q");
+
+    assert_eq!(3, ops.len());
+
+    let QPDFContentOperation::InlineImage(dict, data) = &ops[1] else {
+        panic!("Expected inline image")
+    };
+
+    assert_eq!(Some(2.0), dict["/W"].as_number());
+    assert_eq!(vec![0x00, 0xff], *data);
+}
+
+#[test]
+fn parse_past_malformed_tokens() {
+    let ops = parse(b"--5 1.2.3 0 w");
+
+    assert_eq!(
+        vec![
+            QPDFContentOperation::Operator("--5".to_string(), vec![]),
+            QPDFContentOperation::Operator("1.2.3".to_string(), vec![]),
+            QPDFContentOperation::Operator("w".to_string(), vec![QPDFContentValue::Integer(0)]),
+        ],
+        ops
+    );
+
+    let results: Vec<_> = QPDFContentParser::new(b"1 2 BI /W 1 /H 1 ID \x00 EI 5 w").collect();
+
+    assert_eq!(3, results.len());
+    assert!(results[0].is_err());
+    assert!(matches!(
+        results[1],
+        Ok(QPDFContentOperation::InlineImage(_, _))
+    ));
+    assert!(results[2].is_ok());
+}
+
+#[test]
+fn parse_invalid_content() {
+    let result: Result<Vec<_>, _> = QPDFContentParser::new(b"(unterminated Tj").collect();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn parse_page_content() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let ops = page.content_operations().unwrap();
+
+    assert!(ops.contains(&QPDFContentOperation::Operator(
+        "Tj".to_string(),
+        vec![QPDFContentValue::String(b"THIS".to_vec())]
+    )));
+}
+
+#[test]
+fn escape_builder_strings() {
+    assert_eq!(b"(a\\(b\\)\\\\)".to_vec(), escape_string(b"a(b)\\"));
+    assert_eq!(b"(\\001\\377)".to_vec(), escape_string(&[1, 255]));
+}
+
+#[test]
+fn build_page_content() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let mut builder = QPDFContentBuilder::for_page(&page);
+
+    builder
+        .save()
+        .transform(Matrix::translate(10.0, 20.0))
+        .fill_rgb(1.0, 0.0, 0.0)
+        .rect(Rect::new(0.0, 0.0, 100.0, 50.0))
+        .fill()
+        .begin_text()
+        .standard_font("Helvetica", 12.0)
+        .text_position(72.0, 72.0)
+        .show_text("Cover (draft)")
+        .end_text()
+        .restore();
+
+    let ops: Vec<_> = QPDFContentParser::new(builder.data())
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(11, ops.len());
+    assert_eq!(
+        QPDFContentOperation::Operator(
+            "Tf".to_string(),
+            vec![
+                QPDFContentValue::Name("/F1".to_string()),
+                QPDFContentValue::Integer(12)
+            ]
+        ),
+        ops[6]
+    );
+
+    let fonts = page.resources().unwrap().dict_get_key("/Font".to_string());
+    assert!(fonts.dict_has_key("/TT1".to_string()));
+    assert!(fonts.dict_has_key("/F1".to_string()));
+
+    page.replace_content(builder.data());
+    assert_eq!(builder.data(), page.content_data().unwrap());
+}
+
+#[test]
+fn build_clipping_and_win_ansi_text() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let mut builder = QPDFContentBuilder::for_page(&page);
+
+    builder
+        .rect(Rect::new(0.0, 0.0, 10.0, 10.0))
+        .clip_even_odd()
+        .stroke()
+        .show_text("\u{201c}A\u{201d} \u{2013} \u{20ac}5\u{e9}\u{3b1}");
+
+    assert_eq!(
+        b"0 0 10 10 re\nW*\nS\n(\\223A\\224 \\226 \\2005\\351?) Tj\n".to_vec(),
+        builder.into_data()
+    );
+}
@@ -0,0 +1,43 @@
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QPDFContentValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Real(f64),
+    String(Vec<u8>),
+    Name(String),
+    Array(Vec<QPDFContentValue>),
+    Dictionary(BTreeMap<String, QPDFContentValue>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QPDFContentOperation {
+    Operator(String, Vec<QPDFContentValue>),
+    InlineImage(BTreeMap<String, QPDFContentValue>, Vec<u8>),
+}
+
+impl QPDFContentValue {
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            QPDFContentValue::Integer(v) => Some(*v as f64),
+            QPDFContentValue::Real(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_name(&self) -> Option<&str> {
+        match self {
+            QPDFContentValue::Name(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            QPDFContentValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+}
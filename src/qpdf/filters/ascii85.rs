@@ -0,0 +1,126 @@
+use super::{QPDFStreamDecode, QPDFStreamEncode};
+use crate::qpdf::{QPDFErrors, content::is_whitespace};
+
+#[derive(Default)]
+pub struct ASCII85Decoder {
+    group: Vec<u8>,
+    tilde: bool,
+    done: bool,
+}
+
+#[derive(Default)]
+pub struct ASCII85Encoder {
+    group: Vec<u8>,
+}
+
+impl ASCII85Decoder {
+    fn flush_group(&mut self, output: &mut Vec<u8>) -> Result<(), QPDFErrors> {
+        let n = self.group.len();
+
+        if n == 0 {
+            return Ok(());
+        }
+
+        if n == 1 {
+            return Err(QPDFErrors::InvalidStreamData);
+        }
+
+        // Partial groups are padded with 'u' and the extra bytes dropped
+        let value = (0..5).try_fold(0u64, |acc, i| {
+            let digit = self.group.get(i).copied().unwrap_or(84) as u64;
+            let value = acc * 85 + digit;
+            (value <= u32::MAX as u64).then_some(value)
+        });
+
+        let value = value.ok_or(QPDFErrors::InvalidStreamData)? as u32;
+        output.extend(&value.to_be_bytes()[..n - 1]);
+        self.group.clear();
+
+        Ok(())
+    }
+}
+
+impl QPDFStreamDecode for ASCII85Decoder {
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        for &c in input {
+            if self.done {
+                break;
+            }
+
+            if self.tilde {
+                if c != b'>' {
+                    return Err(QPDFErrors::InvalidStreamData);
+                }
+
+                self.done = true;
+                break;
+            }
+
+            match c {
+                b'~' => self.tilde = true,
+                b'z' if self.group.is_empty() => output.extend([0; 4]),
+                b'!'..=b'u' => {
+                    self.group.push(c - b'!');
+                    if self.group.len() == 5 {
+                        self.flush_group(output)?;
+                    }
+                }
+                _ if is_whitespace(c) => (),
+                _ => return Err(QPDFErrors::InvalidStreamData),
+            }
+        }
+
+        if end || self.done {
+            self.flush_group(output)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ASCII85Encoder {
+    fn flush_group(&mut self, output: &mut Vec<u8>) {
+        let n = self.group.len();
+
+        if n == 0 {
+            return;
+        }
+
+        if n == 4 && self.group.iter().all(|&c| c == 0) {
+            output.push(b'z');
+            self.group.clear();
+            return;
+        }
+
+        self.group.resize(4, 0);
+        let mut value =
+            u32::from_be_bytes([self.group[0], self.group[1], self.group[2], self.group[3]]);
+
+        let mut digits = [0u8; 5];
+        for d in digits.iter_mut().rev() {
+            *d = (value % 85) as u8 + b'!';
+            value /= 85;
+        }
+
+        output.extend(&digits[..n + 1]);
+        self.group.clear();
+    }
+}
+
+impl QPDFStreamEncode for ASCII85Encoder {
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        for &c in input {
+            self.group.push(c);
+            if self.group.len() == 4 {
+                self.flush_group(output);
+            }
+        }
+
+        if end {
+            self.flush_group(output);
+            output.extend(b"~>");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use super::{QPDFStreamDecode, QPDFStreamEncode};
+use crate::qpdf::{QPDFErrors, content::is_whitespace};
+
+const LINE_LENGTH: usize = 64;
+
+#[derive(Default)]
+pub struct ASCIIHexDecoder {
+    pending: Option<u8>,
+    done: bool,
+}
+
+#[derive(Default)]
+pub struct ASCIIHexEncoder {
+    column: usize,
+}
+
+impl QPDFStreamDecode for ASCIIHexDecoder {
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        for &c in input {
+            if self.done {
+                break;
+            }
+
+            let digit = match c {
+                b'>' => {
+                    self.done = true;
+                    continue;
+                }
+                _ if is_whitespace(c) => continue,
+                _ => (c as char)
+                    .to_digit(16)
+                    .ok_or(QPDFErrors::InvalidStreamData)? as u8,
+            };
+
+            match self.pending.take() {
+                Some(high) => output.push(high << 4 | digit),
+                None => self.pending = Some(digit),
+            }
+        }
+
+        // An odd trailing digit behaves as if followed by a zero
+        if (end || self.done)
+            && let Some(high) = self.pending.take()
+        {
+            output.push(high << 4);
+        }
+
+        Ok(())
+    }
+}
+
+impl QPDFStreamEncode for ASCIIHexEncoder {
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        for &c in input {
+            output.extend(format!("{c:02x}").as_bytes());
+            self.column += 2;
+
+            if self.column >= LINE_LENGTH {
+                output.push(b'\n');
+                self.column = 0;
+            }
+        }
+
+        if end {
+            output.push(b'>');
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use miniz_oxide::{
+    DataFormat, MZError, MZFlush, MZStatus,
+    deflate::{
+        core::{CompressorOxide, create_comp_flags_from_zip_params},
+        stream::deflate,
+    },
+    inflate::stream::{InflateState, inflate},
+};
+
+use super::{QPDFStreamDecode, QPDFStreamEncode};
+use crate::qpdf::QPDFErrors;
+
+pub struct FlateDecoder {
+    state: Box<InflateState>,
+    done: bool,
+}
+
+pub struct FlateEncoder {
+    state: Box<CompressorOxide>,
+}
+
+impl Default for FlateDecoder {
+    fn default() -> Self {
+        Self {
+            state: InflateState::new_boxed(DataFormat::Zlib),
+            done: false,
+        }
+    }
+}
+
+impl QPDFStreamDecode for FlateDecoder {
+    fn decode(
+        &mut self,
+        mut input: &[u8],
+        output: &mut Vec<u8>,
+        end: bool,
+    ) -> Result<(), QPDFErrors> {
+        let mut buf = [0u8; 8192];
+
+        while !self.done {
+            let flush = if end { MZFlush::Finish } else { MZFlush::None };
+            let result = inflate(&mut self.state, input, &mut buf, flush);
+
+            input = &input[result.bytes_consumed..];
+            output.extend(&buf[..result.bytes_written]);
+
+            match result.status {
+                Ok(MZStatus::StreamEnd) => self.done = true,
+                Ok(_) if input.is_empty() && result.bytes_written < buf.len() => break,
+                Ok(_) => (),
+                // A truncated stream still yields whatever was decoded so far
+                Err(MZError::Buf) if input.is_empty() => break,
+                Err(_) => return Err(QPDFErrors::InvalidStreamData),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FlateEncoder {
+    pub fn new(level: u8) -> Self {
+        let flags = create_comp_flags_from_zip_params(level.min(10) as i32, 15, 0);
+
+        Self {
+            state: Box::new(CompressorOxide::new(flags)),
+        }
+    }
+}
+
+impl Default for FlateEncoder {
+    fn default() -> Self {
+        Self::new(6)
+    }
+}
+
+impl QPDFStreamEncode for FlateEncoder {
+    fn encode(
+        &mut self,
+        mut input: &[u8],
+        output: &mut Vec<u8>,
+        end: bool,
+    ) -> Result<(), QPDFErrors> {
+        let mut buf = [0u8; 8192];
+        let flush = if end { MZFlush::Finish } else { MZFlush::None };
+
+        loop {
+            let result = deflate(&mut self.state, input, &mut buf, flush);
+
+            input = &input[result.bytes_consumed..];
+            output.extend(&buf[..result.bytes_written]);
+
+            match result.status {
+                Ok(MZStatus::StreamEnd) => break,
+                Ok(_) if !end && input.is_empty() => break,
+                Ok(_) => (),
+                Err(MZError::Buf) if !end && input.is_empty() => break,
+                Err(_) => return Err(QPDFErrors::InvalidStreamData),
+            }
+        }
+
+        Ok(())
+    }
+}
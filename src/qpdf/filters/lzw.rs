@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use super::{QPDFStreamDecode, QPDFStreamEncode};
+use crate::qpdf::QPDFErrors;
+
+const CLEAR_TABLE: usize = 256;
+const END_OF_DATA: usize = 257;
+const MAX_TABLE_SIZE: usize = 4096;
+
+pub struct LZWDecoder {
+    table: Vec<Vec<u8>>,
+    previous: Option<usize>,
+    early_change: usize,
+    bits: u32,
+    bit_count: u32,
+    done: bool,
+}
+
+pub struct LZWEncoder {
+    table: HashMap<(u16, u8), u16>,
+    next_code: usize,
+    // The table size a decoder will have when reading the next code
+    decoder_size: usize,
+    current: Option<u16>,
+    early_change: usize,
+    bits: u32,
+    bit_count: u32,
+    started: bool,
+}
+
+impl LZWDecoder {
+    pub fn new(early_change: i64) -> Self {
+        let mut decoder = Self {
+            table: Vec::with_capacity(MAX_TABLE_SIZE),
+            previous: None,
+            early_change: early_change.clamp(0, 1) as usize,
+            bits: 0,
+            bit_count: 0,
+            done: false,
+        };
+
+        decoder.reset();
+        decoder
+    }
+
+    fn reset(&mut self) {
+        self.table.clear();
+        self.table.extend((0..=255u8).map(|c| vec![c]));
+        // Placeholders for the clear-table and end-of-data codes
+        self.table.extend([Vec::new(), Vec::new()]);
+        self.previous = None;
+    }
+
+    fn code_length(&self) -> u32 {
+        code_length(self.table.len() + self.early_change)
+    }
+
+    fn handle_code(&mut self, code: usize, output: &mut Vec<u8>) -> Result<(), QPDFErrors> {
+        match code {
+            CLEAR_TABLE => {
+                self.reset();
+                return Ok(());
+            }
+            END_OF_DATA => {
+                self.done = true;
+                return Ok(());
+            }
+            _ => (),
+        }
+
+        let Some(previous) = self.previous else {
+            let entry = self.table.get(code).ok_or(QPDFErrors::InvalidStreamData)?;
+            output.extend(entry);
+            self.previous = Some(code);
+            return Ok(());
+        };
+
+        let entry = match self.table.get(code) {
+            Some(entry) => entry.clone(),
+            // The code being defined by this very step
+            None if code == self.table.len() => {
+                let mut entry = self.table[previous].clone();
+                entry.push(entry[0]);
+                entry
+            }
+            None => return Err(QPDFErrors::InvalidStreamData),
+        };
+
+        output.extend(&entry);
+
+        if self.table.len() < MAX_TABLE_SIZE {
+            let mut new = self.table[previous].clone();
+            new.push(entry[0]);
+            self.table.push(new);
+        }
+
+        self.previous = Some(code);
+        Ok(())
+    }
+}
+
+fn code_length(size: usize) -> u32 {
+    match size {
+        ..512 => 9,
+        512..1024 => 10,
+        1024..2048 => 11,
+        _ => 12,
+    }
+}
+
+impl QPDFStreamDecode for LZWDecoder {
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>, _end: bool) -> Result<(), QPDFErrors> {
+        for &c in input {
+            if self.done {
+                break;
+            }
+
+            self.bits = self.bits << 8 | c as u32;
+            self.bit_count += 8;
+
+            while !self.done && self.bit_count >= self.code_length() {
+                let length = self.code_length();
+                self.bit_count -= length;
+                let code = (self.bits >> self.bit_count) & ((1 << length) - 1);
+                self.bits &= (1 << self.bit_count) - 1;
+
+                self.handle_code(code as usize, output)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LZWEncoder {
+    pub fn new(early_change: i64) -> Self {
+        Self {
+            table: HashMap::new(),
+            next_code: END_OF_DATA + 1,
+            decoder_size: END_OF_DATA + 1,
+            current: None,
+            early_change: early_change.clamp(0, 1) as usize,
+            bits: 0,
+            bit_count: 0,
+            started: false,
+        }
+    }
+
+    fn write_code(&mut self, code: usize, output: &mut Vec<u8>) {
+        let length = code_length(self.decoder_size + self.early_change);
+
+        self.bits = self.bits << length | code as u32;
+        self.bit_count += length;
+
+        while self.bit_count >= 8 {
+            self.bit_count -= 8;
+            output.push((self.bits >> self.bit_count) as u8);
+        }
+        self.bits &= (1 << self.bit_count) - 1;
+    }
+
+    fn write_data_code(&mut self, code: u16, output: &mut Vec<u8>) {
+        self.write_code(code as usize, output);
+
+        // Decoders only grow their table from the second code onwards
+        if self.next_code > END_OF_DATA + 1 {
+            self.decoder_size += 1;
+        }
+    }
+
+    fn clear(&mut self, output: &mut Vec<u8>) {
+        self.write_code(CLEAR_TABLE, output);
+        self.table.clear();
+        self.next_code = END_OF_DATA + 1;
+        self.decoder_size = END_OF_DATA + 1;
+    }
+}
+
+impl QPDFStreamEncode for LZWEncoder {
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        if !self.started {
+            self.clear(output);
+            self.started = true;
+        }
+
+        for &c in input {
+            let Some(current) = self.current else {
+                self.current = Some(c as u16);
+                continue;
+            };
+
+            if let Some(&code) = self.table.get(&(current, c)) {
+                self.current = Some(code);
+                continue;
+            }
+
+            self.write_data_code(current, output);
+            self.table.insert((current, c), self.next_code as u16);
+            self.next_code += 1;
+            self.current = Some(c as u16);
+
+            if self.next_code + self.early_change >= MAX_TABLE_SIZE - 1 {
+                self.clear(output);
+            }
+        }
+
+        if end {
+            if let Some(current) = self.current.take() {
+                self.write_data_code(current, output);
+            }
+
+            self.write_code(END_OF_DATA, output);
+            if self.bit_count > 0 {
+                output.push((self.bits << (8 - self.bit_count)) as u8);
+                self.bits = 0;
+                self.bit_count = 0;
+            }
+        }
+
+        Ok(())
+    }
+}
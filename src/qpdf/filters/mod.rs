@@ -0,0 +1,293 @@
+use std::{
+    collections::HashMap,
+    io::{self, Cursor, Read},
+};
+
+use ascii_hex::{ASCIIHexDecoder, ASCIIHexEncoder};
+use ascii85::{ASCII85Decoder, ASCII85Encoder};
+use flate::{FlateDecoder, FlateEncoder};
+use lzw::{LZWDecoder, LZWEncoder};
+use predictor::{PredictorDecoder, PredictorEncoder};
+use run_length::{RunLengthDecoder, RunLengthEncoder};
+use types::{QPDFDecodeParms, QPDFFilter};
+
+use super::{
+    QPDFErrors,
+    object::{QPDFObjectHandler, types::QPDFIsObjectType},
+};
+
+// Size of the raw chunks handed to a decoder at a time
+const CHUNK_SIZE: usize = 4096;
+
+pub trait QPDFStreamDecode {
+    // Decodes a chunk of input into `output`; `end` is set once the input is exhausted
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors>;
+}
+
+pub trait QPDFStreamEncode {
+    // Encodes a chunk of input into `output`; `end` is set once the input is exhausted
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors>;
+}
+
+pub trait QPDFCustomFilter {
+    fn name(&self) -> String;
+
+    fn decoder(&self, parms: &QPDFDecodeParms) -> Result<Box<dyn QPDFStreamDecode>, QPDFErrors>;
+
+    fn encoder(&self, parms: &QPDFDecodeParms) -> Result<Box<dyn QPDFStreamEncode>, QPDFErrors>;
+}
+
+#[derive(Default)]
+pub struct QPDFFilterRegistry {
+    custom: HashMap<String, Box<dyn QPDFCustomFilter>>,
+}
+
+// Runs two decoding or encoding stages back to back
+struct Chained<T: ?Sized> {
+    first: Box<T>,
+    second: Box<T>,
+    scratch: Vec<u8>,
+}
+
+pub struct QPDFDecodeReader<R: Read> {
+    inner: R,
+    decoder: Box<dyn QPDFStreamDecode>,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    pos: usize,
+    finished: bool,
+}
+
+pub struct QPDFStreamReader {
+    inner: Box<dyn Read>,
+}
+
+// Decode Reader
+impl<R: Read> QPDFDecodeReader<R> {
+    pub fn new(inner: R, decoder: Box<dyn QPDFStreamDecode>) -> Self {
+        Self {
+            inner,
+            decoder,
+            input: vec![0; CHUNK_SIZE],
+            output: Vec::new(),
+            pos: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<R: Read> Read for QPDFDecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.output.len() {
+            if self.finished || buf.is_empty() {
+                return Ok(0);
+            }
+
+            self.output.clear();
+            self.pos = 0;
+
+            let n = self.inner.read(&mut self.input)?;
+            self.finished = n == 0;
+
+            self.decoder
+                .decode(&self.input[..n], &mut self.output, self.finished)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{e:?}")))?;
+        }
+
+        let n = buf.len().min(self.output.len() - self.pos);
+        buf[..n].copy_from_slice(&self.output[self.pos..self.pos + n]);
+        self.pos += n;
+
+        Ok(n)
+    }
+}
+
+// Stream Reader
+impl QPDFStreamReader {
+    pub fn new(
+        raw: Vec<u8>,
+        chain: Vec<(QPDFFilter, QPDFDecodeParms)>,
+    ) -> Result<Self, QPDFErrors> {
+        Self::with_registry(raw, chain, &QPDFFilterRegistry::default())
+    }
+
+    pub fn with_registry(
+        raw: Vec<u8>,
+        chain: Vec<(QPDFFilter, QPDFDecodeParms)>,
+        registry: &QPDFFilterRegistry,
+    ) -> Result<Self, QPDFErrors> {
+        let mut inner: Box<dyn Read> = Box::new(Cursor::new(raw));
+        let last = chain.len().saturating_sub(1);
+
+        for (i, (filter, parms)) in chain.into_iter().enumerate() {
+            // Image codecs are left encoded, matching qpdf's generalized decode level
+            if filter.is_image_codec() && i == last {
+                break;
+            }
+
+            inner = Box::new(QPDFDecodeReader::new(
+                inner,
+                registry.decoder(&filter, &parms)?,
+            ));
+        }
+
+        Ok(Self { inner })
+    }
+}
+
+impl Read for QPDFStreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+// Filter Registry
+impl QPDFFilterRegistry {
+    pub fn with_filter(mut self, filter: impl QPDFCustomFilter + 'static) -> Self {
+        self.custom.insert(filter.name(), Box::new(filter));
+        self
+    }
+
+    pub fn decoder(
+        &self,
+        filter: &QPDFFilter,
+        parms: &QPDFDecodeParms,
+    ) -> Result<Box<dyn QPDFStreamDecode>, QPDFErrors> {
+        if let Some(custom) = self.custom.get(&filter.name()) {
+            return custom.decoder(parms);
+        }
+
+        let decoder: Box<dyn QPDFStreamDecode> = match filter {
+            QPDFFilter::FlateDecode => Box::new(FlateDecoder::default()),
+            QPDFFilter::LZWDecode => Box::new(LZWDecoder::new(parms.early_change)),
+            QPDFFilter::ASCIIHexDecode => Box::new(ASCIIHexDecoder::default()),
+            QPDFFilter::ASCII85Decode => Box::new(ASCII85Decoder::default()),
+            QPDFFilter::RunLengthDecode => Box::new(RunLengthDecoder::default()),
+            _ => return Err(QPDFErrors::UnsupportedFilter(filter.name())),
+        };
+
+        if !has_predictor(filter, parms) {
+            return Ok(decoder);
+        }
+
+        Ok(Box::new(Chained::<dyn QPDFStreamDecode>::new(
+            decoder,
+            Box::new(PredictorDecoder::new(parms)?),
+        )))
+    }
+
+    pub fn encoder(
+        &self,
+        filter: &QPDFFilter,
+        parms: &QPDFDecodeParms,
+    ) -> Result<Box<dyn QPDFStreamEncode>, QPDFErrors> {
+        if let Some(custom) = self.custom.get(&filter.name()) {
+            return custom.encoder(parms);
+        }
+
+        let encoder: Box<dyn QPDFStreamEncode> = match filter {
+            QPDFFilter::FlateDecode => Box::new(FlateEncoder::default()),
+            QPDFFilter::LZWDecode => Box::new(LZWEncoder::new(parms.early_change)),
+            QPDFFilter::ASCIIHexDecode => Box::new(ASCIIHexEncoder::default()),
+            QPDFFilter::ASCII85Decode => Box::new(ASCII85Encoder::default()),
+            QPDFFilter::RunLengthDecode => Box::new(RunLengthEncoder::default()),
+            _ => return Err(QPDFErrors::UnsupportedFilter(filter.name())),
+        };
+
+        if !has_predictor(filter, parms) {
+            return Ok(encoder);
+        }
+
+        Ok(Box::new(Chained::<dyn QPDFStreamEncode>::new(
+            Box::new(PredictorEncoder::new(parms)?),
+            encoder,
+        )))
+    }
+
+    // Applies the chain in reverse so that decoding it in order restores `data`
+    pub fn encode(
+        &self,
+        data: &[u8],
+        chain: &[(QPDFFilter, QPDFDecodeParms)],
+    ) -> Result<Vec<u8>, QPDFErrors> {
+        let mut data = data.to_vec();
+
+        for (filter, parms) in chain.iter().rev() {
+            let mut output = Vec::new();
+            self.encoder(filter, parms)?
+                .encode(&data, &mut output, true)?;
+            data = output;
+        }
+
+        Ok(data)
+    }
+}
+
+// Chained Stages
+impl<T: ?Sized> Chained<T> {
+    fn new(first: Box<T>, second: Box<T>) -> Self {
+        Self {
+            first,
+            second,
+            scratch: Vec::new(),
+        }
+    }
+}
+
+impl QPDFStreamDecode for Chained<dyn QPDFStreamDecode> {
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        self.scratch.clear();
+        self.first.decode(input, &mut self.scratch, end)?;
+        self.second.decode(&self.scratch, output, end)
+    }
+}
+
+impl QPDFStreamEncode for Chained<dyn QPDFStreamEncode> {
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        self.scratch.clear();
+        self.first.encode(input, &mut self.scratch, end)?;
+        self.second.encode(&self.scratch, output, end)
+    }
+}
+
+// Predictors only apply to the LZW and Flate filters
+fn has_predictor(filter: &QPDFFilter, parms: &QPDFDecodeParms) -> bool {
+    parms.predictor > 1 && matches!(filter, QPDFFilter::FlateDecode | QPDFFilter::LZWDecode)
+}
+
+pub(crate) fn filter_chain(dict: &QPDFObjectHandler) -> Vec<(QPDFFilter, QPDFDecodeParms)> {
+    let filter = dict.dict_get_key("/Filter".to_string());
+    let parms = dict.dict_get_key("/DecodeParms".to_string());
+
+    if filter.is(QPDFIsObjectType::Name) {
+        let name = filter.name().unwrap_or_default();
+        return vec![(QPDFFilter::from(name.as_str()), parms.into())];
+    }
+
+    if !filter.is(QPDFIsObjectType::Array) {
+        return Vec::new();
+    }
+
+    (0..filter.array_len())
+        .map(|i| {
+            let name = filter.array_get_at(i).name().unwrap_or_default();
+            let parms = match parms.is(QPDFIsObjectType::Array) {
+                true => parms.array_get_at(i).into(),
+                _ => QPDFDecodeParms::default(),
+            };
+
+            (QPDFFilter::from(name.as_str()), parms)
+        })
+        .collect()
+}
+
+pub mod ascii85;
+pub mod ascii_hex;
+pub mod flate;
+pub mod lzw;
+pub mod predictor;
+pub mod run_length;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
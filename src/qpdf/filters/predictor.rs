@@ -0,0 +1,262 @@
+use super::{QPDFStreamDecode, QPDFStreamEncode, types::QPDFDecodeParms};
+use crate::qpdf::QPDFErrors;
+
+const TIFF_PREDICTOR: i64 = 2;
+const PNG_OPTIMUM: i64 = 15;
+
+pub struct PredictorDecoder {
+    parms: QPDFDecodeParms,
+    bytes_per_pixel: usize,
+    row_length: usize,
+    previous: Vec<u8>,
+    pending: Vec<u8>,
+}
+
+pub struct PredictorEncoder {
+    parms: QPDFDecodeParms,
+    bytes_per_pixel: usize,
+    row_length: usize,
+    previous: Vec<u8>,
+    pending: Vec<u8>,
+}
+
+// Layout
+fn layout(parms: &QPDFDecodeParms) -> Result<(usize, usize), QPDFErrors> {
+    let valid = parms.colors > 0
+        && parms.columns > 0
+        && matches!(parms.bits_per_component, 1 | 2 | 4 | 8 | 16)
+        && matches!(parms.predictor, TIFF_PREDICTOR | 10..=PNG_OPTIMUM);
+
+    if !valid {
+        return Err(QPDFErrors::InvalidStreamData);
+    }
+
+    let bits_per_pixel = (parms.colors * parms.bits_per_component) as usize;
+    let row_length = (bits_per_pixel * parms.columns as usize).div_ceil(8);
+
+    Ok((bits_per_pixel.div_ceil(8), row_length))
+}
+
+// Decoding
+impl PredictorDecoder {
+    pub fn new(parms: &QPDFDecodeParms) -> Result<Self, QPDFErrors> {
+        let (bytes_per_pixel, row_length) = layout(parms)?;
+
+        Ok(Self {
+            parms: parms.clone(),
+            bytes_per_pixel,
+            row_length,
+            previous: vec![0; row_length],
+            pending: Vec::new(),
+        })
+    }
+
+    fn stride(&self) -> usize {
+        match self.parms.predictor {
+            TIFF_PREDICTOR => self.row_length,
+            _ => self.row_length + 1,
+        }
+    }
+
+    fn decode_row(&mut self, row: &[u8], output: &mut Vec<u8>) -> Result<(), QPDFErrors> {
+        let mut current = match self.parms.predictor {
+            TIFF_PREDICTOR => row.to_vec(),
+            _ => row[1..].to_vec(),
+        };
+        current.resize(self.row_length, 0);
+
+        match self.parms.predictor {
+            TIFF_PREDICTOR => tiff_decode(&mut current, &self.parms),
+            _ => png_decode(row[0], &mut current, &self.previous, self.bytes_per_pixel)?,
+        }
+
+        let available = row.len() + self.row_length - self.stride();
+        output.extend(&current[..available]);
+        self.previous = current;
+
+        Ok(())
+    }
+}
+
+impl QPDFStreamDecode for PredictorDecoder {
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        self.pending.extend(input);
+
+        let stride = self.stride();
+        let complete = self.pending.len() / stride * stride;
+        let pending = std::mem::take(&mut self.pending);
+
+        for row in pending[..complete].chunks(stride) {
+            self.decode_row(row, output)?;
+        }
+
+        // A truncated final row is decoded as far as it goes
+        let rest = &pending[complete..];
+        if end && rest.len() > stride - self.row_length {
+            self.decode_row(rest, output)?;
+        } else if !end {
+            self.pending = rest.to_vec();
+        }
+
+        Ok(())
+    }
+}
+
+// Encoding
+impl PredictorEncoder {
+    pub fn new(parms: &QPDFDecodeParms) -> Result<Self, QPDFErrors> {
+        let (bytes_per_pixel, row_length) = layout(parms)?;
+
+        Ok(Self {
+            parms: parms.clone(),
+            bytes_per_pixel,
+            row_length,
+            previous: vec![0; row_length],
+            pending: Vec::new(),
+        })
+    }
+
+    fn encode_row(&mut self, row: &[u8], output: &mut Vec<u8>) {
+        if self.parms.predictor == TIFF_PREDICTOR {
+            let mut current = row.to_vec();
+            tiff_encode(&mut current, &self.parms);
+            output.extend(current);
+            return;
+        }
+
+        let bpp = self.bytes_per_pixel;
+        let filtered = match self.parms.predictor {
+            PNG_OPTIMUM => (0..5)
+                .map(|t| png_encode(t, row, &self.previous, bpp))
+                .min_by_key(|(_, data)| {
+                    data.iter()
+                        .map(|&b| (b as i8).unsigned_abs() as u64)
+                        .sum::<u64>()
+                })
+                .expect("A filtered row"),
+            p => png_encode((p - 10) as u8, row, &self.previous, bpp),
+        };
+
+        output.push(filtered.0);
+        output.extend(filtered.1);
+        self.previous = row.to_vec();
+    }
+}
+
+impl QPDFStreamEncode for PredictorEncoder {
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        self.pending.extend(input);
+
+        let complete = self.pending.len() / self.row_length * self.row_length;
+        let pending = std::mem::take(&mut self.pending);
+
+        for row in pending[..complete].chunks(self.row_length) {
+            self.encode_row(row, output);
+        }
+
+        let mut rest = pending[complete..].to_vec();
+        if end && !rest.is_empty() {
+            rest.resize(self.row_length, 0);
+            self.encode_row(&rest, output);
+        } else if !end {
+            self.pending = rest;
+        }
+
+        Ok(())
+    }
+}
+
+// PNG Filters
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+
+    match (pa <= pb && pa <= pc, pb <= pc) {
+        (true, _) => a,
+        (_, true) => b,
+        _ => c,
+    }
+}
+
+fn png_predict(filter: u8, row: &[u8], previous: &[u8], i: usize, bpp: usize) -> u8 {
+    let left = if i >= bpp { row[i - bpp] } else { 0 };
+    let up = previous[i];
+    let up_left = if i >= bpp { previous[i - bpp] } else { 0 };
+
+    match filter {
+        1 => left,
+        2 => up,
+        3 => ((left as u16 + up as u16) / 2) as u8,
+        4 => paeth(left, up, up_left),
+        _ => 0,
+    }
+}
+
+fn png_decode(filter: u8, row: &mut [u8], previous: &[u8], bpp: usize) -> Result<(), QPDFErrors> {
+    if filter > 4 {
+        return Err(QPDFErrors::InvalidStreamData);
+    }
+
+    for i in 0..row.len() {
+        row[i] = row[i].wrapping_add(png_predict(filter, row, previous, i, bpp));
+    }
+
+    Ok(())
+}
+
+fn png_encode(filter: u8, row: &[u8], previous: &[u8], bpp: usize) -> (u8, Vec<u8>) {
+    let data = (0..row.len())
+        .map(|i| row[i].wrapping_sub(png_predict(filter, row, previous, i, bpp)))
+        .collect();
+
+    (filter, data)
+}
+
+// TIFF Filters
+fn sample(row: &[u8], index: usize, bits: usize) -> u32 {
+    (index * bits..(index + 1) * bits)
+        .fold(0, |v, b| v << 1 | ((row[b / 8] >> (7 - b % 8)) & 1) as u32)
+}
+
+fn set_sample(row: &mut [u8], index: usize, bits: usize, value: u32) {
+    for (k, b) in (index * bits..(index + 1) * bits).enumerate() {
+        let mask = 0x80 >> (b % 8);
+
+        match (value >> (bits - 1 - k)) & 1 {
+            1 => row[b / 8] |= mask,
+            _ => row[b / 8] &= !mask,
+        }
+    }
+}
+
+fn tiff_samples(row: &[u8], parms: &QPDFDecodeParms) -> (usize, usize, u32) {
+    let bits = parms.bits_per_component as usize;
+    let count = (parms.colors * parms.columns) as usize;
+    let mask = ((1u64 << bits) - 1) as u32;
+
+    (bits, count.min(row.len() * 8 / bits), mask)
+}
+
+fn tiff_decode(row: &mut [u8], parms: &QPDFDecodeParms) {
+    let (bits, count, mask) = tiff_samples(row, parms);
+    let colors = parms.colors as usize;
+
+    for i in colors..count {
+        let v = sample(row, i, bits).wrapping_add(sample(row, i - colors, bits));
+        set_sample(row, i, bits, v & mask);
+    }
+}
+
+fn tiff_encode(row: &mut [u8], parms: &QPDFDecodeParms) {
+    let (bits, count, mask) = tiff_samples(row, parms);
+    let colors = parms.colors as usize;
+
+    for i in (colors..count).rev() {
+        let v = sample(row, i, bits).wrapping_sub(sample(row, i - colors, bits));
+        set_sample(row, i, bits, v & mask);
+    }
+}
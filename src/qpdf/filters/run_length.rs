@@ -0,0 +1,91 @@
+use super::{QPDFStreamDecode, QPDFStreamEncode};
+use crate::qpdf::QPDFErrors;
+
+const MAX_RUN: usize = 128;
+const END_OF_DATA: u8 = 128;
+
+#[derive(Default)]
+pub struct RunLengthDecoder {
+    pending: Vec<u8>,
+    done: bool,
+}
+
+#[derive(Default)]
+pub struct RunLengthEncoder {
+    pending: Vec<u8>,
+}
+
+impl QPDFStreamDecode for RunLengthDecoder {
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>, _end: bool) -> Result<(), QPDFErrors> {
+        self.pending.extend(input);
+        let mut i = 0;
+
+        while !self.done && i < self.pending.len() {
+            let length = self.pending[i];
+
+            match length {
+                END_OF_DATA => {
+                    self.done = true;
+                    i += 1;
+                }
+                0..=127 if i + (length as usize) + 1 < self.pending.len() => {
+                    let length = length as usize;
+                    output.extend(&self.pending[i + 1..i + length + 2]);
+                    i += length + 2;
+                }
+                129..=255 if i + 1 < self.pending.len() => {
+                    let count = 257 - length as usize;
+                    output.extend(std::iter::repeat_n(self.pending[i + 1], count));
+                    i += 2;
+                }
+                _ => break,
+            }
+        }
+
+        self.pending.drain(..i);
+        Ok(())
+    }
+}
+
+fn run_at(data: &[u8], start: usize) -> usize {
+    data[start..]
+        .iter()
+        .take(MAX_RUN)
+        .take_while(|&&c| c == data[start])
+        .count()
+}
+
+impl QPDFStreamEncode for RunLengthEncoder {
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        self.pending.extend(input);
+
+        // Runs may continue into the next chunk, so encoding waits for the end
+        if !end {
+            return Ok(());
+        }
+
+        let data = std::mem::take(&mut self.pending);
+        let mut i = 0;
+
+        while i < data.len() {
+            let run = run_at(&data, i);
+
+            if run > 1 {
+                output.extend([(257 - run) as u8, data[i]]);
+                i += run;
+                continue;
+            }
+
+            let start = i;
+            while i < data.len() && i - start < MAX_RUN && run_at(&data, i) < 2 {
+                i += 1;
+            }
+
+            output.push((i - start - 1) as u8);
+            output.extend(&data[start..i]);
+        }
+
+        output.push(END_OF_DATA);
+        Ok(())
+    }
+}
@@ -0,0 +1,313 @@
+use std::io::{Cursor, Read};
+
+use super::{
+    QPDFCustomFilter, QPDFDecodeReader, QPDFFilterRegistry, QPDFStreamDecode, QPDFStreamEncode,
+    QPDFStreamReader,
+    ascii_hex::ASCIIHexDecoder,
+    types::{QPDFDecodeParms, QPDFFilter},
+};
+use crate::qpdf::QPDFErrors;
+
+struct XorFilter(u8);
+
+impl QPDFStreamDecode for XorFilter {
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>, _: bool) -> Result<(), QPDFErrors> {
+        output.extend(input.iter().map(|c| c ^ self.0));
+        Ok(())
+    }
+}
+
+impl QPDFStreamEncode for XorFilter {
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>, end: bool) -> Result<(), QPDFErrors> {
+        self.decode(input, output, end)
+    }
+}
+
+impl QPDFCustomFilter for XorFilter {
+    fn name(&self) -> String {
+        "/XorDecode".to_string()
+    }
+
+    fn decoder(&self, _: &QPDFDecodeParms) -> Result<Box<dyn QPDFStreamDecode>, QPDFErrors> {
+        Ok(Box::new(XorFilter(self.0)))
+    }
+
+    fn encoder(&self, _: &QPDFDecodeParms) -> Result<Box<dyn QPDFStreamEncode>, QPDFErrors> {
+        Ok(Box::new(XorFilter(self.0)))
+    }
+}
+
+fn round_trip(
+    data: &[u8],
+    chain: Vec<(QPDFFilter, QPDFDecodeParms)>,
+    registry: &QPDFFilterRegistry,
+) {
+    let encoded = registry.encode(data, &chain).unwrap();
+
+    let mut out = Vec::new();
+    QPDFStreamReader::with_registry(encoded, chain, registry)
+        .unwrap()
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(data, &out[..]);
+}
+
+fn sample_data() -> Vec<u8> {
+    (0..30000u32)
+        .map(|i| match i % 300 {
+            0..100 => (i % 7) as u8,
+            100..200 => 0x42,
+            _ => (i * 31 % 256) as u8,
+        })
+        .collect()
+}
+
+fn decode(data: &[u8], filters: &[QPDFFilter]) -> Vec<u8> {
+    let chain = filters
+        .iter()
+        .map(|f| (f.clone(), QPDFDecodeParms::default()))
+        .collect();
+
+    let mut out = Vec::new();
+    QPDFStreamReader::new(data.to_vec(), chain)
+        .unwrap()
+        .read_to_end(&mut out)
+        .unwrap();
+    out
+}
+
+#[test]
+fn filter_names() {
+    assert_eq!(QPDFFilter::FlateDecode, QPDFFilter::from("/Fl"));
+    assert_eq!(
+        QPDFFilter::ASCII85Decode,
+        QPDFFilter::from("/ASCII85Decode")
+    );
+    assert_eq!("/Foo", QPDFFilter::from("/Foo").name());
+    assert!(QPDFFilter::DCTDecode.is_image_codec());
+}
+
+#[test]
+fn decode_ascii_filters() {
+    assert_eq!(
+        b"Hello",
+        &decode(b"48 65 6c6C6F>", &[QPDFFilter::ASCIIHexDecode])[..]
+    );
+    assert_eq!(b"\xa0", &decode(b"A>", &[QPDFFilter::ASCIIHexDecode])[..]);
+    assert_eq!(
+        b"Hello World",
+        &decode(b"87cURD]i,\"Ebo7~>", &[QPDFFilter::ASCII85Decode])[..]
+    );
+    assert_eq!(
+        b"\0\0\0\0abc",
+        &decode(b"z@:E^~>", &[QPDFFilter::ASCII85Decode])[..]
+    );
+}
+
+#[test]
+fn decode_lzw_and_run_length() {
+    let lzw = [0x80, 0x0b, 0x60, 0x50, 0x22, 0x0c, 0x0c, 0x85, 0x01];
+    assert_eq!(b"-----A---B", &decode(&lzw, &[QPDFFilter::LZWDecode])[..]);
+
+    let rle = [2, b'a', b'b', b'c', 254, b'x', 128];
+    assert_eq!(b"abcxxx", &decode(&rle, &[QPDFFilter::RunLengthDecode])[..]);
+}
+
+#[test]
+fn decode_flate_in_small_reads() {
+    let original: Vec<u8> = (0..20000).map(|i| (i % 251) as u8).collect();
+    let compressed = miniz_oxide::deflate::compress_to_vec_zlib(&original, 6);
+
+    let chain = vec![(QPDFFilter::FlateDecode, QPDFDecodeParms::default())];
+    let mut reader = QPDFStreamReader::new(compressed, chain).unwrap();
+
+    let mut out: Vec<u8> = Vec::new();
+    let mut buf = [0u8; 7];
+    loop {
+        let n = reader.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        out.extend(&buf[..n]);
+    }
+
+    assert_eq!(original, out);
+}
+
+#[test]
+fn decode_filter_chain() {
+    let compressed = miniz_oxide::deflate::compress_to_vec_zlib(b"chained", 6);
+    let hex: String = compressed.iter().map(|b| format!("{b:02x}")).collect();
+
+    let out = decode(
+        hex.as_bytes(),
+        &[QPDFFilter::ASCIIHexDecode, QPDFFilter::FlateDecode],
+    );
+    assert_eq!(b"chained", &out[..]);
+
+    // A trailing image codec is left encoded
+    let out = decode(
+        b"ff d8>",
+        &[QPDFFilter::ASCIIHexDecode, QPDFFilter::DCTDecode],
+    );
+    assert_eq!(vec![0xff, 0xd8], out);
+}
+
+#[test]
+fn invalid_data_is_an_error() {
+    let decoder = Box::new(ASCIIHexDecoder::default());
+    let mut reader = QPDFDecodeReader::new(Cursor::new(b"zz>".to_vec()), decoder);
+
+    assert!(reader.read_to_end(&mut Vec::new()).is_err());
+    assert!(
+        QPDFStreamReader::new(
+            Vec::new(),
+            vec![
+                (QPDFFilter::JBIG2Decode, QPDFDecodeParms::default()),
+                (QPDFFilter::FlateDecode, QPDFDecodeParms::default())
+            ]
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn encode_round_trips() {
+    let registry = QPDFFilterRegistry::default();
+    let data = sample_data();
+
+    for filter in [
+        QPDFFilter::FlateDecode,
+        QPDFFilter::LZWDecode,
+        QPDFFilter::ASCIIHexDecode,
+        QPDFFilter::ASCII85Decode,
+        QPDFFilter::RunLengthDecode,
+    ] {
+        round_trip(
+            &data,
+            vec![(filter.clone(), QPDFDecodeParms::default())],
+            &registry,
+        );
+        round_trip(
+            b"",
+            vec![(filter.clone(), QPDFDecodeParms::default())],
+            &registry,
+        );
+        round_trip(
+            b"abc",
+            vec![(filter, QPDFDecodeParms::default())],
+            &registry,
+        );
+    }
+
+    let parms = QPDFDecodeParms::default().with_early_change(0);
+    round_trip(&data, vec![(QPDFFilter::LZWDecode, parms)], &registry);
+
+    let chain = vec![
+        (QPDFFilter::ASCII85Decode, QPDFDecodeParms::default()),
+        (QPDFFilter::RunLengthDecode, QPDFDecodeParms::default()),
+    ];
+    round_trip(&data, chain, &registry);
+}
+
+#[test]
+fn encode_known_output() {
+    let registry = QPDFFilterRegistry::default();
+    let encode = |data: &[u8], filter: QPDFFilter| {
+        registry
+            .encode(data, &[(filter, QPDFDecodeParms::default())])
+            .unwrap()
+    };
+
+    assert_eq!(
+        b"87cURD]i,\"Ebo7~>",
+        &encode(b"Hello World", QPDFFilter::ASCII85Decode)[..]
+    );
+    assert_eq!(
+        b"z@:E^~>",
+        &encode(b"\0\0\0\0abc", QPDFFilter::ASCII85Decode)[..]
+    );
+    assert_eq!(
+        b"48656c6c6f>",
+        &encode(b"Hello", QPDFFilter::ASCIIHexDecode)[..]
+    );
+    assert_eq!(
+        vec![2, b'a', b'b', b'c', 254, b'x', 128],
+        encode(b"abcxxx", QPDFFilter::RunLengthDecode)
+    );
+    assert_eq!(
+        vec![0x80, 0x0b, 0x60, 0x50, 0x22, 0x0c, 0x0c, 0x85, 0x01],
+        encode(b"-----A---B", QPDFFilter::LZWDecode)
+    );
+}
+
+#[test]
+fn predictors_round_trip() {
+    let registry = QPDFFilterRegistry::default();
+    let data = sample_data();
+
+    for predictor in [2, 10, 11, 12, 13, 14, 15] {
+        for (colors, bits, columns) in [(3, 8, 100), (1, 1, 75), (2, 16, 25), (1, 4, 9)] {
+            let parms = QPDFDecodeParms::default()
+                .with_predictor(predictor)
+                .with_colors(colors)
+                .with_bits_per_component(bits)
+                .with_columns(columns);
+
+            let row = ((colors * bits * columns) as usize).div_ceil(8);
+            let data = &data[..data.len() / row * row];
+
+            round_trip(
+                data,
+                vec![(QPDFFilter::FlateDecode, parms.clone())],
+                &registry,
+            );
+            round_trip(data, vec![(QPDFFilter::LZWDecode, parms)], &registry);
+        }
+    }
+}
+
+#[test]
+fn decode_png_predictor() {
+    // Two rows of two RGB pixels using the Sub and Up filters
+    let rows = [1, 10, 20, 30, 5, 5, 5, 2, 1, 1, 1, 1, 1, 1];
+    let compressed = miniz_oxide::deflate::compress_to_vec_zlib(&rows, 6);
+
+    let parms = QPDFDecodeParms::default()
+        .with_predictor(12)
+        .with_colors(3)
+        .with_columns(2);
+
+    let mut out = Vec::new();
+    QPDFStreamReader::new(compressed, vec![(QPDFFilter::FlateDecode, parms)])
+        .unwrap()
+        .read_to_end(&mut out)
+        .unwrap();
+
+    assert_eq!(vec![10, 20, 30, 15, 25, 35, 11, 21, 31, 16, 26, 36], out);
+}
+
+#[test]
+fn custom_filter_registration() {
+    let registry = QPDFFilterRegistry::default().with_filter(XorFilter(0x5a));
+    let custom = QPDFFilter::from("/XorDecode");
+
+    let encoded = registry
+        .encode(b"secret", &[(custom.clone(), QPDFDecodeParms::default())])
+        .unwrap();
+    assert_eq!(b"secret".map(|c| c ^ 0x5a).to_vec(), encoded);
+
+    let chain = vec![
+        (QPDFFilter::ASCIIHexDecode, QPDFDecodeParms::default()),
+        (custom.clone(), QPDFDecodeParms::default()),
+        (QPDFFilter::FlateDecode, QPDFDecodeParms::default()),
+    ];
+    round_trip(&sample_data(), chain, &registry);
+
+    let unregistered = QPDFFilterRegistry::default();
+    assert!(
+        unregistered
+            .encode(b"secret", &[(custom, QPDFDecodeParms::default())])
+            .is_err()
+    );
+}
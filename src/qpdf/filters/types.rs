@@ -0,0 +1,165 @@
+use crate::qpdf::object::{
+    QPDFObjectHandler,
+    types::{QPDFIsObjectType, QPDFModifyObjectTypes},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QPDFFilter {
+    FlateDecode,
+    LZWDecode,
+    ASCIIHexDecode,
+    ASCII85Decode,
+    RunLengthDecode,
+    DCTDecode,
+    JPXDecode,
+    CCITTFaxDecode,
+    JBIG2Decode,
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFDecodeParms {
+    pub predictor: i64,
+    pub colors: i64,
+    pub bits_per_component: i64,
+    pub columns: i64,
+    pub early_change: i64,
+}
+
+impl From<&str> for QPDFFilter {
+    fn from(value: &str) -> Self {
+        match value.trim_start_matches('/') {
+            "FlateDecode" | "Fl" => QPDFFilter::FlateDecode,
+            "LZWDecode" | "LZW" => QPDFFilter::LZWDecode,
+            "ASCIIHexDecode" | "AHx" => QPDFFilter::ASCIIHexDecode,
+            "ASCII85Decode" | "A85" => QPDFFilter::ASCII85Decode,
+            "RunLengthDecode" | "RL" => QPDFFilter::RunLengthDecode,
+            "DCTDecode" | "DCT" => QPDFFilter::DCTDecode,
+            "JPXDecode" => QPDFFilter::JPXDecode,
+            "CCITTFaxDecode" | "CCF" => QPDFFilter::CCITTFaxDecode,
+            "JBIG2Decode" => QPDFFilter::JBIG2Decode,
+            other => QPDFFilter::Other(format!("/{other}")),
+        }
+    }
+}
+
+impl QPDFFilter {
+    pub fn name(&self) -> String {
+        match self {
+            QPDFFilter::FlateDecode => "/FlateDecode".to_string(),
+            QPDFFilter::LZWDecode => "/LZWDecode".to_string(),
+            QPDFFilter::ASCIIHexDecode => "/ASCIIHexDecode".to_string(),
+            QPDFFilter::ASCII85Decode => "/ASCII85Decode".to_string(),
+            QPDFFilter::RunLengthDecode => "/RunLengthDecode".to_string(),
+            QPDFFilter::DCTDecode => "/DCTDecode".to_string(),
+            QPDFFilter::JPXDecode => "/JPXDecode".to_string(),
+            QPDFFilter::CCITTFaxDecode => "/CCITTFaxDecode".to_string(),
+            QPDFFilter::JBIG2Decode => "/JBIG2Decode".to_string(),
+            QPDFFilter::Other(name) => name.clone(),
+        }
+    }
+
+    pub fn is_image_codec(&self) -> bool {
+        matches!(
+            self,
+            QPDFFilter::DCTDecode
+                | QPDFFilter::JPXDecode
+                | QPDFFilter::CCITTFaxDecode
+                | QPDFFilter::JBIG2Decode
+        )
+    }
+}
+
+impl Default for QPDFDecodeParms {
+    fn default() -> Self {
+        Self {
+            predictor: 1,
+            colors: 1,
+            bits_per_component: 8,
+            columns: 1,
+            early_change: 1,
+        }
+    }
+}
+
+impl QPDFDecodeParms {
+    pub fn with_predictor(mut self, predictor: i64) -> Self {
+        self.predictor = predictor;
+        self
+    }
+
+    pub fn with_colors(mut self, colors: i64) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    pub fn with_bits_per_component(mut self, bits_per_component: i64) -> Self {
+        self.bits_per_component = bits_per_component;
+        self
+    }
+
+    pub fn with_columns(mut self, columns: i64) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    pub fn with_early_change(mut self, early_change: i64) -> Self {
+        self.early_change = early_change;
+        self
+    }
+
+    // Only entries that differ from the defaults are written
+    pub(crate) fn to_object(&self, factory: &QPDFObjectHandler) -> QPDFObjectHandler {
+        let defaults = QPDFDecodeParms::default();
+
+        if *self == defaults {
+            return factory.set(QPDFModifyObjectTypes::Null);
+        }
+
+        let dict = factory.set(QPDFModifyObjectTypes::Dictionary);
+        for (key, value, default) in [
+            ("/Predictor", self.predictor, defaults.predictor),
+            ("/Colors", self.colors, defaults.colors),
+            (
+                "/BitsPerComponent",
+                self.bits_per_component,
+                defaults.bits_per_component,
+            ),
+            ("/Columns", self.columns, defaults.columns),
+            ("/EarlyChange", self.early_change, defaults.early_change),
+        ] {
+            if value != default {
+                dict.dict_replace_key(
+                    key.to_string(),
+                    factory.set(QPDFModifyObjectTypes::Integer(value)),
+                );
+            }
+        }
+
+        dict
+    }
+}
+
+impl From<QPDFObjectHandler> for QPDFDecodeParms {
+    fn from(value: QPDFObjectHandler) -> Self {
+        let mut parms = QPDFDecodeParms::default();
+
+        if !value.is(QPDFIsObjectType::Dictionary) {
+            return parms;
+        }
+
+        for (key, field) in [
+            ("/Predictor", &mut parms.predictor),
+            ("/Colors", &mut parms.colors),
+            ("/BitsPerComponent", &mut parms.bits_per_component),
+            ("/Columns", &mut parms.columns),
+            ("/EarlyChange", &mut parms.early_change),
+        ] {
+            if let Ok(v) = value.dict_get_key(key.to_string()).try_into() {
+                *field = v;
+            }
+        }
+
+        parms
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use crate::qpdf::content::{
+    QPDFContentParser,
+    types::{QPDFContentOperation, QPDFContentValue},
+};
+
+// Guards against ranges that would expand into millions of entries
+const MAX_RANGE: u32 = 0x10000;
+
+#[derive(Debug, Clone, Default)]
+pub struct QPDFCMap {
+    codespaces: Vec<(Vec<u8>, Vec<u8>)>,
+    unicode: HashMap<(usize, u32), String>,
+    cids: HashMap<(usize, u32), u32>,
+    cid_ranges: Vec<(usize, u32, u32, u32)>,
+}
+
+// Construction
+impl QPDFCMap {
+    pub fn parse(data: &[u8]) -> Self {
+        let mut cmap = QPDFCMap::default();
+
+        // Malformed trailing content is ignored, keeping whatever was parsed up to it
+        for operation in QPDFContentParser::new(data).map_while(Result::ok) {
+            let QPDFContentOperation::Operator(op, operands) = operation else {
+                continue;
+            };
+
+            match op.as_str() {
+                "endcodespacerange" => cmap.read_codespaces(&operands),
+                "endbfchar" => cmap.read_bfchar(&operands),
+                "endbfrange" => cmap.read_bfrange(&operands),
+                "endcidchar" => cmap.read_cidchar(&operands),
+                "endcidrange" => cmap.read_cidrange(&operands),
+                _ => (),
+            }
+        }
+
+        cmap
+    }
+
+    // The Identity-H and Identity-V CMaps map two-byte codes to the same CID
+    pub fn identity() -> Self {
+        Self {
+            codespaces: vec![(vec![0, 0], vec![0xff, 0xff])],
+            cid_ranges: vec![(2, 0, 0xffff, 0)],
+            ..Default::default()
+        }
+    }
+
+    fn read_codespaces(&mut self, operands: &[QPDFContentValue]) {
+        for pair in operands.chunks_exact(2) {
+            if let (Some(low), Some(high)) = (pair[0].as_bytes(), pair[1].as_bytes())
+                && low.len() == high.len()
+                && !low.is_empty()
+            {
+                self.codespaces.push((low.to_vec(), high.to_vec()));
+            }
+        }
+    }
+
+    fn read_bfchar(&mut self, operands: &[QPDFContentValue]) {
+        for pair in operands.chunks_exact(2) {
+            let Some(src) = pair[0].as_bytes() else {
+                continue;
+            };
+
+            let dst = match &pair[1] {
+                QPDFContentValue::String(dst) => utf16_to_string(dst),
+                QPDFContentValue::Name(name) => super::encoding::glyph_to_unicode(name),
+                _ => None,
+            };
+
+            if let Some(dst) = dst {
+                self.unicode.insert((src.len(), code(src)), dst);
+            }
+        }
+    }
+
+    fn read_bfrange(&mut self, operands: &[QPDFContentValue]) {
+        for range in operands.chunks_exact(3) {
+            let (Some(low), Some(high)) = (range[0].as_bytes(), range[1].as_bytes()) else {
+                continue;
+            };
+
+            let (start, end) = (code(low), code(high));
+            if end < start || end - start > MAX_RANGE {
+                continue;
+            }
+
+            match &range[2] {
+                QPDFContentValue::String(dst) => {
+                    let mut dst = dst.clone();
+
+                    for c in start..=end {
+                        if let Some(text) = utf16_to_string(&dst) {
+                            self.unicode.insert((low.len(), c), text);
+                        }
+
+                        // Only the last byte of the destination is incremented
+                        if let Some(last) = dst.last_mut() {
+                            *last = last.wrapping_add(1);
+                        }
+                    }
+                }
+                QPDFContentValue::Array(items) => {
+                    for (c, item) in (start..=end).zip(items) {
+                        if let Some(text) = item.as_bytes().and_then(utf16_to_string) {
+                            self.unicode.insert((low.len(), c), text);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn read_cidchar(&mut self, operands: &[QPDFContentValue]) {
+        for pair in operands.chunks_exact(2) {
+            if let (Some(src), Some(cid)) = (pair[0].as_bytes(), pair[1].as_number()) {
+                self.cids.insert((src.len(), code(src)), cid as u32);
+            }
+        }
+    }
+
+    fn read_cidrange(&mut self, operands: &[QPDFContentValue]) {
+        for range in operands.chunks_exact(3) {
+            if let (Some(low), Some(high), Some(cid)) = (
+                range[0].as_bytes(),
+                range[1].as_bytes(),
+                range[2].as_number(),
+            ) {
+                self.cid_ranges
+                    .push((low.len(), code(low), code(high), cid as u32));
+            }
+        }
+    }
+}
+
+// Lookup
+impl QPDFCMap {
+    pub fn has_codespaces(&self) -> bool {
+        !self.codespaces.is_empty()
+    }
+
+    // Splits the next character code off `data`, returning the code and its length in bytes
+    pub fn next_code(&self, data: &[u8], default_length: usize) -> (u32, usize) {
+        for n in 1..=4.min(data.len()) {
+            let prefix = &data[..n];
+
+            let matched = self.codespaces.iter().any(|(low, high)| {
+                low.len() == n && (0..n).all(|i| low[i] <= prefix[i] && prefix[i] <= high[i])
+            });
+
+            if matched {
+                return (code(prefix), n);
+            }
+        }
+
+        let n = default_length.clamp(1, 4).min(data.len());
+        (code(&data[..n]), n)
+    }
+
+    pub fn unicode(&self, code: u32, length: usize) -> Option<&String> {
+        self.unicode.get(&(length, code))
+    }
+
+    pub fn cid(&self, code: u32, length: usize) -> Option<u32> {
+        if let Some(cid) = self.cids.get(&(length, code)) {
+            return Some(*cid);
+        }
+
+        self.cid_ranges
+            .iter()
+            .find(|(n, low, high, _)| *n == length && (*low..=*high).contains(&code))
+            .map(|(_, low, _, cid)| cid + (code - low))
+    }
+}
+
+fn code(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |acc, &b| acc << 8 | b as u32)
+}
+
+pub(crate) fn utf16_to_string(bytes: &[u8]) -> Option<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_be_bytes([c[0], c[1]]))
+        .collect();
+
+    String::from_utf16(&units).ok()
+}
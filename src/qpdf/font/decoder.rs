@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+
+use super::{
+    cmap::{QPDFCMap, utf16_to_string},
+    encoding::{STANDARD_ENCODING, base_encoding, glyph_to_unicode},
+    metrics::{StandardMetrics, standard_metrics},
+    types::{QPDFFontChar, QPDFFontSubtype},
+};
+use crate::qpdf::{
+    object::{QPDFObjectHandler, types::QPDFIsObjectType},
+    write::QPDFWriteDecodeLevel,
+};
+
+const DEFAULT_WIDTH: f64 = 500.0;
+const DEFAULT_CID_WIDTH: f64 = 1000.0;
+const DEFAULT_ASCENT: f64 = 800.0;
+const DEFAULT_DESCENT: f64 = -200.0;
+
+#[derive(Debug, Clone)]
+enum QPDFFontCodes {
+    Simple(Vec<Option<String>>),
+    Composite { cmap: QPDFCMap, unicode: bool },
+}
+
+#[derive(Debug, Clone)]
+pub struct QPDFFontDecoder {
+    codes: QPDFFontCodes,
+    to_unicode: Option<QPDFCMap>,
+    base_font: Option<String>,
+    widths: HashMap<u32, f64>,
+    default_width: f64,
+    standard: Option<&'static StandardMetrics>,
+    scale: f64,
+    ascent: f64,
+    descent: f64,
+}
+
+// Construction
+impl QPDFFontDecoder {
+    pub fn from_object(font: &QPDFObjectHandler) -> Self {
+        let key = |object: &QPDFObjectHandler, key: &str| object.dict_get_key(key.to_string());
+
+        let subtype =
+            QPDFFontSubtype::from(key(font, "/Subtype").name().unwrap_or_default().as_str());
+
+        let to_unicode = key(font, "/ToUnicode");
+        let to_unicode = match to_unicode.is(QPDFIsObjectType::Stream) {
+            true => read_stream(&to_unicode).map(|data| QPDFCMap::parse(&data)),
+            _ => None,
+        };
+
+        // Type3 glyphs are measured in their own glyph space rather than thousandths of a unit
+        let scale = match subtype {
+            QPDFFontSubtype::Type3 => numbers(&key(font, "/FontMatrix"))
+                .first()
+                .copied()
+                .unwrap_or(0.001),
+            _ => 0.001,
+        };
+
+        let base_font = key(font, "/BaseFont")
+            .name()
+            .ok()
+            .map(|n| n.trim_start_matches('/').to_string());
+
+        // Composite fonts keep their metrics in the descendant font
+        let (codes, descendant, (widths, default_width)) = match subtype {
+            QPDFFontSubtype::Type0 => {
+                let descendant = key(font, "/DescendantFonts").array_get_at(0);
+                let widths = cid_widths(&descendant);
+                (composite_codes(&key(font, "/Encoding")), descendant, widths)
+            }
+            _ => (
+                QPDFFontCodes::Simple(simple_codes(&key(font, "/Encoding"))),
+                font.clone(),
+                simple_widths(font),
+            ),
+        };
+
+        // Standard 14 fonts may omit their widths and rely on the built-in metrics
+        let standard = match (&codes, widths.is_empty()) {
+            (QPDFFontCodes::Simple(_), true) => base_font.as_deref().and_then(standard_metrics),
+            _ => None,
+        };
+
+        let descriptor = key(&descendant, "/FontDescriptor");
+        let metric = |name: &str, standard_value: Option<f64>, default: f64| {
+            TryInto::<f64>::try_into(key(&descriptor, name))
+                .ok()
+                .filter(|v| *v != 0.0)
+                .or(standard_value)
+                .unwrap_or(default)
+                * 0.001
+        };
+
+        let (ascent, descent) = match subtype {
+            QPDFFontSubtype::Type3 => {
+                let bbox = numbers(&key(font, "/FontBBox"));
+                let (lly, ury) = (bbox.get(1).copied(), bbox.get(3).copied());
+                (ury.unwrap_or(800.0) * scale, lly.unwrap_or(-200.0) * scale)
+            }
+            _ => (
+                metric("/Ascent", standard.map(|m| m.ascent), DEFAULT_ASCENT),
+                metric("/Descent", standard.map(|m| m.descent), DEFAULT_DESCENT),
+            ),
+        };
+
+        Self {
+            codes,
+            to_unicode,
+            base_font,
+            widths,
+            default_width,
+            standard,
+            scale,
+            ascent,
+            descent,
+        }
+    }
+}
+
+// Metrics
+impl QPDFFontDecoder {
+    pub fn base_font(&self) -> Option<&str> {
+        self.base_font.as_deref()
+    }
+
+    // Ascent and descent are in text space units for a font size of 1
+    pub fn ascent(&self) -> f64 {
+        self.ascent
+    }
+
+    pub fn descent(&self) -> f64 {
+        self.descent
+    }
+}
+
+// Encoding
+impl QPDFFontDecoder {
+    // Maps text back to single-byte codes, characters the font cannot show become '?'
+    pub fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        if matches!(self.codes, QPDFFontCodes::Composite { .. }) {
+            return None;
+        }
+
+        let mut codes: HashMap<char, u8> = HashMap::new();
+        for code in (0..=255u8).rev() {
+            let text = self.text(code as u32, 1);
+            let mut chars = text.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                codes.insert(c, code);
+            }
+        }
+
+        let fallback = codes.get(&'?').copied().unwrap_or(b'?');
+        Some(
+            text.chars()
+                .map(|c| codes.get(&c).copied().unwrap_or(fallback))
+                .collect(),
+        )
+    }
+}
+
+// Decoding
+impl QPDFFontDecoder {
+    pub fn decode(&self, bytes: &[u8]) -> Vec<QPDFFontChar> {
+        let mut chars = Vec::new();
+        let mut rest = bytes;
+
+        while !rest.is_empty() {
+            let (code, length) = self.next_code(rest);
+            rest = &rest[length..];
+
+            let text = self.text(code, length);
+
+            let width_key = match &self.codes {
+                QPDFFontCodes::Composite { cmap, .. } => cmap.cid(code, length).unwrap_or(0),
+                QPDFFontCodes::Simple(_) => code,
+            };
+
+            let width = match (self.widths.get(&width_key), self.standard) {
+                (Some(width), _) => *width,
+                (None, Some(metrics)) => metrics.width(text.chars().next().unwrap_or(' ')),
+                _ => self.default_width,
+            };
+
+            chars.push(QPDFFontChar {
+                code,
+                text,
+                width: width * self.scale,
+                // Word spacing only applies to the single-byte code 32
+                is_space: length == 1 && code == 32,
+            });
+        }
+
+        chars
+    }
+
+    fn next_code(&self, data: &[u8]) -> (u32, usize) {
+        match &self.codes {
+            QPDFFontCodes::Composite { cmap, .. } => cmap.next_code(data, 2),
+            QPDFFontCodes::Simple(_) => (data[0] as u32, 1),
+        }
+    }
+
+    fn text(&self, code: u32, length: usize) -> String {
+        if let Some(text) = self
+            .to_unicode
+            .as_ref()
+            .and_then(|c| c.unicode(code, length))
+        {
+            return text.clone();
+        }
+
+        let text = match &self.codes {
+            QPDFFontCodes::Simple(table) => table.get(code as usize).cloned().flatten(),
+            QPDFFontCodes::Composite { unicode: true, .. } => {
+                utf16_to_string(&code.to_be_bytes()[4 - length..])
+            }
+            QPDFFontCodes::Composite { .. } => None,
+        };
+
+        text.unwrap_or_else(|| char::REPLACEMENT_CHARACTER.to_string())
+    }
+}
+
+fn simple_codes(encoding: &QPDFObjectHandler) -> Vec<Option<String>> {
+    let base_name = match encoding.is(QPDFIsObjectType::Dictionary) {
+        true => encoding.dict_get_key("/BaseEncoding".to_string()).name(),
+        _ => encoding.name(),
+    };
+
+    let base = base_name
+        .ok()
+        .and_then(|name| base_encoding(&name))
+        .unwrap_or(&STANDARD_ENCODING);
+
+    let mut table: Vec<Option<String>> = base.iter().map(|c| c.map(String::from)).collect();
+
+    // Differences are a sequence of starting codes, each followed by the glyph names from there
+    let differences = encoding.dict_get_key("/Differences".to_string());
+    let mut code = 0usize;
+
+    for at in 0..differences.array_len() {
+        let item = differences.array_get_at(at);
+
+        if let Ok(name) = item.name() {
+            if let Some(entry) = table.get_mut(code) {
+                *entry = glyph_to_unicode(&name);
+            }
+            code += 1;
+        } else if let Ok(start) = TryInto::<i64>::try_into(item) {
+            code = start.max(0) as usize;
+        }
+    }
+
+    table
+}
+
+fn composite_codes(encoding: &QPDFObjectHandler) -> QPDFFontCodes {
+    if encoding.is(QPDFIsObjectType::Stream) {
+        return QPDFFontCodes::Composite {
+            cmap: read_stream(encoding)
+                .map(|data| QPDFCMap::parse(&data))
+                .unwrap_or_else(QPDFCMap::identity),
+            unicode: false,
+        };
+    }
+
+    let name = encoding.name().unwrap_or_default();
+
+    // Predefined UCS-2 and UTF-16 CMaps use the Unicode value itself as the character code
+    QPDFFontCodes::Composite {
+        cmap: QPDFCMap::identity(),
+        unicode: name.contains("UCS2") || name.contains("UTF16"),
+    }
+}
+
+fn simple_widths(font: &QPDFObjectHandler) -> (HashMap<u32, f64>, f64) {
+    let first_char: i64 = font
+        .dict_get_key("/FirstChar".to_string())
+        .try_into()
+        .unwrap_or(0);
+
+    let widths = numbers(&font.dict_get_key("/Widths".to_string()))
+        .into_iter()
+        .enumerate()
+        .map(|(i, w)| ((first_char.max(0) as usize + i) as u32, w))
+        .collect();
+
+    let default_width = font
+        .dict_get_key("/FontDescriptor".to_string())
+        .dict_get_key("/MissingWidth".to_string())
+        .try_into()
+        .unwrap_or(DEFAULT_WIDTH);
+
+    (widths, default_width)
+}
+
+fn cid_widths(descendant: &QPDFObjectHandler) -> (HashMap<u32, f64>, f64) {
+    let default_width = descendant
+        .dict_get_key("/DW".to_string())
+        .try_into()
+        .unwrap_or(DEFAULT_CID_WIDTH);
+
+    let w = descendant.dict_get_key("/W".to_string());
+    let mut widths = HashMap::new();
+    let mut at = 0;
+
+    // Entries are either `c [w1 w2 ...]` or `c_first c_last w`
+    while at < w.array_len() {
+        let Ok(first) = TryInto::<i64>::try_into(w.array_get_at(at)) else {
+            break;
+        };
+        let first = first.max(0) as u32;
+        let next = w.array_get_at(at + 1);
+
+        if next.is(QPDFIsObjectType::Array) {
+            for (i, width) in numbers(&next).into_iter().enumerate() {
+                widths.insert(first + i as u32, width);
+            }
+            at += 2;
+            continue;
+        }
+
+        let (Ok(last), Ok(width)) = (
+            TryInto::<i64>::try_into(next),
+            TryInto::<f64>::try_into(w.array_get_at(at + 2)),
+        ) else {
+            break;
+        };
+
+        let last = (last.max(0) as u32).min(first.saturating_add(0xffff));
+        for cid in first..=last {
+            widths.insert(cid, width);
+        }
+        at += 3;
+    }
+
+    (widths, default_width)
+}
+
+fn numbers(array: &QPDFObjectHandler) -> Vec<f64> {
+    (0..array.array_len())
+        .map(|at| array.array_get_at(at).try_into().unwrap_or(0.0))
+        .collect()
+}
+
+fn read_stream(stream: &QPDFObjectHandler) -> Option<Vec<u8>> {
+    stream
+        .stream_data(QPDFWriteDecodeLevel::Generalized)
+        .ok()
+        .map(|d| d.data)
+}
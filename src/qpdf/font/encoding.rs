@@ -0,0 +1,1101 @@
+// Base encodings for simple fonts, indexed by character code
+pub(crate) static STANDARD_ENCODING: [Option<char>; 256] = [
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some(' '),
+    Some('!'),
+    Some('"'),
+    Some('#'),
+    Some('$'),
+    Some('%'),
+    Some('&'),
+    Some('\u{2019}'),
+    Some('('),
+    Some(')'),
+    Some('*'),
+    Some('+'),
+    Some(','),
+    Some('-'),
+    Some('.'),
+    Some('/'),
+    Some('0'),
+    Some('1'),
+    Some('2'),
+    Some('3'),
+    Some('4'),
+    Some('5'),
+    Some('6'),
+    Some('7'),
+    Some('8'),
+    Some('9'),
+    Some(':'),
+    Some(';'),
+    Some('<'),
+    Some('='),
+    Some('>'),
+    Some('?'),
+    Some('@'),
+    Some('A'),
+    Some('B'),
+    Some('C'),
+    Some('D'),
+    Some('E'),
+    Some('F'),
+    Some('G'),
+    Some('H'),
+    Some('I'),
+    Some('J'),
+    Some('K'),
+    Some('L'),
+    Some('M'),
+    Some('N'),
+    Some('O'),
+    Some('P'),
+    Some('Q'),
+    Some('R'),
+    Some('S'),
+    Some('T'),
+    Some('U'),
+    Some('V'),
+    Some('W'),
+    Some('X'),
+    Some('Y'),
+    Some('Z'),
+    Some('['),
+    Some('\\'),
+    Some(']'),
+    Some('^'),
+    Some('_'),
+    Some('\u{2018}'),
+    Some('a'),
+    Some('b'),
+    Some('c'),
+    Some('d'),
+    Some('e'),
+    Some('f'),
+    Some('g'),
+    Some('h'),
+    Some('i'),
+    Some('j'),
+    Some('k'),
+    Some('l'),
+    Some('m'),
+    Some('n'),
+    Some('o'),
+    Some('p'),
+    Some('q'),
+    Some('r'),
+    Some('s'),
+    Some('t'),
+    Some('u'),
+    Some('v'),
+    Some('w'),
+    Some('x'),
+    Some('y'),
+    Some('z'),
+    Some('{'),
+    Some('|'),
+    Some('}'),
+    Some('~'),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some('\u{a1}'),
+    Some('\u{a2}'),
+    Some('\u{a3}'),
+    Some('\u{2044}'),
+    Some('\u{a5}'),
+    Some('\u{192}'),
+    Some('\u{a7}'),
+    Some('\u{a4}'),
+    Some('\''),
+    Some('\u{201c}'),
+    Some('\u{ab}'),
+    Some('\u{2039}'),
+    Some('\u{203a}'),
+    Some('\u{fb01}'),
+    Some('\u{fb02}'),
+    None,
+    Some('\u{2013}'),
+    Some('\u{2020}'),
+    Some('\u{2021}'),
+    Some('\u{b7}'),
+    None,
+    Some('\u{b6}'),
+    Some('\u{2022}'),
+    Some('\u{201a}'),
+    Some('\u{201e}'),
+    Some('\u{201d}'),
+    Some('\u{bb}'),
+    Some('\u{2026}'),
+    Some('\u{2030}'),
+    None,
+    Some('\u{bf}'),
+    None,
+    Some('`'),
+    Some('\u{b4}'),
+    Some('\u{2c6}'),
+    Some('\u{2dc}'),
+    Some('\u{2c9}'),
+    Some('\u{2d8}'),
+    Some('\u{2d9}'),
+    Some('\u{a8}'),
+    None,
+    Some('\u{2da}'),
+    Some('\u{b8}'),
+    None,
+    Some('\u{2dd}'),
+    Some('\u{2db}'),
+    Some('\u{2c7}'),
+    Some('\u{2014}'),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some('\u{c6}'),
+    None,
+    Some('\u{aa}'),
+    None,
+    None,
+    None,
+    None,
+    Some('\u{141}'),
+    Some('\u{d8}'),
+    Some('\u{152}'),
+    Some('\u{ba}'),
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some('\u{e6}'),
+    None,
+    None,
+    None,
+    Some('\u{131}'),
+    None,
+    None,
+    Some('\u{142}'),
+    Some('\u{f8}'),
+    Some('\u{153}'),
+    Some('\u{df}'),
+    None,
+    None,
+    None,
+    None,
+];
+
+pub(crate) static WIN_ANSI_ENCODING: [Option<char>; 256] = [
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some(' '),
+    Some('!'),
+    Some('"'),
+    Some('#'),
+    Some('$'),
+    Some('%'),
+    Some('&'),
+    Some('\''),
+    Some('('),
+    Some(')'),
+    Some('*'),
+    Some('+'),
+    Some(','),
+    Some('-'),
+    Some('.'),
+    Some('/'),
+    Some('0'),
+    Some('1'),
+    Some('2'),
+    Some('3'),
+    Some('4'),
+    Some('5'),
+    Some('6'),
+    Some('7'),
+    Some('8'),
+    Some('9'),
+    Some(':'),
+    Some(';'),
+    Some('<'),
+    Some('='),
+    Some('>'),
+    Some('?'),
+    Some('@'),
+    Some('A'),
+    Some('B'),
+    Some('C'),
+    Some('D'),
+    Some('E'),
+    Some('F'),
+    Some('G'),
+    Some('H'),
+    Some('I'),
+    Some('J'),
+    Some('K'),
+    Some('L'),
+    Some('M'),
+    Some('N'),
+    Some('O'),
+    Some('P'),
+    Some('Q'),
+    Some('R'),
+    Some('S'),
+    Some('T'),
+    Some('U'),
+    Some('V'),
+    Some('W'),
+    Some('X'),
+    Some('Y'),
+    Some('Z'),
+    Some('['),
+    Some('\\'),
+    Some(']'),
+    Some('^'),
+    Some('_'),
+    Some('`'),
+    Some('a'),
+    Some('b'),
+    Some('c'),
+    Some('d'),
+    Some('e'),
+    Some('f'),
+    Some('g'),
+    Some('h'),
+    Some('i'),
+    Some('j'),
+    Some('k'),
+    Some('l'),
+    Some('m'),
+    Some('n'),
+    Some('o'),
+    Some('p'),
+    Some('q'),
+    Some('r'),
+    Some('s'),
+    Some('t'),
+    Some('u'),
+    Some('v'),
+    Some('w'),
+    Some('x'),
+    Some('y'),
+    Some('z'),
+    Some('{'),
+    Some('|'),
+    Some('}'),
+    Some('~'),
+    None,
+    Some('\u{20ac}'),
+    None,
+    Some('\u{201a}'),
+    Some('\u{192}'),
+    Some('\u{201e}'),
+    Some('\u{2026}'),
+    Some('\u{2020}'),
+    Some('\u{2021}'),
+    Some('\u{2c6}'),
+    Some('\u{2030}'),
+    Some('\u{160}'),
+    Some('\u{2039}'),
+    Some('\u{152}'),
+    None,
+    Some('\u{17d}'),
+    None,
+    None,
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{201c}'),
+    Some('\u{201d}'),
+    Some('\u{2022}'),
+    Some('\u{2013}'),
+    Some('\u{2014}'),
+    Some('\u{2dc}'),
+    Some('\u{2122}'),
+    Some('\u{161}'),
+    Some('\u{203a}'),
+    Some('\u{153}'),
+    None,
+    Some('\u{17e}'),
+    Some('\u{178}'),
+    Some('\u{a0}'),
+    Some('\u{a1}'),
+    Some('\u{a2}'),
+    Some('\u{a3}'),
+    Some('\u{a4}'),
+    Some('\u{a5}'),
+    Some('\u{a6}'),
+    Some('\u{a7}'),
+    Some('\u{a8}'),
+    Some('\u{a9}'),
+    Some('\u{aa}'),
+    Some('\u{ab}'),
+    Some('\u{ac}'),
+    Some('-'),
+    Some('\u{ae}'),
+    Some('\u{af}'),
+    Some('\u{b0}'),
+    Some('\u{b1}'),
+    Some('\u{b2}'),
+    Some('\u{b3}'),
+    Some('\u{b4}'),
+    Some('\u{b5}'),
+    Some('\u{b6}'),
+    Some('\u{b7}'),
+    Some('\u{b8}'),
+    Some('\u{b9}'),
+    Some('\u{ba}'),
+    Some('\u{bb}'),
+    Some('\u{bc}'),
+    Some('\u{bd}'),
+    Some('\u{be}'),
+    Some('\u{bf}'),
+    Some('\u{c0}'),
+    Some('\u{c1}'),
+    Some('\u{c2}'),
+    Some('\u{c3}'),
+    Some('\u{c4}'),
+    Some('\u{c5}'),
+    Some('\u{c6}'),
+    Some('\u{c7}'),
+    Some('\u{c8}'),
+    Some('\u{c9}'),
+    Some('\u{ca}'),
+    Some('\u{cb}'),
+    Some('\u{cc}'),
+    Some('\u{cd}'),
+    Some('\u{ce}'),
+    Some('\u{cf}'),
+    Some('\u{d0}'),
+    Some('\u{d1}'),
+    Some('\u{d2}'),
+    Some('\u{d3}'),
+    Some('\u{d4}'),
+    Some('\u{d5}'),
+    Some('\u{d6}'),
+    Some('\u{d7}'),
+    Some('\u{d8}'),
+    Some('\u{d9}'),
+    Some('\u{da}'),
+    Some('\u{db}'),
+    Some('\u{dc}'),
+    Some('\u{dd}'),
+    Some('\u{de}'),
+    Some('\u{df}'),
+    Some('\u{e0}'),
+    Some('\u{e1}'),
+    Some('\u{e2}'),
+    Some('\u{e3}'),
+    Some('\u{e4}'),
+    Some('\u{e5}'),
+    Some('\u{e6}'),
+    Some('\u{e7}'),
+    Some('\u{e8}'),
+    Some('\u{e9}'),
+    Some('\u{ea}'),
+    Some('\u{eb}'),
+    Some('\u{ec}'),
+    Some('\u{ed}'),
+    Some('\u{ee}'),
+    Some('\u{ef}'),
+    Some('\u{f0}'),
+    Some('\u{f1}'),
+    Some('\u{f2}'),
+    Some('\u{f3}'),
+    Some('\u{f4}'),
+    Some('\u{f5}'),
+    Some('\u{f6}'),
+    Some('\u{f7}'),
+    Some('\u{f8}'),
+    Some('\u{f9}'),
+    Some('\u{fa}'),
+    Some('\u{fb}'),
+    Some('\u{fc}'),
+    Some('\u{fd}'),
+    Some('\u{fe}'),
+    Some('\u{ff}'),
+];
+
+pub(crate) static MAC_ROMAN_ENCODING: [Option<char>; 256] = [
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some(' '),
+    Some('!'),
+    Some('"'),
+    Some('#'),
+    Some('$'),
+    Some('%'),
+    Some('&'),
+    Some('\''),
+    Some('('),
+    Some(')'),
+    Some('*'),
+    Some('+'),
+    Some(','),
+    Some('-'),
+    Some('.'),
+    Some('/'),
+    Some('0'),
+    Some('1'),
+    Some('2'),
+    Some('3'),
+    Some('4'),
+    Some('5'),
+    Some('6'),
+    Some('7'),
+    Some('8'),
+    Some('9'),
+    Some(':'),
+    Some(';'),
+    Some('<'),
+    Some('='),
+    Some('>'),
+    Some('?'),
+    Some('@'),
+    Some('A'),
+    Some('B'),
+    Some('C'),
+    Some('D'),
+    Some('E'),
+    Some('F'),
+    Some('G'),
+    Some('H'),
+    Some('I'),
+    Some('J'),
+    Some('K'),
+    Some('L'),
+    Some('M'),
+    Some('N'),
+    Some('O'),
+    Some('P'),
+    Some('Q'),
+    Some('R'),
+    Some('S'),
+    Some('T'),
+    Some('U'),
+    Some('V'),
+    Some('W'),
+    Some('X'),
+    Some('Y'),
+    Some('Z'),
+    Some('['),
+    Some('\\'),
+    Some(']'),
+    Some('^'),
+    Some('_'),
+    Some('`'),
+    Some('a'),
+    Some('b'),
+    Some('c'),
+    Some('d'),
+    Some('e'),
+    Some('f'),
+    Some('g'),
+    Some('h'),
+    Some('i'),
+    Some('j'),
+    Some('k'),
+    Some('l'),
+    Some('m'),
+    Some('n'),
+    Some('o'),
+    Some('p'),
+    Some('q'),
+    Some('r'),
+    Some('s'),
+    Some('t'),
+    Some('u'),
+    Some('v'),
+    Some('w'),
+    Some('x'),
+    Some('y'),
+    Some('z'),
+    Some('{'),
+    Some('|'),
+    Some('}'),
+    Some('~'),
+    None,
+    Some('\u{c4}'),
+    Some('\u{c5}'),
+    Some('\u{c7}'),
+    Some('\u{c9}'),
+    Some('\u{d1}'),
+    Some('\u{d6}'),
+    Some('\u{dc}'),
+    Some('\u{e1}'),
+    Some('\u{e0}'),
+    Some('\u{e2}'),
+    Some('\u{e4}'),
+    Some('\u{e3}'),
+    Some('\u{e5}'),
+    Some('\u{e7}'),
+    Some('\u{e9}'),
+    Some('\u{e8}'),
+    Some('\u{ea}'),
+    Some('\u{eb}'),
+    Some('\u{ed}'),
+    Some('\u{ec}'),
+    Some('\u{ee}'),
+    Some('\u{ef}'),
+    Some('\u{f1}'),
+    Some('\u{f3}'),
+    Some('\u{f2}'),
+    Some('\u{f4}'),
+    Some('\u{f6}'),
+    Some('\u{f5}'),
+    Some('\u{fa}'),
+    Some('\u{f9}'),
+    Some('\u{fb}'),
+    Some('\u{fc}'),
+    Some('\u{2020}'),
+    Some('\u{b0}'),
+    Some('\u{a2}'),
+    Some('\u{a3}'),
+    Some('\u{a7}'),
+    Some('\u{2022}'),
+    Some('\u{b6}'),
+    Some('\u{df}'),
+    Some('\u{ae}'),
+    Some('\u{a9}'),
+    Some('\u{2122}'),
+    Some('\u{b4}'),
+    Some('\u{a8}'),
+    Some('\u{2260}'),
+    Some('\u{c6}'),
+    Some('\u{d8}'),
+    Some('\u{221e}'),
+    Some('\u{b1}'),
+    Some('\u{2264}'),
+    Some('\u{2265}'),
+    Some('\u{a5}'),
+    Some('\u{b5}'),
+    Some('\u{2202}'),
+    Some('\u{2211}'),
+    Some('\u{220f}'),
+    Some('\u{3c0}'),
+    Some('\u{222b}'),
+    Some('\u{aa}'),
+    Some('\u{ba}'),
+    Some('\u{3a9}'),
+    Some('\u{e6}'),
+    Some('\u{f8}'),
+    Some('\u{bf}'),
+    Some('\u{a1}'),
+    Some('\u{ac}'),
+    Some('\u{221a}'),
+    Some('\u{192}'),
+    Some('\u{2248}'),
+    Some('\u{2206}'),
+    Some('\u{ab}'),
+    Some('\u{bb}'),
+    Some('\u{2026}'),
+    Some('\u{a0}'),
+    Some('\u{c0}'),
+    Some('\u{c3}'),
+    Some('\u{d5}'),
+    Some('\u{152}'),
+    Some('\u{153}'),
+    Some('\u{2013}'),
+    Some('\u{2014}'),
+    Some('\u{201c}'),
+    Some('\u{201d}'),
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{f7}'),
+    Some('\u{25ca}'),
+    Some('\u{ff}'),
+    Some('\u{178}'),
+    Some('\u{2044}'),
+    Some('\u{a4}'),
+    Some('\u{2039}'),
+    Some('\u{203a}'),
+    Some('\u{fb01}'),
+    Some('\u{fb02}'),
+    Some('\u{2021}'),
+    Some('\u{b7}'),
+    Some('\u{201a}'),
+    Some('\u{201e}'),
+    Some('\u{2030}'),
+    Some('\u{c2}'),
+    Some('\u{ca}'),
+    Some('\u{c1}'),
+    Some('\u{cb}'),
+    Some('\u{c8}'),
+    Some('\u{cd}'),
+    Some('\u{ce}'),
+    Some('\u{cf}'),
+    Some('\u{cc}'),
+    Some('\u{d3}'),
+    Some('\u{d4}'),
+    Some('\u{f8ff}'),
+    Some('\u{d2}'),
+    Some('\u{da}'),
+    Some('\u{db}'),
+    Some('\u{d9}'),
+    Some('\u{131}'),
+    Some('\u{2c6}'),
+    Some('\u{2dc}'),
+    Some('\u{af}'),
+    Some('\u{2d8}'),
+    Some('\u{2d9}'),
+    Some('\u{2da}'),
+    Some('\u{b8}'),
+    Some('\u{2dd}'),
+    Some('\u{2db}'),
+    Some('\u{2c7}'),
+];
+
+// Adobe Glyph List entries for the glyphs used by the base encodings, sorted by name
+pub(crate) static GLYPH_NAMES: [(&str, char); 268] = [
+    ("A", 'A'),
+    ("AE", '\u{c6}'),
+    ("Aacute", '\u{c1}'),
+    ("Acircumflex", '\u{c2}'),
+    ("Adieresis", '\u{c4}'),
+    ("Agrave", '\u{c0}'),
+    ("Aring", '\u{c5}'),
+    ("Atilde", '\u{c3}'),
+    ("B", 'B'),
+    ("C", 'C'),
+    ("Ccedilla", '\u{c7}'),
+    ("D", 'D'),
+    ("Delta", '\u{2206}'),
+    ("E", 'E'),
+    ("Eacute", '\u{c9}'),
+    ("Ecircumflex", '\u{ca}'),
+    ("Edieresis", '\u{cb}'),
+    ("Egrave", '\u{c8}'),
+    ("Eth", '\u{d0}'),
+    ("Euro", '\u{20ac}'),
+    ("F", 'F'),
+    ("G", 'G'),
+    ("Gbreve", '\u{11e}'),
+    ("H", 'H'),
+    ("I", 'I'),
+    ("Iacute", '\u{cd}'),
+    ("Icircumflex", '\u{ce}'),
+    ("Idieresis", '\u{cf}'),
+    ("Idotaccent", '\u{130}'),
+    ("Igrave", '\u{cc}'),
+    ("J", 'J'),
+    ("K", 'K'),
+    ("L", 'L'),
+    ("Lslash", '\u{141}'),
+    ("M", 'M'),
+    ("N", 'N'),
+    ("Ntilde", '\u{d1}'),
+    ("O", 'O'),
+    ("OE", '\u{152}'),
+    ("Oacute", '\u{d3}'),
+    ("Ocircumflex", '\u{d4}'),
+    ("Odieresis", '\u{d6}'),
+    ("Ograve", '\u{d2}'),
+    ("Omega", '\u{3a9}'),
+    ("Oslash", '\u{d8}'),
+    ("Otilde", '\u{d5}'),
+    ("P", 'P'),
+    ("Q", 'Q'),
+    ("R", 'R'),
+    ("S", 'S'),
+    ("Scaron", '\u{160}'),
+    ("Scedilla", '\u{15e}'),
+    ("T", 'T'),
+    ("Tcedilla", '\u{162}'),
+    ("Thorn", '\u{de}'),
+    ("U", 'U'),
+    ("Uacute", '\u{da}'),
+    ("Ucircumflex", '\u{db}'),
+    ("Udieresis", '\u{dc}'),
+    ("Ugrave", '\u{d9}'),
+    ("V", 'V'),
+    ("W", 'W'),
+    ("X", 'X'),
+    ("Y", 'Y'),
+    ("Yacute", '\u{dd}'),
+    ("Ydieresis", '\u{178}'),
+    ("Z", 'Z'),
+    ("Zcaron", '\u{17d}'),
+    ("a", 'a'),
+    ("aacute", '\u{e1}'),
+    ("acircumflex", '\u{e2}'),
+    ("acute", '\u{b4}'),
+    ("adieresis", '\u{e4}'),
+    ("ae", '\u{e6}'),
+    ("agrave", '\u{e0}'),
+    ("ampersand", '&'),
+    ("apple", '\u{f8ff}'),
+    ("approxequal", '\u{2248}'),
+    ("aring", '\u{e5}'),
+    ("arrowdown", '\u{2193}'),
+    ("arrowleft", '\u{2190}'),
+    ("arrowright", '\u{2192}'),
+    ("arrowup", '\u{2191}'),
+    ("asciicircum", '^'),
+    ("asciitilde", '~'),
+    ("asterisk", '*'),
+    ("at", '@'),
+    ("atilde", '\u{e3}'),
+    ("b", 'b'),
+    ("backslash", '\\'),
+    ("bar", '|'),
+    ("braceleft", '{'),
+    ("braceright", '}'),
+    ("bracketleft", '['),
+    ("bracketright", ']'),
+    ("breve", '\u{2d8}'),
+    ("brokenbar", '\u{a6}'),
+    ("bullet", '\u{2022}'),
+    ("c", 'c'),
+    ("caron", '\u{2c7}'),
+    ("ccedilla", '\u{e7}'),
+    ("cedilla", '\u{b8}'),
+    ("cent", '\u{a2}'),
+    ("checkmark", '\u{2713}'),
+    ("circumflex", '\u{2c6}'),
+    ("colon", ':'),
+    ("comma", ','),
+    ("copyright", '\u{a9}'),
+    ("copyrightserif", '\u{a9}'),
+    ("currency", '\u{a4}'),
+    ("d", 'd'),
+    ("dagger", '\u{2020}'),
+    ("daggerdbl", '\u{2021}'),
+    ("degree", '\u{b0}'),
+    ("dieresis", '\u{a8}'),
+    ("divide", '\u{f7}'),
+    ("dollar", '$'),
+    ("dotaccent", '\u{2d9}'),
+    ("dotlessi", '\u{131}'),
+    ("dotlessj", '\u{237}'),
+    ("e", 'e'),
+    ("eacute", '\u{e9}'),
+    ("ecircumflex", '\u{ea}'),
+    ("edieresis", '\u{eb}'),
+    ("egrave", '\u{e8}'),
+    ("eight", '8'),
+    ("ellipsis", '\u{2026}'),
+    ("emdash", '\u{2014}'),
+    ("endash", '\u{2013}'),
+    ("equal", '='),
+    ("eth", '\u{f0}'),
+    ("exclam", '!'),
+    ("exclamdown", '\u{a1}'),
+    ("f", 'f'),
+    ("ff", '\u{fb00}'),
+    ("ffi", '\u{fb03}'),
+    ("ffl", '\u{fb04}'),
+    ("fi", '\u{fb01}'),
+    ("five", '5'),
+    ("fl", '\u{fb02}'),
+    ("florin", '\u{192}'),
+    ("four", '4'),
+    ("fraction", '\u{2044}'),
+    ("g", 'g'),
+    ("gbreve", '\u{11f}'),
+    ("germandbls", '\u{df}'),
+    ("grave", '`'),
+    ("greater", '>'),
+    ("greaterequal", '\u{2265}'),
+    ("guillemotleft", '\u{ab}'),
+    ("guillemotright", '\u{bb}'),
+    ("guilsinglleft", '\u{2039}'),
+    ("guilsinglright", '\u{203a}'),
+    ("h", 'h'),
+    ("hungarumlaut", '\u{2dd}'),
+    ("hyphen", '-'),
+    ("i", 'i'),
+    ("iacute", '\u{ed}'),
+    ("icircumflex", '\u{ee}'),
+    ("idieresis", '\u{ef}'),
+    ("igrave", '\u{ec}'),
+    ("infinity", '\u{221e}'),
+    ("integral", '\u{222b}'),
+    ("j", 'j'),
+    ("k", 'k'),
+    ("l", 'l'),
+    ("less", '<'),
+    ("lessequal", '\u{2264}'),
+    ("logicalnot", '\u{ac}'),
+    ("lozenge", '\u{25ca}'),
+    ("lslash", '\u{142}'),
+    ("m", 'm'),
+    ("macron", '\u{2c9}'),
+    ("middot", '\u{b7}'),
+    ("minus", '\u{2212}'),
+    ("mu", '\u{b5}'),
+    ("multiply", '\u{d7}'),
+    ("n", 'n'),
+    ("nbspace", '\u{a0}'),
+    ("nine", '9'),
+    ("notequal", '\u{2260}'),
+    ("ntilde", '\u{f1}'),
+    ("numbersign", '#'),
+    ("o", 'o'),
+    ("oacute", '\u{f3}'),
+    ("ocircumflex", '\u{f4}'),
+    ("odieresis", '\u{f6}'),
+    ("oe", '\u{153}'),
+    ("ogonek", '\u{2db}'),
+    ("ograve", '\u{f2}'),
+    ("one", '1'),
+    ("onedotenleader", '\u{2024}'),
+    ("onehalf", '\u{bd}'),
+    ("onequarter", '\u{bc}'),
+    ("onesuperior", '\u{b9}'),
+    ("ordfeminine", '\u{aa}'),
+    ("ordmasculine", '\u{ba}'),
+    ("oslash", '\u{f8}'),
+    ("otilde", '\u{f5}'),
+    ("p", 'p'),
+    ("paragraph", '\u{b6}'),
+    ("parenleft", '('),
+    ("parenright", ')'),
+    ("partialdiff", '\u{2202}'),
+    ("percent", '%'),
+    ("period", '.'),
+    ("periodcentered", '\u{b7}'),
+    ("perthousand", '\u{2030}'),
+    ("pi", '\u{3c0}'),
+    ("plus", '+'),
+    ("plusminus", '\u{b1}'),
+    ("product", '\u{220f}'),
+    ("q", 'q'),
+    ("question", '?'),
+    ("questiondown", '\u{bf}'),
+    ("quotedbl", '"'),
+    ("quotedblbase", '\u{201e}'),
+    ("quotedblleft", '\u{201c}'),
+    ("quotedblright", '\u{201d}'),
+    ("quoteleft", '\u{2018}'),
+    ("quotereversed", '\u{201b}'),
+    ("quoteright", '\u{2019}'),
+    ("quotesinglbase", '\u{201a}'),
+    ("quotesingle", '\''),
+    ("r", 'r'),
+    ("radical", '\u{221a}'),
+    ("registered", '\u{ae}'),
+    ("registerserif", '\u{ae}'),
+    ("ring", '\u{2da}'),
+    ("s", 's'),
+    ("scaron", '\u{161}'),
+    ("scedilla", '\u{15f}'),
+    ("section", '\u{a7}'),
+    ("semicolon", ';'),
+    ("seven", '7'),
+    ("sfthyphen", '\u{ad}'),
+    ("six", '6'),
+    ("slash", '/'),
+    ("space", ' '),
+    ("sterling", '\u{a3}'),
+    ("summation", '\u{2211}'),
+    ("t", 't'),
+    ("thorn", '\u{fe}'),
+    ("three", '3'),
+    ("threequarters", '\u{be}'),
+    ("threesuperior", '\u{b3}'),
+    ("tilde", '\u{2dc}'),
+    ("trademark", '\u{2122}'),
+    ("trademarkserif", '\u{2122}'),
+    ("two", '2'),
+    ("twodotenleader", '\u{2025}'),
+    ("twosuperior", '\u{b2}'),
+    ("u", 'u'),
+    ("uacute", '\u{fa}'),
+    ("ucircumflex", '\u{fb}'),
+    ("udieresis", '\u{fc}'),
+    ("ugrave", '\u{f9}'),
+    ("underscore", '_'),
+    ("v", 'v'),
+    ("w", 'w'),
+    ("x", 'x'),
+    ("y", 'y'),
+    ("yacute", '\u{fd}'),
+    ("ydieresis", '\u{ff}'),
+    ("yen", '\u{a5}'),
+    ("z", 'z'),
+    ("zcaron", '\u{17e}'),
+    ("zero", '0'),
+];
+
+pub(crate) fn base_encoding(name: &str) -> Option<&'static [Option<char>; 256]> {
+    match name.trim_start_matches('/') {
+        "StandardEncoding" => Some(&STANDARD_ENCODING),
+        "WinAnsiEncoding" => Some(&WIN_ANSI_ENCODING),
+        "MacRomanEncoding" => Some(&MAC_ROMAN_ENCODING),
+        _ => None,
+    }
+}
+
+// Maps a glyph name to text using the glyph list and the uniXXXX / uXXXXXX conventions
+pub fn glyph_to_unicode(name: &str) -> Option<String> {
+    let name = name.trim_start_matches('/');
+
+    // Suffixes such as ".sc" or ".alt" name variants of the same character
+    let base = name.split('.').next().unwrap_or(name);
+
+    if base.contains('_') {
+        return base
+            .split('_')
+            .map(glyph_to_unicode)
+            .collect::<Option<String>>();
+    }
+
+    if let Ok(i) = GLYPH_NAMES.binary_search_by(|(n, _)| (*n).cmp(base)) {
+        return Some(GLYPH_NAMES[i].1.to_string());
+    }
+
+    let hex_chars = |digits: &str, size: usize| {
+        if digits.is_empty() || !digits.len().is_multiple_of(size) {
+            return None;
+        }
+
+        (0..digits.len())
+            .step_by(size)
+            .map(|i| {
+                u32::from_str_radix(digits.get(i..i + size)?, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+            })
+            .collect::<Option<String>>()
+    };
+
+    if let Some(digits) = base.strip_prefix("uni") {
+        return hex_chars(digits, 4);
+    }
+
+    match base.strip_prefix('u') {
+        Some(digits) if (4..=6).contains(&digits.len()) => hex_chars(digits, digits.len()),
+        _ => None,
+    }
+}
@@ -0,0 +1,165 @@
+// Advance widths of the standard 14 fonts, from the Adobe core font metrics
+#[derive(Debug)]
+pub(crate) struct StandardMetrics {
+    // Widths of the printable ASCII characters, U+0020 to U+007E
+    ascii: [u16; 95],
+    // Used for characters outside the table
+    fallback: u16,
+    pub ascent: f64,
+    pub descent: f64,
+}
+
+static HELVETICA: StandardMetrics = StandardMetrics {
+    ascii: [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556,
+        556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722,
+        722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722,
+        667, 944, 667, 667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556,
+        556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500,
+        500, 334, 260, 334, 584,
+    ],
+    fallback: 556,
+    ascent: 718.0,
+    descent: -207.0,
+};
+
+static HELVETICA_BOLD: StandardMetrics = StandardMetrics {
+    ascii: [
+        278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556,
+        556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722,
+        722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722,
+        667, 944, 667, 667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611,
+        611, 278, 278, 556, 278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556,
+        500, 389, 280, 389, 584,
+    ],
+    fallback: 611,
+    ascent: 718.0,
+    descent: -207.0,
+};
+
+static TIMES_ROMAN: StandardMetrics = StandardMetrics {
+    ascii: [
+        250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278, 500, 500,
+        500, 500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444, 921, 722, 667, 667,
+        722, 611, 556, 722, 722, 333, 389, 722, 611, 889, 722, 722, 556, 722, 667, 556, 611, 722,
+        722, 944, 722, 722, 611, 333, 278, 333, 469, 500, 333, 444, 500, 444, 500, 444, 333, 500,
+        500, 278, 278, 500, 278, 778, 500, 500, 500, 500, 333, 389, 278, 500, 500, 722, 500, 500,
+        444, 480, 200, 480, 541,
+    ],
+    fallback: 500,
+    ascent: 683.0,
+    descent: -217.0,
+};
+
+static TIMES_BOLD: StandardMetrics = StandardMetrics {
+    ascii: [
+        250, 333, 555, 500, 500, 1000, 833, 278, 333, 333, 500, 570, 250, 333, 250, 278, 500, 500,
+        500, 500, 500, 500, 500, 500, 500, 500, 333, 333, 570, 570, 570, 500, 930, 722, 667, 722,
+        722, 667, 611, 778, 778, 389, 500, 778, 667, 944, 722, 778, 611, 778, 722, 556, 667, 722,
+        722, 1000, 722, 722, 667, 333, 278, 333, 581, 500, 333, 500, 556, 444, 556, 444, 333, 500,
+        556, 278, 333, 556, 278, 833, 556, 500, 556, 556, 444, 389, 333, 556, 500, 722, 500, 500,
+        444, 394, 220, 394, 520,
+    ],
+    fallback: 500,
+    ascent: 676.0,
+    descent: -205.0,
+};
+
+static TIMES_ITALIC: StandardMetrics = StandardMetrics {
+    ascii: [
+        250, 333, 420, 500, 500, 833, 778, 214, 333, 333, 500, 675, 250, 333, 250, 278, 500, 500,
+        500, 500, 500, 500, 500, 500, 500, 500, 333, 333, 675, 675, 675, 500, 920, 611, 611, 667,
+        722, 611, 611, 722, 722, 333, 444, 667, 556, 833, 667, 722, 611, 722, 611, 500, 556, 722,
+        611, 833, 611, 556, 556, 389, 278, 389, 422, 500, 333, 500, 500, 444, 500, 444, 278, 500,
+        500, 278, 278, 444, 278, 722, 500, 500, 500, 500, 389, 389, 278, 500, 444, 667, 444, 444,
+        389, 400, 275, 400, 541,
+    ],
+    fallback: 500,
+    ascent: 683.0,
+    descent: -205.0,
+};
+
+static TIMES_BOLD_ITALIC: StandardMetrics = StandardMetrics {
+    ascii: [
+        250, 389, 555, 500, 500, 833, 778, 278, 333, 333, 500, 570, 250, 333, 250, 278, 500, 500,
+        500, 500, 500, 500, 500, 500, 500, 500, 333, 333, 570, 570, 570, 500, 832, 667, 667, 667,
+        722, 667, 667, 722, 778, 389, 500, 667, 611, 889, 722, 722, 611, 722, 667, 556, 611, 722,
+        667, 889, 667, 611, 611, 333, 278, 333, 570, 500, 333, 500, 500, 444, 500, 444, 333, 500,
+        556, 278, 278, 500, 278, 778, 556, 500, 500, 500, 389, 389, 278, 556, 444, 667, 500, 444,
+        389, 348, 220, 348, 570,
+    ],
+    fallback: 500,
+    ascent: 699.0,
+    descent: -205.0,
+};
+
+static COURIER: StandardMetrics = StandardMetrics {
+    ascii: [600; 95],
+    fallback: 600,
+    ascent: 629.0,
+    descent: -157.0,
+};
+
+// Symbolic fonts have no Latin glyphs, so only their overall extent is known
+static SYMBOL: StandardMetrics = StandardMetrics {
+    ascii: [0; 95],
+    fallback: 500,
+    ascent: 1010.0,
+    descent: -293.0,
+};
+
+static ZAPF_DINGBATS: StandardMetrics = StandardMetrics {
+    ascii: [0; 95],
+    fallback: 788,
+    ascent: 820.0,
+    descent: -143.0,
+};
+
+impl StandardMetrics {
+    pub fn width(&self, c: char) -> f64 {
+        let width = match c {
+            ' '..='~' => self.ascii[c as usize - 0x20],
+            _ => 0,
+        };
+
+        match width {
+            0 => self.fallback as f64,
+            width => width as f64,
+        }
+    }
+}
+
+// Resolves a base font name, including common aliases such as Arial,Bold, to its standard metrics
+pub(crate) fn standard_metrics(base_font: &str) -> Option<&'static StandardMetrics> {
+    let name = base_font.trim_start_matches('/');
+
+    // Subset prefixes do not change the metrics of the underlying font
+    let name = match super::is_subset_name(name) {
+        true => &name[7..],
+        _ => name,
+    };
+
+    let lower = name.to_ascii_lowercase();
+
+    let bold = lower.contains("bold");
+    let italic = lower.contains("italic") || lower.contains("oblique");
+
+    let metrics = match lower.as_str() {
+        n if n.starts_with("helvetica") || n.starts_with("arial") => match bold {
+            true => &HELVETICA_BOLD,
+            _ => &HELVETICA,
+        },
+        n if n.starts_with("times") => match (bold, italic) {
+            (true, true) => &TIMES_BOLD_ITALIC,
+            (true, false) => &TIMES_BOLD,
+            (false, true) => &TIMES_ITALIC,
+            (false, false) => &TIMES_ROMAN,
+        },
+        n if n.starts_with("courier") => &COURIER,
+        n if n.starts_with("symbol") => &SYMBOL,
+        n if n.starts_with("zapfdingbats") => &ZAPF_DINGBATS,
+        _ => return None,
+    };
+
+    Some(metrics)
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use types::{QPDFFontInfo, QPDFFontSubtype};
+
+use super::object::{
+    QPDFObjectHandler,
+    types::{Generation, ObjectId, QPDFIsObjectType},
+};
+
+// Limits how deeply form XObject resources are followed
+const MAX_RESOURCE_DEPTH: usize = 32;
+
+// Subset fonts are named with six uppercase letters and a plus sign, e.g. ABCDEF+Helvetica
+pub fn is_subset_name(base_font: &str) -> bool {
+    let bytes = base_font.as_bytes();
+
+    bytes.len() > 7 && bytes[6] == b'+' && bytes[..6].iter().all(|c| c.is_ascii_uppercase())
+}
+
+impl QPDFFontInfo {
+    pub fn from_object(name: String, font: &QPDFObjectHandler) -> Self {
+        let key = |object: &QPDFObjectHandler, key: &str| object.dict_get_key(key.to_string());
+
+        let subtype = key(font, "/Subtype").name().unwrap_or_default();
+        let subtype = QPDFFontSubtype::from(subtype.as_str());
+
+        let base_font = key(font, "/BaseFont")
+            .name()
+            .ok()
+            .map(|n| n.trim_start_matches('/').to_string());
+
+        // Composite fonts keep their glyph data in the descendant font
+        let descriptor_owner = match subtype {
+            QPDFFontSubtype::Type0 => key(font, "/DescendantFonts").array_get_at(0),
+            _ => font.clone(),
+        };
+
+        let descriptor = key(&descriptor_owner, "/FontDescriptor");
+        let embedded = subtype == QPDFFontSubtype::Type3
+            || ["/FontFile", "/FontFile2", "/FontFile3"]
+                .iter()
+                .any(|k| key(&descriptor, k).is(QPDFIsObjectType::Stream));
+
+        Self {
+            name,
+            object_id: font.object_id(),
+            generation: font.generation(),
+            subset: base_font.as_deref().is_some_and(is_subset_name),
+            base_font,
+            subtype,
+            encoding: encoding_name(&key(font, "/Encoding"))
+                .map(|n| n.trim_start_matches('/').to_string()),
+            embedded,
+            to_unicode: key(font, "/ToUnicode").is(QPDFIsObjectType::Stream),
+        }
+    }
+}
+
+fn encoding_name(encoding: &QPDFObjectHandler) -> Option<String> {
+    if let Ok(name) = encoding.name() {
+        return Some(name);
+    }
+
+    // Embedded CMaps carry their name in the stream dictionary
+    if encoding.is(QPDFIsObjectType::Stream) {
+        return encoding
+            .dict()
+            .dict_get_key("/CMapName".to_string())
+            .name()
+            .ok();
+    }
+
+    if encoding.is(QPDFIsObjectType::Dictionary) {
+        let base = encoding.dict_get_key("/BaseEncoding".to_string());
+        return Some(base.name().unwrap_or("/Differences".to_string()));
+    }
+
+    None
+}
+
+pub(crate) fn collect_fonts(
+    resources: &QPDFObjectHandler,
+    depth: usize,
+    seen: &mut HashSet<(ObjectId, Generation)>,
+    fonts: &mut Vec<QPDFFontInfo>,
+) {
+    let font_dict = resources.dict_get_key("/Font".to_string());
+
+    for name in font_dict.dict_keys() {
+        let font = font_dict.dict_get_key(name.clone());
+
+        // Direct font dictionaries have no id and are always reported
+        let id = (font.object_id(), font.generation());
+        if !font.is(QPDFIsObjectType::Dictionary) || (id.0 != 0 && !seen.insert(id)) {
+            continue;
+        }
+
+        fonts.push(QPDFFontInfo::from_object(name, &font));
+    }
+
+    if depth >= MAX_RESOURCE_DEPTH {
+        return;
+    }
+
+    let xobjects = resources.dict_get_key("/XObject".to_string());
+
+    for name in xobjects.dict_keys() {
+        let xobject = xobjects.dict_get_key(name);
+        let dict = xobject.dict();
+
+        let is_form = xobject.is(QPDFIsObjectType::Stream)
+            && dict
+                .dict_get_key("/Subtype".to_string())
+                .is(QPDFIsObjectType::NameEquals("/Form".to_string()));
+
+        // Forms are marked as seen too, so shared or cyclic forms are only walked once
+        if !is_form || !seen.insert((xobject.object_id(), xobject.generation())) {
+            continue;
+        }
+
+        let form_resources = dict.dict_get_key("/Resources".to_string());
+        if form_resources.is(QPDFIsObjectType::Dictionary) {
+            collect_fonts(&form_resources, depth + 1, seen, fonts);
+        }
+    }
+}
+
+pub mod cmap;
+pub mod decoder;
+pub mod encoding;
+pub mod metrics;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+
+use super::{
+    cmap::QPDFCMap,
+    encoding::glyph_to_unicode,
+    is_subset_name,
+    metrics::standard_metrics,
+    types::{QPDFFontInfo, QPDFFontSubtype},
+};
+use crate::qpdf::{
+    QPDF, content::builder::QPDFContentBuilder, page::QPDFPage, read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+#[test]
+fn detect_subset_names() {
+    assert!(is_subset_name("AAAAAB+HelveticaNeue-Bold"));
+    assert!(!is_subset_name("Helvetica"));
+    assert!(!is_subset_name("AAAAA+Helvetica"));
+    assert!(!is_subset_name("aaaaab+Helvetica"));
+    assert_eq!(QPDFFontSubtype::Type0, QPDFFontSubtype::from("/Type0"));
+    assert_eq!(
+        QPDFFontSubtype::Other("CIDFontType2".to_string()),
+        QPDFFontSubtype::from("/CIDFontType2")
+    );
+}
+
+#[test]
+fn map_glyph_names_to_unicode() {
+    assert_eq!(Some("A".to_string()), glyph_to_unicode("/A"));
+    assert_eq!(Some("\u{2019}".to_string()), glyph_to_unicode("quoteright"));
+    assert_eq!(Some("a".to_string()), glyph_to_unicode("a.sc"));
+    assert_eq!(Some("ff".to_string()), glyph_to_unicode("f_f"));
+    assert_eq!(Some("\u{20ac}".to_string()), glyph_to_unicode("uni20AC"));
+    assert_eq!(Some("\u{1f600}".to_string()), glyph_to_unicode("u1F600"));
+    assert_eq!(None, glyph_to_unicode("g123"));
+}
+
+#[test]
+fn parse_to_unicode_cmap() {
+    let cmap = QPDFCMap::parse(
+        b"/CIDInit /ProcSet findresource begin 12 dict begin begincmap\n\
+          1 begincodespacerange <0000> <FFFF> endcodespacerange\n\
+          2 beginbfchar <0003> <0020> <0011> <D83DDE00> endbfchar\n\
+          2 beginbfrange <0024> <0026> <0041> <0030> <0031> [<0066> <FB01>] endbfrange\n\
+          endcmap CMapName currentdict /CMap defineresource pop end end",
+    );
+
+    assert!(cmap.has_codespaces());
+    assert_eq!((0x0024, 2), cmap.next_code(&[0x00, 0x24, 0x00], 1));
+    assert_eq!(Some(&" ".to_string()), cmap.unicode(0x0003, 2));
+    assert_eq!(Some(&"\u{1f600}".to_string()), cmap.unicode(0x0011, 2));
+    assert_eq!(Some(&"C".to_string()), cmap.unicode(0x0026, 2));
+    assert_eq!(Some(&"\u{fb01}".to_string()), cmap.unicode(0x0031, 2));
+    assert_eq!(None, cmap.unicode(0x0026, 1));
+}
+
+#[test]
+fn parse_cid_cmap() {
+    let cmap = QPDFCMap::parse(
+        b"2 begincodespacerange <00> <80> <8140> <9FFC> endcodespacerange\n\
+          1 begincidrange <8140> <817E> 633 endcidrange\n\
+          1 begincidchar <20> 1 endcidchar",
+    );
+
+    assert_eq!((0x20, 1), cmap.next_code(b" A", 2));
+    assert_eq!((0x8141, 2), cmap.next_code(&[0x81, 0x41], 1));
+    assert_eq!(Some(1), cmap.cid(0x20, 1));
+    assert_eq!(Some(634), cmap.cid(0x8141, 2));
+    assert_eq!(None, cmap.cid(0x9000, 2));
+    assert_eq!(Some(0x1234), QPDFCMap::identity().cid(0x1234, 2));
+}
+
+#[test]
+fn resolve_standard_metrics() {
+    let helvetica = standard_metrics("Helvetica").unwrap();
+    assert_eq!(722.0, helvetica.width('H'));
+    assert_eq!(556.0, helvetica.width('\u{e9}'));
+
+    let arial_bold = standard_metrics("ABCDEF+Arial,Bold").unwrap();
+    assert_eq!(333.0, arial_bold.width('!'));
+
+    assert_eq!(444.0, standard_metrics("Times-Italic").unwrap().width('e'));
+    assert_eq!(600.0, standard_metrics("CourierNew").unwrap().width('W'));
+    assert!(standard_metrics("Garamond").is_none());
+}
+
+#[test]
+fn page_font_inventory() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let fonts = page.fonts();
+    assert_eq!(1, fonts.len());
+
+    let font: &QPDFFontInfo = &fonts[0];
+    assert_eq!("/TT1", font.name);
+    assert_eq!(6, font.object_id);
+    assert_eq!(
+        Some("AAAAAB+HelveticaNeue-Bold".to_string()),
+        font.base_font
+    );
+    assert_eq!(QPDFFontSubtype::TrueType, font.subtype);
+    assert_eq!(Some("MacRomanEncoding".to_string()), font.encoding);
+    assert!(font.embedded);
+    assert!(font.subset);
+    assert!(!font.to_unicode);
+}
+
+#[test]
+fn document_font_inventory() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(1).unwrap());
+    let mut content = QPDFContentBuilder::for_page(&page);
+    content
+        .begin_text()
+        .standard_font("Helvetica", 12.0)
+        .show_text("Added")
+        .end_text();
+    page.append_content(content.data());
+
+    let fonts = qpdf.fonts().unwrap();
+    assert_eq!(2, fonts.len());
+
+    let embedded = fonts.iter().find(|f| f.info.embedded).unwrap();
+    assert_eq!(vec![0, 1, 2], embedded.pages);
+
+    let standard = fonts.iter().find(|f| !f.info.embedded).unwrap();
+    assert_eq!(Some("Helvetica".to_string()), standard.info.base_font);
+    assert_eq!(QPDFFontSubtype::Type1, standard.info.subtype);
+    assert_eq!(vec![1], standard.pages);
+    assert!(!standard.info.subset);
+}
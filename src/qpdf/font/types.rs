@@ -0,0 +1,52 @@
+use crate::qpdf::object::types::{Generation, ObjectId};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QPDFFontSubtype {
+    Type1,
+    MMType1,
+    TrueType,
+    Type0,
+    Type3,
+    Other(String),
+}
+
+// Name values are given without their leading slash, `name` stays the /Font resource key
+#[derive(Debug, Clone)]
+pub struct QPDFFontInfo {
+    pub name: String,
+    pub object_id: ObjectId,
+    pub generation: Generation,
+    pub base_font: Option<String>,
+    pub subtype: QPDFFontSubtype,
+    pub encoding: Option<String>,
+    pub embedded: bool,
+    pub subset: bool,
+    pub to_unicode: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct QPDFDocumentFont {
+    pub info: QPDFFontInfo,
+    pub pages: Vec<usize>,
+}
+
+impl From<&str> for QPDFFontSubtype {
+    fn from(value: &str) -> Self {
+        match value.trim_start_matches('/') {
+            "Type1" => QPDFFontSubtype::Type1,
+            "MMType1" => QPDFFontSubtype::MMType1,
+            "TrueType" => QPDFFontSubtype::TrueType,
+            "Type0" => QPDFFontSubtype::Type0,
+            "Type3" => QPDFFontSubtype::Type3,
+            other => QPDFFontSubtype::Other(other.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFFontChar {
+    pub code: u32,
+    pub text: String,
+    pub width: f64,
+    pub is_space: bool,
+}
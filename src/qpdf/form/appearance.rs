@@ -0,0 +1,343 @@
+use super::QPDFFormField;
+use crate::qpdf::{
+    content::{
+        QPDFContentParser,
+        builder::QPDFContentBuilder,
+        types::{QPDFContentOperation, QPDFContentValue},
+    },
+    font::decoder::QPDFFontDecoder,
+    geometry::{Matrix, Rect, format_number},
+    object::{
+        QPDFObjectHandler,
+        types::{QPDFIsObjectType, QPDFModifyObjectTypes},
+    },
+};
+
+// Space kept clear between the widget border and its text
+const PADDING: f64 = 2.0;
+const DEFAULT_APPEARANCE: &str = "/Helv 0 Tf 0 g";
+const MAX_AUTO_SIZE: f64 = 12.0;
+const LINE_SPACING: f64 = 1.15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+struct TextLayout {
+    font: QPDFObjectHandler,
+    decoder: QPDFFontDecoder,
+    size: f64,
+    color: Vec<u8>,
+    width: f64,
+    height: f64,
+    alignment: Alignment,
+}
+
+// Builds the normal appearance stream showing `text` in one widget of a text or combo box field
+pub(crate) fn text_appearance(
+    field: &QPDFFormField,
+    widget: &QPDFObjectHandler,
+    acroform: &QPDFObjectHandler,
+    text: &str,
+) -> Option<QPDFObjectHandler> {
+    let rect: Rect = widget.dict_get_key("/Rect".to_string()).try_into().ok()?;
+    if rect.is_empty() {
+        return None;
+    }
+
+    let flags = field.info().flags;
+    let text = match flags.password {
+        true => "*".repeat(text.chars().count()),
+        _ => text.to_string(),
+    };
+
+    let mut layout = text_layout(field, widget, acroform, &rect)?;
+    let bytes = layout.decoder.encode(&text)?;
+
+    let max_len: i64 = field
+        .inherited_key("/MaxLen")
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or(0);
+
+    let resources = widget.set(QPDFModifyObjectTypes::Dictionary);
+    let mut builder = QPDFContentBuilder::new(resources.clone());
+
+    builder
+        .raw(b"/Tx BMC")
+        .save()
+        .rect(Rect::new(1.0, 1.0, rect.width() - 1.0, rect.height() - 1.0))
+        .clip()
+        .end_path()
+        .begin_text()
+        .raw(&layout.color.clone());
+
+    if flags.multiline {
+        if layout.size == 0.0 {
+            layout.size = MAX_AUTO_SIZE;
+        }
+        builder.font(&layout.font, layout.size);
+        show_lines(&mut builder, &layout, &text);
+    } else if flags.comb && max_len > 0 {
+        if layout.size == 0.0 {
+            layout.size = single_line_size(&layout, &bytes);
+        }
+        builder.font(&layout.font, layout.size);
+        show_comb(&mut builder, &layout, &bytes, max_len as usize);
+    } else {
+        if layout.size == 0.0 {
+            layout.size = single_line_size(&layout, &bytes);
+        }
+        builder.font(&layout.font, layout.size);
+
+        let x = aligned_x(&layout, text_width(&layout, &bytes));
+        builder
+            .text_matrix(Matrix::translate(x, single_line_baseline(&layout)))
+            .show_bytes(&bytes);
+    }
+
+    builder.end_text().restore().raw(b"EMC");
+
+    let stream = widget.set(QPDFModifyObjectTypes::Stream);
+    stream.replace_stream_data(builder.data(), None, None);
+
+    let dict = stream.dict();
+    let name = |name: &str| dict.set(QPDFModifyObjectTypes::Name(name.to_string()));
+    dict.dict_replace_key("/Type".to_string(), name("/XObject"));
+    dict.dict_replace_key("/Subtype".to_string(), name("/Form"));
+    dict.dict_replace_key(
+        "/BBox".to_string(),
+        Rect::from_size(rect.width(), rect.height()).to_object(&dict),
+    );
+    dict.dict_replace_key("/Resources".to_string(), resources);
+
+    Some(stream)
+}
+
+fn text_layout(
+    field: &QPDFFormField,
+    widget: &QPDFObjectHandler,
+    acroform: &QPDFObjectHandler,
+    rect: &Rect,
+) -> Option<TextLayout> {
+    // Widgets may carry their own /DA, otherwise it comes from the field or the form
+    let da = [widget.dict_get_key("/DA".to_string())]
+        .into_iter()
+        .chain(field.inherited_key("/DA"))
+        .chain([acroform.dict_get_key("/DA".to_string())])
+        .find_map(|da| TryInto::<String>::try_into(da).ok())
+        .unwrap_or_else(|| DEFAULT_APPEARANCE.to_string());
+
+    let mut font_name = "/Helv".to_string();
+    let mut size = 0.0;
+    let mut color = Vec::new();
+
+    for operation in QPDFContentParser::new(da.as_bytes()).map_while(Result::ok) {
+        let QPDFContentOperation::Operator(op, operands) = operation else {
+            continue;
+        };
+
+        match (op.as_str(), operands.as_slice()) {
+            ("Tf", [QPDFContentValue::Name(name), value]) => {
+                font_name = name.clone();
+                size = value.as_number().unwrap_or(0.0);
+            }
+            ("g" | "rg" | "k", values) => {
+                let values: Vec<String> = values
+                    .iter()
+                    .filter_map(|v| v.as_number())
+                    .map(format_number)
+                    .collect();
+                color = format!("{} {op}", values.join(" ")).into_bytes();
+            }
+            _ => (),
+        }
+    }
+
+    let font = resource_font(acroform, &font_name)?;
+
+    let alignment = match field
+        .inherited_key("/Q")
+        .and_then(|q| TryInto::<i64>::try_into(q).ok())
+    {
+        Some(1) => Alignment::Center,
+        Some(2) => Alignment::Right,
+        _ => Alignment::Left,
+    };
+
+    Some(TextLayout {
+        decoder: QPDFFontDecoder::from_object(&font),
+        font,
+        size,
+        color,
+        width: rect.width(),
+        height: rect.height(),
+        alignment,
+    })
+}
+
+// Fonts missing from the form's default resources are added there as Helvetica, so that every
+// widget and later fill shares a single font object
+fn resource_font(acroform: &QPDFObjectHandler, name: &str) -> Option<QPDFObjectHandler> {
+    let fonts = child_dict(&child_dict(acroform, "/DR")?, "/Font")?;
+
+    let font = fonts.dict_get_key(name.to_string());
+    if font.is(QPDFIsObjectType::Dictionary) {
+        return Some(font);
+    }
+
+    let font = helvetica(acroform)?;
+    fonts.dict_replace_key(name.to_string(), font.clone());
+    Some(font)
+}
+
+fn child_dict(parent: &QPDFObjectHandler, key: &str) -> Option<QPDFObjectHandler> {
+    if !parent.is(QPDFIsObjectType::Dictionary) {
+        return None;
+    }
+
+    if !parent
+        .dict_get_key(key.to_string())
+        .is(QPDFIsObjectType::Dictionary)
+    {
+        parent.dict_replace_key(
+            key.to_string(),
+            parent.set(QPDFModifyObjectTypes::Dictionary),
+        );
+    }
+
+    Some(parent.dict_get_key(key.to_string()))
+}
+
+fn helvetica(factory: &QPDFObjectHandler) -> Option<QPDFObjectHandler> {
+    let font = factory.set(QPDFModifyObjectTypes::Dictionary);
+
+    for (key, value) in [
+        ("/Type", "/Font"),
+        ("/Subtype", "/Type1"),
+        ("/BaseFont", "/Helvetica"),
+        ("/Encoding", "/WinAnsiEncoding"),
+    ] {
+        font.dict_replace_key(
+            key.to_string(),
+            font.set(QPDFModifyObjectTypes::Name(value.to_string())),
+        );
+    }
+
+    font.make_indirect()
+}
+
+fn text_width(layout: &TextLayout, bytes: &[u8]) -> f64 {
+    layout
+        .decoder
+        .decode(bytes)
+        .iter()
+        .map(|c| c.width)
+        .sum::<f64>()
+        * layout.size
+}
+
+fn aligned_x(layout: &TextLayout, width: f64) -> f64 {
+    match layout.alignment {
+        Alignment::Left => PADDING,
+        Alignment::Center => (layout.width - width) / 2.0,
+        Alignment::Right => layout.width - PADDING - width,
+    }
+}
+
+fn font_height(layout: &TextLayout) -> f64 {
+    let height = layout.decoder.ascent() - layout.decoder.descent();
+
+    match height > 0.0 {
+        true => height,
+        _ => 1.0,
+    }
+}
+
+// Auto-sized text fills the widget height, shrinking further if it would overflow the width
+fn single_line_size(layout: &TextLayout, bytes: &[u8]) -> f64 {
+    let mut size = ((layout.height - 2.0 * PADDING) / font_height(layout)).min(MAX_AUTO_SIZE);
+
+    let width: f64 = layout.decoder.decode(bytes).iter().map(|c| c.width).sum();
+    let available = layout.width - 2.0 * PADDING;
+    if width * size > available && width > 0.0 {
+        size = available / width;
+    }
+
+    size.max(1.0)
+}
+
+fn single_line_baseline(layout: &TextLayout) -> f64 {
+    let descent = layout.decoder.descent() * layout.size;
+    (layout.height - font_height(layout) * layout.size) / 2.0 - descent
+}
+
+// Comb fields place one character in the middle of each of MaxLen equal cells
+fn show_comb(builder: &mut QPDFContentBuilder, layout: &TextLayout, bytes: &[u8], max_len: usize) {
+    let cell = layout.width / max_len as f64;
+    let y = single_line_baseline(layout);
+
+    for (at, byte) in bytes.iter().take(max_len).enumerate() {
+        let width = text_width(layout, &[*byte]);
+        let x = at as f64 * cell + (cell - width) / 2.0;
+
+        builder
+            .text_matrix(Matrix::translate(x, y))
+            .show_bytes(&[*byte]);
+    }
+}
+
+fn show_lines(builder: &mut QPDFContentBuilder, layout: &TextLayout, text: &str) {
+    let available = layout.width - 2.0 * PADDING;
+    let leading = layout.size * LINE_SPACING;
+    let mut y = layout.height - PADDING - layout.decoder.ascent() * layout.size;
+
+    for line in wrap_lines(layout, text, available) {
+        let Some(bytes) = layout.decoder.encode(&line) else {
+            continue;
+        };
+
+        let x = aligned_x(layout, text_width(layout, &bytes));
+        builder
+            .text_matrix(Matrix::translate(x, y))
+            .show_bytes(&bytes);
+
+        y -= leading;
+    }
+}
+
+// Breaks text at explicit newlines, then between words wherever a line would overflow
+fn wrap_lines(layout: &TextLayout, text: &str, available: f64) -> Vec<String> {
+    let width = |s: &str| {
+        layout
+            .decoder
+            .encode(s)
+            .map(|bytes| text_width(layout, &bytes))
+            .unwrap_or(0.0)
+    };
+
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+
+        for word in paragraph.split(' ') {
+            let candidate = match line.is_empty() {
+                true => word.to_string(),
+                _ => format!("{line} {word}"),
+            };
+
+            if !line.is_empty() && width(&candidate) > available {
+                lines.push(line);
+                line = word.to_string();
+            } else {
+                line = candidate;
+            }
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
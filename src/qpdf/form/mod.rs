@@ -0,0 +1,598 @@
+use std::collections::{HashMap, HashSet};
+
+use types::{
+    QPDFFieldFlags, QPDFFieldOption, QPDFFieldType, QPDFFieldValue, QPDFFieldWidget,
+    QPDFFormFieldInfo,
+};
+
+use super::{
+    QPDF, QPDFErrors,
+    annotation::{
+        QPDFAnnotation, collect_annotations, draw_appearance, remove_annotations,
+        types::QPDFAnnotationFlattenScope,
+    },
+    content::builder::QPDFContentBuilder,
+    object::{
+        QPDFObjectHandler,
+        types::{Generation, ObjectId, QPDFIsObjectType, QPDFModifyObjectTypes},
+    },
+    page::QPDFPage,
+};
+
+// Limits how deeply nested field hierarchies are followed
+const MAX_FIELD_DEPTH: usize = 32;
+
+// Keys a terminal field inherits from its ancestors when it does not set them itself
+const INHERITABLE_KEYS: [&str; 6] = ["/FT", "/V", "/DV", "/Ff", "/DA", "/Q"];
+
+pub struct QPDFFormField {
+    info: QPDFFormFieldInfo,
+    object: QPDFObjectHandler,
+    inherited: HashMap<String, QPDFObjectHandler>,
+}
+
+impl QPDFFormField {
+    pub fn info(&self) -> &QPDFFormFieldInfo {
+        &self.info
+    }
+
+    pub fn object(&self) -> &QPDFObjectHandler {
+        &self.object
+    }
+
+    // Looks a key up on the field, then on the ancestors it inherits from
+    pub(crate) fn inherited_key(&self, key: &str) -> Option<QPDFObjectHandler> {
+        match self.object.dict_has_key(key.to_string()) {
+            true => Some(self.object.dict_get_key(key.to_string())),
+            _ => self.inherited.get(key).cloned(),
+        }
+    }
+
+    pub(crate) fn widget_objects(&self) -> Vec<QPDFObjectHandler> {
+        widget_objects(&self.object)
+    }
+}
+
+pub(crate) fn collect_fields(qpdf: &QPDF) -> Vec<QPDFFormField> {
+    let Some(root) = qpdf.get_object_root() else {
+        return Vec::new();
+    };
+
+    let pages = annotation_pages(qpdf);
+    let fields = root
+        .dict_get_key("/AcroForm".to_string())
+        .dict_get_key("/Fields".to_string());
+
+    let mut found = Vec::new();
+    let mut seen = HashSet::new();
+
+    for at in 0..fields.array_len() {
+        let walk = FieldWalk {
+            name: String::new(),
+            inherited: HashMap::new(),
+            depth: 0,
+        };
+        collect(
+            &fields.array_get_at(at),
+            walk,
+            &pages,
+            &mut seen,
+            &mut found,
+        );
+    }
+
+    found
+}
+
+struct FieldWalk {
+    name: String,
+    inherited: HashMap<String, QPDFObjectHandler>,
+    depth: usize,
+}
+
+fn collect(
+    field: &QPDFObjectHandler,
+    mut walk: FieldWalk,
+    pages: &HashMap<(ObjectId, Generation), usize>,
+    seen: &mut HashSet<(ObjectId, Generation)>,
+    found: &mut Vec<QPDFFormField>,
+) {
+    if walk.depth > MAX_FIELD_DEPTH || !field.is(QPDFIsObjectType::Dictionary) {
+        return;
+    }
+
+    let id = (field.object_id(), field.generation());
+    if id.0 != 0 && !seen.insert(id) {
+        return;
+    }
+
+    let partial: Option<String> = field.dict_get_key("/T".to_string()).try_into().ok();
+    if let Some(partial) = partial {
+        walk.name = match walk.name.is_empty() {
+            true => partial,
+            _ => format!("{}.{}", walk.name, partial),
+        };
+    }
+
+    // Kids with a partial name are fields of their own, the rest are widgets
+    let kids = field.dict_get_key("/Kids".to_string());
+    let children: Vec<QPDFObjectHandler> = (0..kids.array_len())
+        .map(|at| kids.array_get_at(at))
+        .filter(|kid| kid.dict_has_key("/T".to_string()))
+        .collect();
+
+    if children.is_empty() {
+        found.push(read_field(field, &walk, pages));
+        return;
+    }
+
+    for key in INHERITABLE_KEYS {
+        if field.dict_has_key(key.to_string()) {
+            walk.inherited
+                .insert(key.to_string(), field.dict_get_key(key.to_string()));
+        }
+    }
+
+    for child in children {
+        let walk = FieldWalk {
+            name: walk.name.clone(),
+            inherited: walk.inherited.clone(),
+            depth: walk.depth + 1,
+        };
+        collect(&child, walk, pages, seen, found);
+    }
+}
+
+fn read_field(
+    field: &QPDFObjectHandler,
+    walk: &FieldWalk,
+    pages: &HashMap<(ObjectId, Generation), usize>,
+) -> QPDFFormField {
+    let mut form_field = QPDFFormField {
+        info: QPDFFormFieldInfo {
+            object_id: field.object_id(),
+            generation: field.generation(),
+            name: walk.name.clone(),
+            field_type: QPDFFieldType::Unknown(String::new()),
+            value: None,
+            default_value: None,
+            options: Vec::new(),
+            export_values: Vec::new(),
+            widgets: Vec::new(),
+            flags: QPDFFieldFlags::default(),
+        },
+        object: field.clone(),
+        inherited: walk.inherited.clone(),
+    };
+
+    let key = |key: &str| form_field.inherited_key(key);
+
+    let field_type = key("/FT")
+        .and_then(|ft| ft.name().ok())
+        .map(|ft| QPDFFieldType::from(ft.as_str()))
+        .unwrap_or(QPDFFieldType::Unknown(String::new()));
+    let flags: i64 = key("/Ff").and_then(|ff| ff.try_into().ok()).unwrap_or(0);
+
+    let widgets = form_field.widget_objects();
+    let mut export_values = Vec::new();
+
+    // Each on state of a button widget is one of the values the field can export
+    if field_type == QPDFFieldType::Button {
+        for widget in &widgets {
+            let normal = widget
+                .dict_get_key("/AP".to_string())
+                .dict_get_key("/N".to_string());
+
+            for state in normal.dict_keys() {
+                if state != "/Off" && !export_values.contains(&state) {
+                    export_values.push(state);
+                }
+            }
+        }
+    }
+
+    let info = QPDFFormFieldInfo {
+        value: key("/V").and_then(|v| field_value(&v)),
+        default_value: key("/DV").and_then(|v| field_value(&v)),
+        options: field_options(&field.dict_get_key("/Opt".to_string())),
+        export_values,
+        widgets: widgets
+            .iter()
+            .map(|widget| QPDFFieldWidget {
+                object_id: widget.object_id(),
+                generation: widget.generation(),
+                page_index: pages
+                    .get(&(widget.object_id(), widget.generation()))
+                    .copied(),
+                rect: widget.dict_get_key("/Rect".to_string()).try_into().ok(),
+                appearance_state: widget.dict_get_key("/AS".to_string()).name().ok(),
+            })
+            .collect(),
+        flags: QPDFFieldFlags::decode(flags, &field_type),
+        field_type,
+        ..form_field.info
+    };
+
+    form_field.info = info;
+    form_field
+}
+
+pub(crate) fn fill_field(qpdf: &QPDF, name: &str, value: QPDFFieldValue) -> Result<(), QPDFErrors> {
+    let root = qpdf.get_object_root().ok_or(QPDFErrors::InvalidObject)?;
+    let acroform = root.dict_get_key("/AcroForm".to_string());
+
+    let field = collect_fields(qpdf)
+        .into_iter()
+        .find(|field| field.info.name == name)
+        .ok_or(QPDFErrors::KeyNotFound)?;
+    let invalid = || QPDFErrors::InvalidFieldValue(name.to_string());
+
+    let info = field.info();
+    let object = field.object();
+    let set = |value: QPDFObjectHandler| object.dict_replace_key("/V".to_string(), value);
+
+    let complete = match (&info.field_type, value) {
+        (QPDFFieldType::Text, QPDFFieldValue::Text(text)) => {
+            set(object.set(QPDFModifyObjectTypes::String(text.clone())));
+            write_text_appearances(&field, &acroform, &text)
+        }
+        (QPDFFieldType::Button, QPDFFieldValue::State(state)) if !info.flags.pushbutton => {
+            let state = match state.starts_with('/') {
+                true => state,
+                _ => format!("/{state}"),
+            };
+            if state != "/Off" && !info.export_values.contains(&state) {
+                return Err(invalid());
+            }
+
+            set(object.set(QPDFModifyObjectTypes::Name(state.clone())));
+            write_button_states(&field, &state);
+            true
+        }
+        (QPDFFieldType::Choice, QPDFFieldValue::Text(choice)) => {
+            // Selected indices would contradict the new value
+            object.dict_remove_key("/I".to_string());
+            set(object.set(QPDFModifyObjectTypes::String(choice.clone())));
+            write_choice_appearances(&field, &acroform, &choice)
+        }
+        (QPDFFieldType::Choice, QPDFFieldValue::Choices(choices)) => {
+            if choices.len() > 1 && !info.flags.multi_select {
+                return Err(invalid());
+            }
+
+            object.dict_remove_key("/I".to_string());
+            let array = object.set(QPDFModifyObjectTypes::Array);
+            for choice in &choices {
+                array.array_append(object.set(QPDFModifyObjectTypes::String(choice.clone())));
+            }
+            set(array);
+
+            match choices.as_slice() {
+                [choice] => write_choice_appearances(&field, &acroform, choice),
+                _ => write_choice_appearances(&field, &acroform, ""),
+            }
+        }
+        _ => return Err(invalid()),
+    };
+
+    // Viewers rebuild whatever appearances could not be generated here
+    if !complete && acroform.is(QPDFIsObjectType::Dictionary) {
+        acroform.dict_replace_key(
+            "/NeedAppearances".to_string(),
+            acroform.set(QPDFModifyObjectTypes::Bool(true)),
+        );
+    }
+
+    Ok(())
+}
+
+// Returns false when some widget was left without an up to date appearance
+fn write_text_appearances(field: &QPDFFormField, acroform: &QPDFObjectHandler, text: &str) -> bool {
+    let mut complete = true;
+
+    for widget in field.widget_objects() {
+        match appearance::text_appearance(field, &widget, acroform, text) {
+            Some(stream) => {
+                let ap = widget.set(QPDFModifyObjectTypes::Dictionary);
+                ap.dict_replace_key("/N".to_string(), stream);
+                widget.dict_replace_key("/AP".to_string(), ap);
+            }
+            None => complete = false,
+        }
+    }
+
+    complete
+}
+
+fn write_choice_appearances(
+    field: &QPDFFormField,
+    acroform: &QPDFObjectHandler,
+    choice: &str,
+) -> bool {
+    // List boxes show every option, only combo boxes display just the value
+    if !field.info().flags.combo {
+        return false;
+    }
+
+    let display = field
+        .info()
+        .options
+        .iter()
+        .find(|option| option.export == choice)
+        .map_or(choice, |option| option.display.as_str());
+
+    write_text_appearances(field, acroform, display)
+}
+
+// Each widget shows the state only if it has an appearance for it, radio siblings turn off
+fn write_button_states(field: &QPDFFormField, state: &str) {
+    for widget in field.widget_objects() {
+        let normal = widget
+            .dict_get_key("/AP".to_string())
+            .dict_get_key("/N".to_string());
+
+        let shown = match normal.dict_has_key(state.to_string()) {
+            true => state,
+            _ => "/Off",
+        };
+        widget.dict_replace_key(
+            "/AS".to_string(),
+            widget.set(QPDFModifyObjectTypes::Name(shown.to_string())),
+        );
+    }
+}
+
+// Rebuilds the appearances of a field from its current value, false when that is not possible
+fn refresh_appearances(field: &QPDFFormField, acroform: &QPDFObjectHandler) -> bool {
+    match (&field.info().field_type, &field.info().value) {
+        (QPDFFieldType::Text, Some(QPDFFieldValue::Text(text))) => {
+            write_text_appearances(field, acroform, text)
+        }
+        (QPDFFieldType::Text, None) => write_text_appearances(field, acroform, ""),
+        (QPDFFieldType::Choice, Some(QPDFFieldValue::Text(choice))) => {
+            write_choice_appearances(field, acroform, choice)
+        }
+        (QPDFFieldType::Choice, Some(QPDFFieldValue::Choices(choices))) if choices.len() == 1 => {
+            write_choice_appearances(field, acroform, &choices[0])
+        }
+        (QPDFFieldType::Choice, None) => write_choice_appearances(field, acroform, ""),
+        (QPDFFieldType::Choice, _) => false,
+        (QPDFFieldType::Button, Some(QPDFFieldValue::State(state))) => {
+            write_button_states(field, state);
+            true
+        }
+        // Pushbuttons and signatures have nothing to rebuild from
+        _ => true,
+    }
+}
+
+// Draws the widgets of matching fields into their pages, then removes the fields from the form
+pub(crate) fn flatten_fields<F: Fn(&QPDFFormField) -> bool>(
+    qpdf: &QPDF,
+    scope: QPDFAnnotationFlattenScope,
+    predicate: F,
+) -> Result<usize, QPDFErrors> {
+    let root = qpdf.get_object_root().ok_or(QPDFErrors::InvalidObject)?;
+    let acroform = root.dict_get_key("/AcroForm".to_string());
+
+    let fields: Vec<QPDFFormField> = collect_fields(qpdf).into_iter().filter(predicate).collect();
+    let widgets: HashSet<(ObjectId, Generation)> = fields
+        .iter()
+        .flat_map(|field| field.widget_objects())
+        .map(|widget| (widget.object_id(), widget.generation()))
+        .collect();
+
+    // Stale appearances are rebuilt first, fields that cannot be drawn here are refused rather
+    // than flattened with outdated or missing content
+    let need_appearances: bool = acroform
+        .dict_get_key("/NeedAppearances".to_string())
+        .try_into()
+        .unwrap_or(false);
+    if need_appearances {
+        for field in &fields {
+            if !refresh_appearances(field, &acroform) {
+                return Err(QPDFErrors::MissingAppearance(field.info().name.clone()));
+            }
+        }
+    }
+
+    for at in 0..(qpdf.len_pages().max(0) as usize) {
+        let page = QPDFPage::from(qpdf.get_page(at).ok_or(QPDFErrors::InvalidPage)?);
+        flatten_widgets(&page, &widgets, scope);
+    }
+
+    // Calculation order entries would otherwise point at fields that are gone
+    let co = acroform.dict_get_key("/CO".to_string());
+    for field in &fields {
+        detach(&acroform, field.object(), 0);
+
+        let id = (field.info().object_id, field.info().generation);
+        for at in (0..co.array_len()).rev() {
+            let entry = co.array_get_at(at);
+            if (entry.object_id(), entry.generation()) == id {
+                co.array_erase_at(at);
+            }
+        }
+    }
+
+    // Once nothing is left the form itself goes, so viewers no longer offer to fill it
+    if acroform.dict_get_key("/Fields".to_string()).array_len() == 0 {
+        root.dict_remove_key("/AcroForm".to_string());
+    }
+
+    Ok(fields.len())
+}
+
+// Flattens the fields annotation flattening covers, leaving them alone while viewers are asked
+// to redraw appearances as qpdf does
+pub(crate) fn flatten_shown_fields(
+    qpdf: &QPDF,
+    scope: QPDFAnnotationFlattenScope,
+) -> Result<usize, QPDFErrors> {
+    let acroform = qpdf
+        .get_object_root()
+        .map(|root| root.dict_get_key("/AcroForm".to_string()))
+        .filter(|acroform| acroform.is(QPDFIsObjectType::Dictionary));
+    let Some(acroform) = acroform else {
+        return Ok(0);
+    };
+
+    let need_appearances: bool = acroform
+        .dict_get_key("/NeedAppearances".to_string())
+        .try_into()
+        .unwrap_or(false);
+    if need_appearances {
+        return Ok(0);
+    }
+
+    flatten_fields(qpdf, scope, |field| {
+        let widgets = field.widget_objects();
+        !widgets.is_empty()
+            && widgets.into_iter().all(|widget| {
+                QPDFAnnotation::from_object(widget)
+                    .is_ok_and(|annot| annot.info().flags.is_shown(scope))
+            })
+    })
+}
+
+fn flatten_widgets(
+    page: &QPDFPage,
+    widgets: &HashSet<(ObjectId, Generation)>,
+    scope: QPDFAnnotationFlattenScope,
+) {
+    let mut builder = QPDFContentBuilder::for_page(page);
+
+    for annot in collect_annotations(page.object()) {
+        if widgets.contains(&(annot.info().object_id, annot.info().generation)) {
+            draw_appearance(&mut builder, &annot, scope);
+        }
+    }
+
+    if !builder.data().is_empty() {
+        page.append_content(builder.data());
+    }
+
+    // Widgets without an appearance are dropped as well since their field is going away
+    let removed = remove_annotations(page.object(), |annot| {
+        widgets.contains(&(annot.info().object_id, annot.info().generation))
+    });
+
+    if removed > 0
+        && page
+            .object()
+            .dict_get_key("/Annots".to_string())
+            .array_len()
+            == 0
+    {
+        page.object().dict_remove_key("/Annots".to_string());
+    }
+}
+
+// Unlinks a field from its parent, pruning ancestors that are left without kids
+fn detach(acroform: &QPDFObjectHandler, field: &QPDFObjectHandler, depth: usize) {
+    let parent = field.dict_get_key("/Parent".to_string());
+    let siblings = match parent.is(QPDFIsObjectType::Dictionary) {
+        true => parent.dict_get_key("/Kids".to_string()),
+        _ => acroform.dict_get_key("/Fields".to_string()),
+    };
+
+    let id = (field.object_id(), field.generation());
+    for at in (0..siblings.array_len()).rev() {
+        let sibling = siblings.array_get_at(at);
+        if (sibling.object_id(), sibling.generation()) == id {
+            siblings.array_erase_at(at);
+        }
+    }
+
+    if parent.is(QPDFIsObjectType::Dictionary)
+        && siblings.array_len() == 0
+        && depth < MAX_FIELD_DEPTH
+    {
+        detach(acroform, &parent, depth + 1);
+    }
+}
+
+// A terminal field without widget kids is merged with its only widget
+fn widget_objects(field: &QPDFObjectHandler) -> Vec<QPDFObjectHandler> {
+    let kids = field.dict_get_key("/Kids".to_string());
+
+    if kids.array_len() == 0 {
+        return match field.dict_has_key("/Rect".to_string()) {
+            true => vec![field.clone()],
+            _ => Vec::new(),
+        };
+    }
+
+    (0..kids.array_len())
+        .map(|at| kids.array_get_at(at))
+        .filter(|kid| kid.is(QPDFIsObjectType::Dictionary) && !kid.dict_has_key("/T".to_string()))
+        .collect()
+}
+
+fn field_value(value: &QPDFObjectHandler) -> Option<QPDFFieldValue> {
+    if let Ok(state) = value.name() {
+        return Some(QPDFFieldValue::State(state));
+    }
+
+    if value.is(QPDFIsObjectType::Array) {
+        let choices = (0..value.array_len())
+            .filter_map(|at| value.array_get_at(at).try_into().ok())
+            .collect();
+        return Some(QPDFFieldValue::Choices(choices));
+    }
+
+    match value.is(QPDFIsObjectType::String) {
+        true => value.clone().try_into().ok().map(QPDFFieldValue::Text),
+        _ => None,
+    }
+}
+
+// Options are either plain strings or pairs of export value and display text
+fn field_options(opt: &QPDFObjectHandler) -> Vec<QPDFFieldOption> {
+    (0..opt.array_len())
+        .filter_map(|at| {
+            let item = opt.array_get_at(at);
+
+            match item.is(QPDFIsObjectType::Array) {
+                true => Some(QPDFFieldOption {
+                    export: item.array_get_at(0).try_into().ok()?,
+                    display: item.array_get_at(1).try_into().ok()?,
+                }),
+                _ => {
+                    let text: String = item.try_into().ok()?;
+                    Some(QPDFFieldOption {
+                        export: text.clone(),
+                        display: text,
+                    })
+                }
+            }
+        })
+        .collect()
+}
+
+// Widgets are matched to pages through the /Annots arrays, since /P is optional
+fn annotation_pages(qpdf: &QPDF) -> HashMap<(ObjectId, Generation), usize> {
+    let mut pages = HashMap::new();
+
+    for at in 0..(qpdf.len_pages().max(0) as usize) {
+        let Some(page) = qpdf.get_page(at) else {
+            continue;
+        };
+
+        let annots = page.dict_get_key("/Annots".to_string());
+        for i in 0..annots.array_len() {
+            let annot = annots.array_get_at(i);
+            pages
+                .entry((annot.object_id(), annot.generation()))
+                .or_insert(at);
+        }
+    }
+
+    pages
+}
+
+pub mod appearance;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
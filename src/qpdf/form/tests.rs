@@ -0,0 +1,495 @@
+use std::path::PathBuf;
+
+use super::types::{QPDFFieldFlags, QPDFFieldOption, QPDFFieldType, QPDFFieldValue};
+use crate::qpdf::{
+    QPDF, QPDFErrors,
+    annotation::types::QPDFAnnotationFlattenScope,
+    geometry::Rect,
+    object::{QPDFObjectHandler, types::QPDFModifyObjectTypes},
+    page::QPDFPage,
+    read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+fn dict(factory: &QPDFObjectHandler, entries: Vec<(&str, QPDFObjectHandler)>) -> QPDFObjectHandler {
+    let dict = factory.set(QPDFModifyObjectTypes::Dictionary);
+    for (key, value) in entries {
+        dict.dict_replace_key(key.to_string(), value);
+    }
+
+    dict.make_indirect().unwrap()
+}
+
+fn array(factory: &QPDFObjectHandler, items: Vec<QPDFObjectHandler>) -> QPDFObjectHandler {
+    let array = factory.set(QPDFModifyObjectTypes::Array);
+    for item in items {
+        array.array_append(item);
+    }
+
+    array
+}
+
+#[test]
+fn decode_field_flags() {
+    let text = QPDFFieldFlags::decode(1 | 1 << 12 | 1 << 24, &QPDFFieldType::Text);
+    assert!(text.read_only && text.multiline && text.comb);
+    assert!(!text.radio && !text.combo);
+
+    // Bit 18 is a combo box flag for choices but means nothing for text fields
+    let choice = QPDFFieldFlags::decode(1 << 17 | 1 << 21, &QPDFFieldType::Choice);
+    assert!(choice.combo && choice.multi_select);
+    assert!(!QPDFFieldFlags::decode(1 << 17, &QPDFFieldType::Text).combo);
+
+    let radio = QPDFFieldFlags::decode(1 << 14 | 1 << 15, &QPDFFieldType::Button);
+    assert!(radio.no_toggle_off && radio.radio && !radio.pushbutton);
+
+    for (flags, field_type) in [
+        (0x1007, QPDFFieldType::Text),
+        (0x2e0000, QPDFFieldType::Choice),
+        (0x200c003, QPDFFieldType::Button),
+    ] {
+        assert_eq!(flags, i64::from(QPDFFieldFlags::decode(flags, &field_type)));
+    }
+}
+
+#[test]
+fn collect_form_fields() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+    assert!(qpdf.form_fields().is_empty());
+
+    let root = qpdf.get_object_root().unwrap();
+    let page = qpdf.get_page(1).unwrap();
+    let name = |n: &str| root.set(QPDFModifyObjectTypes::Name(n.to_string()));
+    let string = |s: &str| root.set(QPDFModifyObjectTypes::String(s.to_string()));
+    let int = |v: i64| root.set(QPDFModifyObjectTypes::Integer(v));
+    let rect = |r: Rect| r.to_object(&root);
+
+    let first = dict(
+        &root,
+        vec![
+            ("/T", string("first")),
+            ("/V", string("Ada")),
+            ("/Subtype", name("/Widget")),
+            ("/Rect", rect(Rect::new(10.0, 10.0, 110.0, 30.0))),
+        ],
+    );
+
+    let on = dict(&root, vec![]);
+    let states = dict(&root, vec![("/Yes", on.clone()), ("/Off", on)]);
+    let appearance = dict(&root, vec![("/N", states)]);
+    let check_widget = dict(
+        &root,
+        vec![
+            ("/Subtype", name("/Widget")),
+            ("/Rect", rect(Rect::new(10.0, 40.0, 20.0, 50.0))),
+            ("/AP", appearance),
+            ("/AS", name("/Yes")),
+        ],
+    );
+    let check = dict(
+        &root,
+        vec![
+            ("/T", string("agree")),
+            ("/FT", name("/Btn")),
+            ("/V", name("/Yes")),
+            ("/Kids", array(&root, vec![check_widget.clone()])),
+        ],
+    );
+
+    let colour = dict(
+        &root,
+        vec![
+            ("/T", string("colour")),
+            ("/FT", name("/Ch")),
+            ("/Ff", int(1 << 17)),
+            ("/V", string("r")),
+            (
+                "/Opt",
+                array(
+                    &root,
+                    vec![
+                        array(&root, vec![string("r"), string("Red")]),
+                        string("Blue"),
+                    ],
+                ),
+            ),
+            ("/Rect", rect(Rect::new(10.0, 60.0, 110.0, 80.0))),
+        ],
+    );
+
+    let name_group = dict(
+        &root,
+        vec![
+            ("/T", string("name")),
+            ("/FT", name("/Tx")),
+            ("/Ff", int(1)),
+            ("/Kids", array(&root, vec![first.clone()])),
+        ],
+    );
+    first.dict_replace_key("/Parent".to_string(), name_group.clone());
+
+    let form = dict(
+        &root,
+        vec![(
+            "/Fields",
+            array(&root, vec![name_group, check, colour.clone()]),
+        )],
+    );
+    root.dict_replace_key("/AcroForm".to_string(), form);
+    page.dict_replace_key(
+        "/Annots".to_string(),
+        array(&root, vec![first, check_widget, colour]),
+    );
+
+    let fields = qpdf.form_fields();
+    assert_eq!(3, fields.len());
+
+    let first = fields[0].info();
+    assert_eq!("name.first", first.name);
+    assert_eq!(QPDFFieldType::Text, first.field_type);
+    assert_eq!(Some(QPDFFieldValue::Text("Ada".to_string())), first.value);
+    assert!(first.flags.read_only);
+    assert_eq!(1, first.widgets.len());
+    assert_eq!(Some(1), first.widgets[0].page_index);
+
+    let check = fields[1].info();
+    assert_eq!("agree", check.name);
+    assert_eq!(Some(QPDFFieldValue::State("/Yes".to_string())), check.value);
+    assert_eq!(vec!["/Yes".to_string()], check.export_values);
+    assert_eq!(Some("/Yes".to_string()), check.widgets[0].appearance_state);
+
+    let colour = fields[2].info();
+    assert_eq!(QPDFFieldType::Choice, colour.field_type);
+    assert!(colour.flags.combo);
+    assert_eq!(
+        vec![
+            QPDFFieldOption {
+                export: "r".to_string(),
+                display: "Red".to_string()
+            },
+            QPDFFieldOption {
+                export: "Blue".to_string(),
+                display: "Blue".to_string()
+            },
+        ],
+        colour.options
+    );
+    assert_eq!(
+        Some(Rect::new(10.0, 60.0, 110.0, 80.0)),
+        colour.widgets[0].rect
+    );
+}
+
+#[test]
+fn fill_form_fields() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    let name = |n: &str| root.set(QPDFModifyObjectTypes::Name(n.to_string()));
+    let string = |s: &str| root.set(QPDFModifyObjectTypes::String(s.to_string()));
+    let int = |v: i64| root.set(QPDFModifyObjectTypes::Integer(v));
+    let rect = |r: Rect| r.to_object(&root);
+
+    let text = dict(
+        &root,
+        vec![
+            ("/T", string("city")),
+            ("/FT", name("/Tx")),
+            ("/Q", int(1)),
+            ("/DA", string("/Helv 10 Tf 0 0 1 rg")),
+            ("/Rect", rect(Rect::new(10.0, 10.0, 210.0, 30.0))),
+        ],
+    );
+
+    let on = dict(&root, vec![]);
+    let states = dict(&root, vec![("/Yes", on.clone()), ("/Off", on)]);
+    let appearance = dict(&root, vec![("/N", states)]);
+    let check = dict(
+        &root,
+        vec![
+            ("/T", string("agree")),
+            ("/FT", name("/Btn")),
+            ("/AS", name("/Off")),
+            ("/AP", appearance),
+            ("/Rect", rect(Rect::new(10.0, 40.0, 20.0, 50.0))),
+        ],
+    );
+
+    let colour = dict(
+        &root,
+        vec![
+            ("/T", string("colour")),
+            ("/FT", name("/Ch")),
+            ("/Ff", int(1 << 17)),
+            (
+                "/Opt",
+                array(&root, vec![array(&root, vec![string("r"), string("Red")])]),
+            ),
+            ("/Rect", rect(Rect::new(10.0, 60.0, 110.0, 80.0))),
+        ],
+    );
+
+    let form = dict(
+        &root,
+        vec![(
+            "/Fields",
+            array(&root, vec![text.clone(), check.clone(), colour.clone()]),
+        )],
+    );
+    root.dict_replace_key("/AcroForm".to_string(), form.clone());
+
+    qpdf.set_field_value("city", QPDFFieldValue::Text("Paris".to_string()))
+        .unwrap();
+    let field = &qpdf.form_fields()[0];
+    assert_eq!(
+        Some(QPDFFieldValue::Text("Paris".to_string())),
+        field.info().value
+    );
+
+    let stream = text
+        .dict_get_key("/AP".to_string())
+        .dict_get_key("/N".to_string());
+    let content = String::from_utf8(stream.raw_stream_data().unwrap()).unwrap();
+    assert!(content.starts_with("/Tx BMC\n"));
+    assert!(content.contains("0 0 1 rg\n"));
+    assert!(content.contains("(Paris) Tj"));
+    let bbox: Rect = stream
+        .dict()
+        .dict_get_key("/BBox".to_string())
+        .try_into()
+        .unwrap();
+    assert_eq!(Rect::from_size(200.0, 20.0), bbox);
+
+    // The missing /Helv font is added to the form once and shared by later fills
+    let helv = form
+        .dict_get_key("/DR".to_string())
+        .dict_get_key("/Font".to_string())
+        .dict_get_key("/Helv".to_string());
+    assert!(helv.object_id() != 0);
+
+    qpdf.set_field_value("city", QPDFFieldValue::Text("Lyon".to_string()))
+        .unwrap();
+    let font = text
+        .dict_get_key("/AP".to_string())
+        .dict_get_key("/N".to_string())
+        .dict()
+        .dict_get_key("/Resources".to_string())
+        .dict_get_key("/Font".to_string())
+        .dict_get_key("/F1".to_string());
+    assert_eq!(helv.object_id(), font.object_id());
+
+    qpdf.set_field_value("agree", QPDFFieldValue::State("Yes".to_string()))
+        .unwrap();
+    assert_eq!(
+        Ok("/Yes".to_string()),
+        check.dict_get_key("/AS".to_string()).name()
+    );
+    assert_eq!(
+        Ok("/Yes".to_string()),
+        check.dict_get_key("/V".to_string()).name()
+    );
+    assert!(matches!(
+        qpdf.set_field_value("agree", QPDFFieldValue::State("/Maybe".to_string())),
+        Err(QPDFErrors::InvalidFieldValue(_))
+    ));
+
+    qpdf.set_field_value("colour", QPDFFieldValue::Text("r".to_string()))
+        .unwrap();
+    let stream = colour
+        .dict_get_key("/AP".to_string())
+        .dict_get_key("/N".to_string());
+    let content = String::from_utf8(stream.raw_stream_data().unwrap()).unwrap();
+    assert!(content.contains("(Red) Tj"));
+    assert!(matches!(
+        qpdf.set_field_value(
+            "colour",
+            QPDFFieldValue::Choices(vec!["r".to_string(), "b".to_string()])
+        ),
+        Err(QPDFErrors::InvalidFieldValue(_))
+    ));
+
+    assert!(matches!(
+        qpdf.set_field_value("city", QPDFFieldValue::State("/Yes".to_string())),
+        Err(QPDFErrors::InvalidFieldValue(_))
+    ));
+    assert!(matches!(
+        qpdf.set_field_value("missing", QPDFFieldValue::Text(String::new())),
+        Err(QPDFErrors::KeyNotFound)
+    ));
+    assert!(!form.dict_has_key("/NeedAppearances".to_string()));
+}
+
+#[test]
+fn flatten_form_fields() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let name = |n: &str| root.set(QPDFModifyObjectTypes::Name(n.to_string()));
+    let string = |s: &str| root.set(QPDFModifyObjectTypes::String(s.to_string()));
+    let int = |v: i64| root.set(QPDFModifyObjectTypes::Integer(v));
+
+    let widget = |partial: &str, rect: Rect| {
+        dict(
+            &root,
+            vec![
+                ("/T", string(partial)),
+                ("/Subtype", name("/Widget")),
+                ("/F", int(4)),
+                ("/Rect", rect.to_object(&root)),
+            ],
+        )
+    };
+
+    let first = widget("first", Rect::new(100.0, 100.0, 200.0, 120.0));
+    let last = widget("last", Rect::new(100.0, 130.0, 200.0, 150.0));
+    let signed = widget("signed", Rect::new(100.0, 160.0, 200.0, 180.0));
+
+    let group = dict(
+        &root,
+        vec![
+            ("/T", string("name")),
+            ("/FT", name("/Tx")),
+            ("/Kids", array(&root, vec![first.clone(), last.clone()])),
+        ],
+    );
+    for kid in [&first, &last] {
+        kid.dict_replace_key("/Parent".to_string(), group.clone());
+    }
+    signed.dict_replace_key("/FT".to_string(), name("/Tx"));
+
+    let fields = array(&root, vec![group, signed.clone()]);
+    let order = array(&root, vec![first.clone(), signed.clone()]);
+    let form = dict(
+        &root,
+        vec![("/Fields", fields.clone()), ("/CO", order.clone())],
+    );
+    root.dict_replace_key("/AcroForm".to_string(), form.clone());
+    page.object().dict_replace_key(
+        "/Annots".to_string(),
+        array(&root, vec![first, last, signed.clone()]),
+    );
+
+    for field in ["name.first", "name.last", "signed"] {
+        qpdf.set_field_value(field, QPDFFieldValue::Text(field.to_string()))
+            .unwrap();
+    }
+
+    let flattened = qpdf
+        .flatten_form_fields(QPDFAnnotationFlattenScope::Print, |field| {
+            field.info().name.starts_with("name.")
+        })
+        .unwrap();
+    assert_eq!(2, flattened);
+
+    // The emptied group is pruned while the remaining field keeps the form alive
+    assert_eq!(1, fields.array_len());
+    assert_eq!(1, page.annotations().len());
+    assert_eq!("signed", qpdf.form_fields()[0].info().name);
+    assert_eq!(1, order.array_len());
+
+    let content = String::from_utf8(page.content_data().unwrap()).unwrap();
+    assert!(content.contains("q\n1 0 0 1 100 100 cm\n/X1 Do\nQ\n"));
+    assert!(content.contains("/X2 Do"));
+
+    // List boxes cannot be redrawn here, so stale appearances are refused
+    let pick = widget("pick", Rect::new(300.0, 100.0, 400.0, 150.0));
+    pick.dict_replace_key("/FT".to_string(), name("/Ch"));
+    fields.array_append(pick);
+    form.dict_replace_key(
+        "/NeedAppearances".to_string(),
+        root.set(QPDFModifyObjectTypes::Bool(true)),
+    );
+    assert!(matches!(
+        qpdf.flatten_form_fields(QPDFAnnotationFlattenScope::Print, |_| true),
+        Err(QPDFErrors::MissingAppearance(name)) if name == "pick"
+    ));
+    fields.array_erase_at(1);
+
+    // Values changed without an appearance are redrawn before flattening
+    signed.dict_replace_key("/V".to_string(), string("changed"));
+    assert_eq!(
+        1,
+        qpdf.flatten_form_fields(QPDFAnnotationFlattenScope::Print, |_| true)
+            .unwrap()
+    );
+    let stream = signed
+        .dict_get_key("/AP".to_string())
+        .dict_get_key("/N".to_string());
+    let appearance = String::from_utf8(stream.raw_stream_data().unwrap()).unwrap();
+    assert!(appearance.contains("(changed) Tj"));
+
+    assert!(!root.dict_has_key("/AcroForm".to_string()));
+    assert!(!page.object().dict_has_key("/Annots".to_string()));
+    assert!(qpdf.form_fields().is_empty());
+}
+
+#[test]
+fn flatten_annotations_covers_form_fields() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let name = |n: &str| root.set(QPDFModifyObjectTypes::Name(n.to_string()));
+    let string = |s: &str| root.set(QPDFModifyObjectTypes::String(s.to_string()));
+
+    let field = dict(
+        &root,
+        vec![
+            ("/T", string("note")),
+            ("/FT", name("/Tx")),
+            ("/Subtype", name("/Widget")),
+            ("/F", root.set(QPDFModifyObjectTypes::Integer(4))),
+            (
+                "/Rect",
+                Rect::new(100.0, 100.0, 200.0, 120.0).to_object(&root),
+            ),
+        ],
+    );
+    let form = dict(
+        &root,
+        vec![
+            ("/Fields", array(&root, vec![field.clone()])),
+            (
+                "/NeedAppearances",
+                root.set(QPDFModifyObjectTypes::Bool(true)),
+            ),
+        ],
+    );
+    root.dict_replace_key("/AcroForm".to_string(), form.clone());
+    page.object()
+        .dict_replace_key("/Annots".to_string(), array(&root, vec![field]));
+    qpdf.set_field_value("note", QPDFFieldValue::Text("kept".to_string()))
+        .unwrap();
+
+    // Viewers are still asked to redraw the fields, so they are left alone
+    form.dict_replace_key(
+        "/NeedAppearances".to_string(),
+        root.set(QPDFModifyObjectTypes::Bool(true)),
+    );
+    assert_eq!(
+        0,
+        qpdf.flatten_annotations(QPDFAnnotationFlattenScope::All)
+            .unwrap()
+    );
+    assert_eq!(1, page.annotations().len());
+
+    form.dict_remove_key("/NeedAppearances".to_string());
+    assert_eq!(
+        1,
+        qpdf.flatten_annotations(QPDFAnnotationFlattenScope::All)
+            .unwrap()
+    );
+    assert!(page.annotations().is_empty());
+    assert!(!root.dict_has_key("/AcroForm".to_string()));
+}
@@ -0,0 +1,148 @@
+use crate::qpdf::{
+    geometry::Rect,
+    object::types::{Generation, ObjectId},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QPDFFieldType {
+    Text,
+    Button,
+    Choice,
+    Signature,
+    Unknown(String),
+}
+
+// Decoded /Ff entry, one field per pdf_form_field_flag_e bit that applies to the field type
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QPDFFieldFlags {
+    pub read_only: bool,
+    pub required: bool,
+    pub no_export: bool,
+    pub no_toggle_off: bool,
+    pub radio: bool,
+    pub pushbutton: bool,
+    pub radios_in_unison: bool,
+    pub multiline: bool,
+    pub password: bool,
+    pub file_select: bool,
+    pub do_not_spell_check: bool,
+    pub do_not_scroll: bool,
+    pub comb: bool,
+    pub rich_text: bool,
+    pub combo: bool,
+    pub edit: bool,
+    pub sort: bool,
+    pub multi_select: bool,
+    pub commit_on_sel_change: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QPDFFieldValue {
+    Text(String),
+    // Button states are names such as /Yes or /Off
+    State(String),
+    Choices(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFFieldOption {
+    pub export: String,
+    pub display: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFFieldWidget {
+    pub object_id: ObjectId,
+    pub generation: Generation,
+    pub page_index: Option<usize>,
+    pub rect: Option<Rect>,
+    pub appearance_state: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFFormFieldInfo {
+    pub object_id: ObjectId,
+    pub generation: Generation,
+    pub name: String,
+    pub field_type: QPDFFieldType,
+    pub value: Option<QPDFFieldValue>,
+    pub default_value: Option<QPDFFieldValue>,
+    pub options: Vec<QPDFFieldOption>,
+    pub export_values: Vec<String>,
+    pub widgets: Vec<QPDFFieldWidget>,
+    pub flags: QPDFFieldFlags,
+}
+
+impl From<&str> for QPDFFieldType {
+    fn from(name: &str) -> Self {
+        match name {
+            "/Tx" => QPDFFieldType::Text,
+            "/Btn" => QPDFFieldType::Button,
+            "/Ch" => QPDFFieldType::Choice,
+            "/Sig" => QPDFFieldType::Signature,
+            _ => QPDFFieldType::Unknown(name.to_string()),
+        }
+    }
+}
+
+impl QPDFFieldFlags {
+    // Several bits mean different things depending on the field type
+    pub fn decode(flags: i64, field_type: &QPDFFieldType) -> Self {
+        let bit = |n: u32| flags & (1 << n) != 0;
+
+        let button = *field_type == QPDFFieldType::Button;
+        let text = *field_type == QPDFFieldType::Text;
+        let choice = *field_type == QPDFFieldType::Choice;
+
+        Self {
+            read_only: bit(0),
+            required: bit(1),
+            no_export: bit(2),
+            no_toggle_off: button && bit(14),
+            radio: button && bit(15),
+            pushbutton: button && bit(16),
+            // The spec puts RadiosInUnison at bit 26, not where qpdf's header has it
+            radios_in_unison: button && bit(25),
+            multiline: text && bit(12),
+            password: text && bit(13),
+            file_select: text && bit(20),
+            do_not_spell_check: (text || choice) && bit(22),
+            do_not_scroll: text && bit(23),
+            comb: text && bit(24),
+            rich_text: text && bit(25),
+            combo: choice && bit(17),
+            edit: choice && bit(18),
+            sort: choice && bit(19),
+            multi_select: choice && bit(21),
+            commit_on_sel_change: choice && bit(26),
+        }
+    }
+}
+
+impl From<QPDFFieldFlags> for i64 {
+    fn from(flags: QPDFFieldFlags) -> Self {
+        [
+            (flags.read_only, 0),
+            (flags.required, 1),
+            (flags.no_export, 2),
+            (flags.no_toggle_off, 14),
+            (flags.radio, 15),
+            (flags.pushbutton, 16),
+            (flags.radios_in_unison, 25),
+            (flags.multiline, 12),
+            (flags.password, 13),
+            (flags.file_select, 20),
+            (flags.do_not_spell_check, 22),
+            (flags.do_not_scroll, 23),
+            (flags.comb, 24),
+            (flags.rich_text, 25),
+            (flags.combo, 17),
+            (flags.edit, 18),
+            (flags.sort, 19),
+            (flags.multi_select, 21),
+            (flags.commit_on_sel_change, 26),
+        ]
+        .iter()
+        .fold(0, |acc, (set, n)| acc | (*set as i64) << n)
+    }
+}
@@ -0,0 +1,242 @@
+use std::fmt::Display;
+
+use super::object::{
+    QPDFObjectHandler,
+    types::{QPDFIsObjectType, QPDFModifyObjectTypes},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub llx: f64,
+    pub lly: f64,
+    pub urx: f64,
+    pub ury: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+// Rect Construction
+impl Rect {
+    pub fn new(llx: f64, lly: f64, urx: f64, ury: f64) -> Self {
+        Self {
+            llx: llx.min(urx),
+            lly: lly.min(ury),
+            urx: llx.max(urx),
+            ury: lly.max(ury),
+        }
+    }
+
+    pub fn from_size(width: f64, height: f64) -> Self {
+        Self::new(0.0, 0.0, width, height)
+    }
+
+    pub(crate) fn to_object(self, factory: &QPDFObjectHandler) -> QPDFObjectHandler {
+        let array = factory.set(QPDFModifyObjectTypes::Array);
+
+        for v in [self.llx, self.lly, self.urx, self.ury] {
+            array.array_append(factory.set(QPDFModifyObjectTypes::Real(v, 4)));
+        }
+
+        array
+    }
+}
+
+// Rect Methods
+impl Rect {
+    pub fn width(&self) -> f64 {
+        self.urx - self.llx
+    }
+
+    pub fn height(&self) -> f64 {
+        self.ury - self.lly
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width() <= 0.0 || self.height() <= 0.0
+    }
+
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.llx && x <= self.urx && y >= self.lly && y <= self.ury
+    }
+
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let r = Rect {
+            llx: self.llx.max(other.llx),
+            lly: self.lly.max(other.lly),
+            urx: self.urx.min(other.urx),
+            ury: self.ury.min(other.ury),
+        };
+
+        match r.llx > r.urx || r.lly > r.ury {
+            true => None,
+            _ => Some(r),
+        }
+    }
+
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            llx: self.llx.min(other.llx),
+            lly: self.lly.min(other.lly),
+            urx: self.urx.max(other.urx),
+            ury: self.ury.max(other.ury),
+        }
+    }
+
+    pub fn expand(&self, margin: f64) -> Rect {
+        Rect::new(
+            self.llx - margin,
+            self.lly - margin,
+            self.urx + margin,
+            self.ury + margin,
+        )
+    }
+
+    pub fn transform(&self, m: &Matrix) -> Rect {
+        let corners = [
+            m.apply(self.llx, self.lly),
+            m.apply(self.urx, self.lly),
+            m.apply(self.llx, self.ury),
+            m.apply(self.urx, self.ury),
+        ];
+
+        let (x0, y0) = corners[0];
+        corners
+            .iter()
+            .fold(Rect::new(x0, y0, x0, y0), |r, &(x, y)| {
+                r.union(&Rect::new(x, y, x, y))
+            })
+    }
+}
+
+impl TryInto<Rect> for QPDFObjectHandler {
+    type Error = ();
+
+    fn try_into(self) -> Result<Rect, Self::Error> {
+        if !self.is(QPDFIsObjectType::Array) || self.array_len() != 4 {
+            return Err(());
+        }
+
+        let mut v = [0.0; 4];
+        for (i, item) in v.iter_mut().enumerate() {
+            *item = self.array_get_at(i as i32).try_into()?;
+        }
+
+        Ok(Rect::new(v[0], v[1], v[2], v[3]))
+    }
+}
+
+// Matrix Construction
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Matrix {
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    pub fn translate(tx: f64, ty: f64) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, tx, ty)
+    }
+
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        Self::new(sx, 0.0, 0.0, sy, 0.0, 0.0)
+    }
+
+    pub fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+}
+
+// Matrix Methods
+impl Matrix {
+    // Returns `self × other`, i.e. `self` applied first, following the `cm` operator
+    pub fn multiply(&self, other: &Matrix) -> Matrix {
+        Matrix {
+            a: self.a * other.a + self.b * other.c,
+            b: self.a * other.b + self.b * other.d,
+            c: self.c * other.a + self.d * other.c,
+            d: self.c * other.b + self.d * other.d,
+            e: self.e * other.a + self.f * other.c + other.e,
+            f: self.e * other.b + self.f * other.d + other.f,
+        }
+    }
+
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    pub fn invert(&self) -> Option<Matrix> {
+        let det = self.a * self.d - self.b * self.c;
+
+        if det.abs() < f64::EPSILON {
+            return None;
+        }
+
+        Some(Matrix {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            e: (self.c * self.f - self.d * self.e) / det,
+            f: (self.b * self.e - self.a * self.f) / det,
+        })
+    }
+}
+
+impl TryInto<Matrix> for QPDFObjectHandler {
+    type Error = ();
+
+    fn try_into(self) -> Result<Matrix, Self::Error> {
+        if !self.is(QPDFIsObjectType::Array) || self.array_len() != 6 {
+            return Err(());
+        }
+
+        let mut v = [0.0; 6];
+        for (i, item) in v.iter_mut().enumerate() {
+            *item = self.array_get_at(i as i32).try_into()?;
+        }
+
+        Ok(Matrix::new(v[0], v[1], v[2], v[3], v[4], v[5]))
+    }
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let v = [self.a, self.b, self.c, self.d, self.e, self.f].map(format_number);
+        write!(f, "{}", v.join(" "))
+    }
+}
+
+// Formats a number the way it should appear in a content stream
+pub(crate) fn format_number(v: f64) -> String {
+    let v = (v * 100000.0).round() / 100000.0;
+
+    if v == v.trunc() {
+        return format!("{}", v as i64);
+    }
+
+    let s = format!("{v:.5}");
+    s.trim_end_matches('0').to_string()
+}
+
+#[cfg(test)]
+mod tests;
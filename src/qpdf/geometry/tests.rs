@@ -0,0 +1,54 @@
+use super::*;
+
+#[test]
+fn rect_is_normalized() {
+    let r = Rect::new(100.0, 200.0, 0.0, 50.0);
+
+    assert_eq!(Rect::new(0.0, 50.0, 100.0, 200.0), r);
+    assert_eq!(100.0, r.width());
+    assert_eq!(150.0, r.height());
+}
+
+#[test]
+fn rect_intersect_and_union() {
+    let a = Rect::new(0.0, 0.0, 100.0, 100.0);
+    let b = Rect::new(50.0, 50.0, 150.0, 150.0);
+
+    assert_eq!(Some(Rect::new(50.0, 50.0, 100.0, 100.0)), a.intersect(&b));
+    assert_eq!(Rect::new(0.0, 0.0, 150.0, 150.0), a.union(&b));
+    assert!(
+        a.intersect(&Rect::new(200.0, 200.0, 300.0, 300.0))
+            .is_none()
+    );
+}
+
+#[test]
+fn matrix_multiply_applies_left_first() {
+    let m = Matrix::scale(2.0, 2.0).multiply(&Matrix::translate(10.0, 20.0));
+
+    assert_eq!((12.0, 22.0), m.apply(1.0, 1.0));
+}
+
+#[test]
+fn matrix_invert() {
+    let m = Matrix::new(2.0, 0.0, 0.0, 4.0, 10.0, 20.0);
+    let i = m.invert().unwrap();
+
+    assert_eq!(Matrix::identity(), m.multiply(&i));
+    assert!(Matrix::scale(0.0, 1.0).invert().is_none());
+}
+
+#[test]
+fn rect_transform_by_rotation() {
+    let r = Rect::new(0.0, 0.0, 100.0, 50.0).transform(&Matrix::rotate(90.0));
+
+    assert!((r.llx + 50.0).abs() < 1e-9);
+    assert!((r.ury - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn matrix_display() {
+    let m = Matrix::new(0.5, 0.0, 0.0, 0.333333333, 12.0, -3.25);
+
+    assert_eq!("0.5 0 0 0.33333 12 -3.25", m.to_string());
+}
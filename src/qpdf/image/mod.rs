@@ -0,0 +1,630 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::Read,
+};
+
+use png::encode_png;
+use types::{
+    QPDFImageColorSpace, QPDFImageFormat, QPDFImageInfo, QPDFImageOptimizeParams,
+    QPDFImageOptimizeResult, QPDFImageReplaceParams,
+};
+
+use super::{
+    QPDFErrors,
+    content::{
+        QPDFContentParser,
+        types::{QPDFContentOperation, QPDFContentValue},
+    },
+    filters::{
+        QPDFFilterRegistry, QPDFStreamEncode, QPDFStreamReader, filter_chain,
+        flate::FlateEncoder,
+        predictor::PredictorEncoder,
+        types::{QPDFDecodeParms, QPDFFilter},
+    },
+    object::{
+        QPDFObjectHandler,
+        types::{Generation, ObjectId, QPDFIsObjectType, QPDFModifyObjectTypes},
+    },
+    write::QPDFWriteDecodeLevel,
+};
+
+// Limits how deeply form XObjects are followed when looking for images
+const MAX_FORM_DEPTH: usize = 32;
+
+pub struct QPDFImage {
+    info: QPDFImageInfo,
+    source: QPDFImageSource,
+}
+
+pub(crate) enum QPDFImageSource {
+    XObject(QPDFObjectHandler),
+    Inline(Vec<u8>, Vec<(QPDFFilter, QPDFDecodeParms)>),
+}
+
+// Construction
+impl QPDFImage {
+    pub fn from_xobject(
+        name: Option<String>,
+        object: QPDFObjectHandler,
+    ) -> Result<Self, QPDFErrors> {
+        let dict = object.dict();
+
+        if !object.is(QPDFIsObjectType::Stream)
+            || !dict
+                .dict_get_key("/Subtype".to_string())
+                .is(QPDFIsObjectType::NameEquals("/Image".to_string()))
+        {
+            return Err(QPDFErrors::InvalidObject);
+        }
+
+        let int = |key: &str| dict.dict_get_key(key.to_string()).try_into().ok();
+        let image_mask = dict
+            .dict_get_key("/ImageMask".to_string())
+            .try_into()
+            .unwrap_or(false);
+
+        let color_space = dict.dict_get_key("/ColorSpace".to_string());
+        let color_space = match image_mask {
+            true => QPDFImageColorSpace::DeviceGray,
+            _ => color_space_from_object(&color_space, None, 0),
+        };
+
+        let default_bpc = if image_mask { 1 } else { 8 };
+        let decode = dict.dict_get_key("/Decode".to_string());
+        let decode = (0..decode.array_len())
+            .filter_map(|i| decode.array_get_at(i).try_into().ok())
+            .collect();
+
+        let info = QPDFImageInfo {
+            name,
+            object_id: object.object_id(),
+            generation: object.generation(),
+            width: int("/Width").unwrap_or(0),
+            height: int("/Height").unwrap_or(0),
+            bits_per_component: int("/BitsPerComponent").unwrap_or(default_bpc),
+            color_space,
+            filters: filter_chain(&dict).into_iter().map(|(f, _)| f).collect(),
+            decode,
+            image_mask,
+            inline: false,
+        };
+
+        Ok(Self {
+            info,
+            source: QPDFImageSource::XObject(object),
+        })
+    }
+
+    pub fn from_inline(
+        dict: &BTreeMap<String, QPDFContentValue>,
+        data: Vec<u8>,
+        resources: &QPDFObjectHandler,
+    ) -> Self {
+        let get = |short: &str, long: &str| dict.get(short).or_else(|| dict.get(long));
+        let int = |short: &str, long: &str| get(short, long).and_then(|v| v.as_number());
+
+        let image_mask = matches!(get("/IM", "/ImageMask"), Some(QPDFContentValue::Bool(true)));
+        let default_bpc = if image_mask { 1.0 } else { 8.0 };
+        let color_space = match (image_mask, get("/CS", "/ColorSpace")) {
+            (false, Some(value)) => color_space_from_value(value, resources),
+            _ => QPDFImageColorSpace::DeviceGray,
+        };
+
+        let filters: Vec<QPDFFilter> = match get("/F", "/Filter") {
+            Some(QPDFContentValue::Name(name)) => vec![QPDFFilter::from(name.as_str())],
+            Some(QPDFContentValue::Array(names)) => names
+                .iter()
+                .filter_map(|v| v.as_name())
+                .map(QPDFFilter::from)
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let parms: Vec<QPDFDecodeParms> = match get("/DP", "/DecodeParms") {
+            Some(QPDFContentValue::Array(items)) => items.iter().map(parms_from_value).collect(),
+            Some(value) => vec![parms_from_value(value)],
+            None => Vec::new(),
+        };
+
+        let chain = filters
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (f.clone(), parms.get(i).cloned().unwrap_or_default()))
+            .collect();
+
+        let decode = match get("/D", "/Decode") {
+            Some(QPDFContentValue::Array(items)) => {
+                items.iter().filter_map(|v| v.as_number()).collect()
+            }
+            _ => Vec::new(),
+        };
+
+        let info = QPDFImageInfo {
+            name: None,
+            object_id: 0,
+            generation: 0,
+            width: int("/W", "/Width").unwrap_or(0.0) as i64,
+            height: int("/H", "/Height").unwrap_or(0.0) as i64,
+            bits_per_component: int("/BPC", "/BitsPerComponent").unwrap_or(default_bpc) as i64,
+            color_space,
+            filters,
+            decode,
+            image_mask,
+            inline: true,
+        };
+
+        Self {
+            info,
+            source: QPDFImageSource::Inline(data, chain),
+        }
+    }
+
+    pub fn info(&self) -> &QPDFImageInfo {
+        &self.info
+    }
+
+    pub fn object(&self) -> Option<&QPDFObjectHandler> {
+        match &self.source {
+            QPDFImageSource::XObject(object) => Some(object),
+            QPDFImageSource::Inline(..) => None,
+        }
+    }
+}
+
+// Extraction
+impl QPDFImage {
+    fn image_codec(&self) -> Option<&QPDFFilter> {
+        self.info.filters.last().filter(|f| f.is_image_codec())
+    }
+
+    // Decodes every filter except a trailing image codec such as DCTDecode
+    pub fn encoded_data(&self) -> Result<Vec<u8>, QPDFErrors> {
+        let mut reader = match &self.source {
+            QPDFImageSource::XObject(object) => object.stream_reader()?,
+            QPDFImageSource::Inline(data, chain) => {
+                QPDFStreamReader::new(data.clone(), chain.clone())?
+            }
+        };
+
+        let mut data = Vec::new();
+        reader
+            .read_to_end(&mut data)
+            .map_err(|_| QPDFErrors::InvalidStreamData)?;
+
+        Ok(data)
+    }
+
+    pub fn raw_samples(&self) -> Result<Vec<u8>, QPDFErrors> {
+        if let Some(codec) = self.image_codec() {
+            return Err(QPDFErrors::UnsupportedFilter(codec.name()));
+        }
+
+        self.encoded_data()
+    }
+
+    pub fn passthrough(&self) -> Result<(QPDFImageFormat, Vec<u8>), QPDFErrors> {
+        let format = match self.image_codec() {
+            Some(QPDFFilter::DCTDecode) => QPDFImageFormat::Jpeg,
+            Some(QPDFFilter::JPXDecode) => QPDFImageFormat::Jpeg2000,
+            _ => return Err(QPDFErrors::UnsupportedImage),
+        };
+
+        Ok((format, self.encoded_data()?))
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>, QPDFErrors> {
+        encode_png(&self.info, &self.raw_samples()?)
+    }
+
+    // Prefers the original JPEG bytes, then PNG, then the raw samples
+    pub fn extract(&self) -> Result<(QPDFImageFormat, Vec<u8>), QPDFErrors> {
+        match self.image_codec() {
+            Some(QPDFFilter::DCTDecode | QPDFFilter::JPXDecode) => self.passthrough(),
+            Some(codec) => Err(QPDFErrors::UnsupportedFilter(codec.name())),
+            None => match self.to_png() {
+                Ok(png) => Ok((QPDFImageFormat::Png, png)),
+                Err(QPDFErrors::UnsupportedImage) => {
+                    Ok((QPDFImageFormat::Raw, self.raw_samples()?))
+                }
+                Err(e) => Err(e),
+            },
+        }
+    }
+}
+
+// Replacement
+impl QPDFImage {
+    pub fn replace(
+        &mut self,
+        data: &[u8],
+        params: QPDFImageReplaceParams,
+    ) -> Result<(), QPDFErrors> {
+        let QPDFImageSource::XObject(object) = &self.source else {
+            return Err(QPDFErrors::InvalidObject);
+        };
+
+        let mut info = self.info.clone();
+        info.width = params.width.unwrap_or(info.width);
+        info.height = params.height.unwrap_or(info.height);
+        info.bits_per_component = params.bits_per_component.unwrap_or(info.bits_per_component);
+        info.filters = params.filters.iter().map(|(f, _)| f.clone()).collect();
+
+        let color_space = match params.color_space {
+            Some(space) => {
+                let value = color_space_to_object(&space, object)?;
+                info.color_space = space;
+                Some(value)
+            }
+            None => None,
+        };
+
+        let encoded = match params.pre_encoded {
+            true => data.to_vec(),
+            _ => {
+                if info.width <= 0 || info.height <= 0 || data.len() < sample_length(&info) {
+                    return Err(QPDFErrors::InvalidStreamData);
+                }
+
+                QPDFFilterRegistry::default().encode(data, &params.filters)?
+            }
+        };
+
+        let dict = object.dict();
+        for (key, value) in [
+            ("/Width", info.width),
+            ("/Height", info.height),
+            ("/BitsPerComponent", info.bits_per_component),
+        ] {
+            dict.dict_replace_key(
+                key.to_string(),
+                object.set(QPDFModifyObjectTypes::Integer(value)),
+            );
+        }
+
+        if let Some(color_space) = color_space {
+            dict.dict_replace_key("/ColorSpace".to_string(), color_space);
+        }
+
+        object.replace_encoded_stream_data(&encoded, &params.filters)?;
+        self.info = info;
+
+        Ok(())
+    }
+}
+
+// Optimization
+impl QPDFImage {
+    fn can_downsample(&self) -> bool {
+        self.info.bits_per_component == 8
+            && !self.info.image_mask
+            && !matches!(self.info.color_space, QPDFImageColorSpace::Indexed { .. })
+    }
+
+    // Recompresses uncompressed or Flate images, returning None when nothing was gained
+    pub fn optimize(
+        &mut self,
+        params: &QPDFImageOptimizeParams,
+    ) -> Result<Option<QPDFImageOptimizeResult>, QPDFErrors> {
+        let Some(object) = self.object() else {
+            return Ok(None);
+        };
+
+        let recompressible = self
+            .info
+            .filters
+            .iter()
+            .all(|f| *f == QPDFFilter::FlateDecode);
+        let before = object.raw_stream_data()?.len();
+
+        if !recompressible || before < params.threshold {
+            return Ok(None);
+        }
+
+        let mut samples = self.raw_samples()?;
+        if self.info.width <= 0
+            || self.info.height <= 0
+            || samples.len() < sample_length(&self.info)
+        {
+            return Ok(None);
+        }
+
+        let components = self.info.color_space.components();
+        let (mut width, mut height) = (self.info.width, self.info.height);
+        let mut replace = QPDFImageReplaceParams::default().with_pre_encoded(true);
+
+        if let Some(max) = params.max_dimension
+            && max > 0
+            && width.max(height) > max
+            && self.can_downsample()
+        {
+            let factor = (width.max(height) + max - 1) / max;
+            (samples, width, height) = downsample(&samples, width, height, components, factor);
+            replace = replace.with_width(width).with_height(height);
+        }
+
+        let bpc = self.info.bits_per_component;
+        let mut parms = QPDFDecodeParms::default();
+        if params.predictor && bpc >= 8 {
+            parms = parms
+                .with_predictor(15)
+                .with_colors(components)
+                .with_bits_per_component(bpc)
+                .with_columns(width);
+        }
+
+        let encoded = compress(&samples, &parms, params.level)?;
+        if encoded.len() >= before {
+            return Ok(None);
+        }
+
+        let result = QPDFImageOptimizeResult {
+            object_id: self.info.object_id,
+            generation: self.info.generation,
+            before,
+            after: encoded.len(),
+        };
+
+        self.replace(
+            &encoded,
+            replace.with_filter(QPDFFilter::FlateDecode, parms),
+        )?;
+        Ok(Some(result))
+    }
+}
+
+fn sample_length(info: &QPDFImageInfo) -> usize {
+    let bits = info.width * info.color_space.components() * info.bits_per_component;
+    (bits.max(0) as usize).div_ceil(8) * info.height.max(0) as usize
+}
+
+fn compress(samples: &[u8], parms: &QPDFDecodeParms, level: u8) -> Result<Vec<u8>, QPDFErrors> {
+    let mut predicted = Vec::new();
+    let samples = match parms.predictor > 1 {
+        true => {
+            PredictorEncoder::new(parms)?.encode(samples, &mut predicted, true)?;
+            &predicted
+        }
+        _ => samples,
+    };
+
+    let mut compressed = Vec::new();
+    FlateEncoder::new(level).encode(samples, &mut compressed, true)?;
+    Ok(compressed)
+}
+
+// Averages blocks of `factor` by `factor` pixels of an 8-bit image
+fn downsample(
+    samples: &[u8],
+    width: i64,
+    height: i64,
+    components: i64,
+    factor: i64,
+) -> (Vec<u8>, i64, i64) {
+    let (w, h, c, f) = (
+        width as usize,
+        height as usize,
+        components as usize,
+        factor as usize,
+    );
+    let (new_w, new_h) = (w.div_ceil(f), h.div_ceil(f));
+    let mut out = Vec::with_capacity(new_w * new_h * c);
+
+    for y in 0..new_h {
+        for x in 0..new_w {
+            for k in 0..c {
+                let (mut sum, mut n) = (0u64, 0u64);
+
+                for sy in y * f..((y + 1) * f).min(h) {
+                    for sx in x * f..((x + 1) * f).min(w) {
+                        sum += samples[(sy * w + sx) * c + k] as u64;
+                        n += 1;
+                    }
+                }
+
+                out.push((sum / n) as u8);
+            }
+        }
+    }
+
+    (out, new_w as i64, new_h as i64)
+}
+
+pub(crate) fn collect_images(
+    data: &[u8],
+    resources: &QPDFObjectHandler,
+    depth: usize,
+    seen: &mut HashSet<(ObjectId, Generation)>,
+    images: &mut Vec<QPDFImage>,
+) -> Result<(), QPDFErrors> {
+    let xobjects = resources.dict_get_key("/XObject".to_string());
+
+    for operation in QPDFContentParser::new(data) {
+        let name = match operation? {
+            QPDFContentOperation::InlineImage(dict, data) => {
+                images.push(QPDFImage::from_inline(&dict, data, resources));
+                continue;
+            }
+            QPDFContentOperation::Operator(op, operands) if op == "Do" => {
+                match operands.first().and_then(|v| v.as_name()) {
+                    Some(name) => name.to_string(),
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+
+        let xobject = xobjects.dict_get_key(name.clone());
+        if !xobject.is(QPDFIsObjectType::Stream)
+            || !seen.insert((xobject.object_id(), xobject.generation()))
+        {
+            continue;
+        }
+
+        let subtype = xobject.dict().dict_get_key("/Subtype".to_string());
+
+        if subtype.is(QPDFIsObjectType::NameEquals("/Image".to_string())) {
+            images.push(QPDFImage::from_xobject(Some(name), xobject)?);
+        } else if subtype.is(QPDFIsObjectType::NameEquals("/Form".to_string()))
+            && depth < MAX_FORM_DEPTH
+        {
+            let form_resources = xobject.dict().dict_get_key("/Resources".to_string());
+            let form_resources = match form_resources.is(QPDFIsObjectType::Dictionary) {
+                true => form_resources,
+                _ => resources.clone(),
+            };
+
+            let content = xobject.stream_data(QPDFWriteDecodeLevel::Generalized)?;
+            collect_images(&content.data, &form_resources, depth + 1, seen, images)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn color_space_from_object(
+    value: &QPDFObjectHandler,
+    resources: Option<&QPDFObjectHandler>,
+    depth: usize,
+) -> QPDFImageColorSpace {
+    if depth > MAX_FORM_DEPTH {
+        return QPDFImageColorSpace::Other(String::new());
+    }
+
+    if let Ok(name) = value.name() {
+        let space = QPDFImageColorSpace::from_name(&name);
+
+        let named = resources.map(|r| r.dict_get_key("/ColorSpace".to_string()).dict_get_key(name));
+
+        return match (&space, named) {
+            (QPDFImageColorSpace::Other(_), Some(named)) if !named.is(QPDFIsObjectType::Null) => {
+                color_space_from_object(&named, None, depth + 1)
+            }
+            _ => space,
+        };
+    }
+
+    if !value.is(QPDFIsObjectType::Array) || value.array_len() == 0 {
+        return QPDFImageColorSpace::DeviceGray;
+    }
+
+    let family = value.array_get_at(0).name().unwrap_or_default();
+
+    match family.as_str() {
+        "/ICCBased" => {
+            let n = value
+                .array_get_at(1)
+                .dict()
+                .dict_get_key("/N".to_string())
+                .try_into()
+                .unwrap_or(3);
+
+            QPDFImageColorSpace::ICCBased(n)
+        }
+        "/Indexed" | "/I" => {
+            let lookup = value.array_get_at(3);
+            let lookup = match lookup.is(QPDFIsObjectType::Stream) {
+                true => lookup
+                    .stream_data(QPDFWriteDecodeLevel::Generalized)
+                    .map(|d| d.data)
+                    .unwrap_or_default(),
+                _ => lookup.binary_string().unwrap_or_default(),
+            };
+
+            QPDFImageColorSpace::Indexed {
+                base: Box::new(color_space_from_object(
+                    &value.array_get_at(1),
+                    resources,
+                    depth + 1,
+                )),
+                hival: value.array_get_at(2).try_into().unwrap_or(0),
+                lookup,
+            }
+        }
+        "/Separation" => QPDFImageColorSpace::Separation,
+        "/DeviceN" => QPDFImageColorSpace::DeviceN(value.array_get_at(1).array_len() as i64),
+        name => QPDFImageColorSpace::from_name(name),
+    }
+}
+
+pub(crate) fn color_space_to_object(
+    space: &QPDFImageColorSpace,
+    factory: &QPDFObjectHandler,
+) -> Result<QPDFObjectHandler, QPDFErrors> {
+    let name = |n: &str| factory.set(QPDFModifyObjectTypes::Name(n.to_string()));
+
+    match space {
+        QPDFImageColorSpace::DeviceGray => Ok(name("/DeviceGray")),
+        QPDFImageColorSpace::DeviceRGB => Ok(name("/DeviceRGB")),
+        QPDFImageColorSpace::DeviceCMYK => Ok(name("/DeviceCMYK")),
+        QPDFImageColorSpace::Indexed {
+            base,
+            hival,
+            lookup,
+        } => {
+            let table = factory.set(QPDFModifyObjectTypes::Stream);
+            table.replace_stream_data(lookup, None, None);
+
+            let array = factory.set(QPDFModifyObjectTypes::Array);
+            array.array_append(name("/Indexed"));
+            array.array_append(color_space_to_object(base, factory)?);
+            array.array_append(factory.set(QPDFModifyObjectTypes::Integer(*hival)));
+            array.array_append(table);
+
+            Ok(array)
+        }
+        _ => Err(QPDFErrors::UnsupportedImage),
+    }
+}
+
+fn color_space_from_value(
+    value: &QPDFContentValue,
+    resources: &QPDFObjectHandler,
+) -> QPDFImageColorSpace {
+    match value {
+        QPDFContentValue::Name(name) => match QPDFImageColorSpace::from_name(name) {
+            QPDFImageColorSpace::Other(_) => {
+                let named = resources
+                    .dict_get_key("/ColorSpace".to_string())
+                    .dict_get_key(name.clone());
+
+                color_space_from_object(&named, None, 0)
+            }
+            space => space,
+        },
+        QPDFContentValue::Array(items) if items.len() == 4 => match items[0].as_name() {
+            Some("/I" | "/Indexed") => QPDFImageColorSpace::Indexed {
+                base: Box::new(color_space_from_value(&items[1], resources)),
+                hival: items[2].as_number().unwrap_or(0.0) as i64,
+                lookup: items[3].as_bytes().map(|b| b.to_vec()).unwrap_or_default(),
+            },
+            _ => QPDFImageColorSpace::Other(String::new()),
+        },
+        _ => QPDFImageColorSpace::Other(String::new()),
+    }
+}
+
+fn parms_from_value(value: &QPDFContentValue) -> QPDFDecodeParms {
+    let QPDFContentValue::Dictionary(dict) = value else {
+        return QPDFDecodeParms::default();
+    };
+
+    let int = |key: &str, default: i64| {
+        dict.get(key)
+            .and_then(|v| v.as_number())
+            .map_or(default, |v| v as i64)
+    };
+
+    let defaults = QPDFDecodeParms::default();
+    QPDFDecodeParms::default()
+        .with_predictor(int("/Predictor", defaults.predictor))
+        .with_colors(int("/Colors", defaults.colors))
+        .with_bits_per_component(int("/BitsPerComponent", defaults.bits_per_component))
+        .with_columns(int("/Columns", defaults.columns))
+        .with_early_change(int("/EarlyChange", defaults.early_change))
+}
+
+pub mod png;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
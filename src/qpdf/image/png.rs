@@ -0,0 +1,195 @@
+use miniz_oxide::deflate::compress_to_vec_zlib;
+
+use super::types::{QPDFImageColorSpace, QPDFImageInfo};
+use crate::qpdf::QPDFErrors;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+const GRAY: u8 = 0;
+const RGB: u8 = 2;
+const PALETTE: u8 = 3;
+
+struct PngLayout {
+    color_type: u8,
+    bit_depth: u8,
+    rows: Vec<Vec<u8>>,
+    palette: Option<Vec<u8>>,
+}
+
+pub(crate) fn encode_png(info: &QPDFImageInfo, samples: &[u8]) -> Result<Vec<u8>, QPDFErrors> {
+    let layout = layout(info, samples)?;
+
+    let mut scanlines = Vec::new();
+    for row in &layout.rows {
+        scanlines.push(0);
+        scanlines.extend(row);
+    }
+
+    let mut ihdr = Vec::new();
+    ihdr.extend((info.width as u32).to_be_bytes());
+    ihdr.extend((info.height as u32).to_be_bytes());
+    ihdr.extend([layout.bit_depth, layout.color_type, 0, 0, 0]);
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &ihdr);
+    if let Some(palette) = &layout.palette {
+        write_chunk(&mut png, b"PLTE", palette);
+    }
+    write_chunk(&mut png, b"IDAT", &compress_to_vec_zlib(&scanlines, 6));
+    write_chunk(&mut png, b"IEND", &[]);
+
+    Ok(png)
+}
+
+fn layout(info: &QPDFImageInfo, samples: &[u8]) -> Result<PngLayout, QPDFErrors> {
+    let bpc = info.bits_per_component;
+    let components = info.color_space.components();
+
+    if info.width <= 0 || info.height <= 0 || !matches!(bpc, 1 | 2 | 4 | 8 | 16) {
+        return Err(QPDFErrors::UnsupportedImage);
+    }
+
+    let width = info.width as usize;
+    let row_length = (width * (components * bpc) as usize).div_ceil(8);
+
+    if samples.len() < row_length * info.height as usize {
+        return Err(QPDFErrors::InvalidStreamData);
+    }
+
+    let rows = samples.chunks(row_length).take(info.height as usize);
+    let inverted = matches!(info.decode.as_slice(), [d0, d1, ..] if d0 > d1);
+
+    let gray = |invert: bool| PngLayout {
+        color_type: GRAY,
+        bit_depth: bpc as u8,
+        rows: rows
+            .clone()
+            .map(|r| r.iter().map(|&c| if invert { !c } else { c }).collect())
+            .collect(),
+        palette: None,
+    };
+
+    let space = match &info.color_space {
+        QPDFImageColorSpace::ICCBased(1) => &QPDFImageColorSpace::DeviceGray,
+        QPDFImageColorSpace::ICCBased(3) => &QPDFImageColorSpace::DeviceRGB,
+        QPDFImageColorSpace::ICCBased(4) => &QPDFImageColorSpace::DeviceCMYK,
+        space => space,
+    };
+
+    if info.image_mask {
+        return Ok(gray(inverted));
+    }
+
+    let layout = match space {
+        QPDFImageColorSpace::DeviceGray | QPDFImageColorSpace::CalGray => gray(inverted),
+        // Separation tints measure ink coverage, so full tint is dark
+        QPDFImageColorSpace::Separation => gray(!inverted),
+        QPDFImageColorSpace::DeviceRGB | QPDFImageColorSpace::CalRGB => PngLayout {
+            color_type: RGB,
+            bit_depth: if bpc == 16 { 16 } else { 8 },
+            rows: rows
+                .map(|r| match bpc {
+                    8 | 16 => r.to_vec(),
+                    _ => to_8bit(r, width * 3, bpc),
+                })
+                .collect(),
+            palette: None,
+        },
+        QPDFImageColorSpace::DeviceCMYK => PngLayout {
+            color_type: RGB,
+            bit_depth: 8,
+            rows: rows
+                .map(|r| cmyk_to_rgb(&to_8bit(r, width * 4, bpc)))
+                .collect(),
+            palette: None,
+        },
+        QPDFImageColorSpace::Indexed {
+            base,
+            hival,
+            lookup,
+        } if bpc <= 8 => PngLayout {
+            color_type: PALETTE,
+            bit_depth: bpc as u8,
+            rows: rows.map(|r| r.to_vec()).collect(),
+            palette: Some(palette(base, *hival, lookup)?),
+        },
+        _ => return Err(QPDFErrors::UnsupportedImage),
+    };
+
+    Ok(layout)
+}
+
+fn palette(base: &QPDFImageColorSpace, hival: i64, lookup: &[u8]) -> Result<Vec<u8>, QPDFErrors> {
+    let entries = (hival.clamp(0, 255) + 1) as usize;
+    let components = base.components() as usize;
+
+    let mut table = lookup.to_vec();
+    table.resize(entries * components, 0);
+
+    let rgb = match base {
+        QPDFImageColorSpace::DeviceRGB
+        | QPDFImageColorSpace::CalRGB
+        | QPDFImageColorSpace::ICCBased(3) => table,
+        QPDFImageColorSpace::DeviceGray
+        | QPDFImageColorSpace::CalGray
+        | QPDFImageColorSpace::ICCBased(1) => table.iter().flat_map(|&g| [g, g, g]).collect(),
+        QPDFImageColorSpace::DeviceCMYK | QPDFImageColorSpace::ICCBased(4) => cmyk_to_rgb(&table),
+        _ => return Err(QPDFErrors::UnsupportedImage),
+    };
+
+    Ok(rgb)
+}
+
+// Scales packed samples of any bit depth to one byte each
+fn to_8bit(row: &[u8], count: usize, bpc: i64) -> Vec<u8> {
+    let bits = bpc as usize;
+    let max = (1u32 << bits) - 1;
+
+    (0..count)
+        .map(|i| match bits {
+            8 => row[i],
+            16 => row[i * 2],
+            _ => {
+                let bit = i * bits;
+                let v = (row[bit / 8] >> (8 - bits - bit % 8)) as u32 & max;
+                (v * 255 / max) as u8
+            }
+        })
+        .collect()
+}
+
+fn cmyk_to_rgb(cmyk: &[u8]) -> Vec<u8> {
+    cmyk.chunks_exact(4)
+        .flat_map(|p| {
+            let k = 255 - p[3] as u32;
+            [0, 1, 2].map(|i| ((255 - p[i] as u32) * k / 255) as u8)
+        })
+        .collect()
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+
+    !crc
+}
@@ -0,0 +1,332 @@
+use std::path::PathBuf;
+
+use miniz_oxide::inflate::decompress_to_vec_zlib;
+
+use super::{
+    downsample,
+    png::{crc32, encode_png},
+    types::{
+        QPDFImageColorSpace, QPDFImageFormat, QPDFImageInfo, QPDFImageOptimizeParams,
+        QPDFImageReplaceParams,
+    },
+};
+use crate::qpdf::{
+    QPDF,
+    content::builder::QPDFContentBuilder,
+    filters::{
+        QPDFFilterRegistry,
+        types::{QPDFDecodeParms, QPDFFilter},
+    },
+    object::{QPDFObjectHandler, types::QPDFModifyObjectTypes},
+    page::QPDFPage,
+    read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+fn info(width: i64, height: i64, bpc: i64, color_space: QPDFImageColorSpace) -> QPDFImageInfo {
+    QPDFImageInfo {
+        name: None,
+        object_id: 0,
+        generation: 0,
+        width,
+        height,
+        bits_per_component: bpc,
+        color_space,
+        filters: Vec::new(),
+        decode: Vec::new(),
+        image_mask: false,
+        inline: false,
+    }
+}
+
+// Returns the chunk types and contents of a PNG file, checking each CRC
+fn chunks(png: &[u8]) -> Vec<(String, Vec<u8>)> {
+    assert_eq!(b"\x89PNG\r\n\x1a\n", &png[..8]);
+
+    let mut chunks = Vec::new();
+    let mut pos = 8;
+
+    while pos < png.len() {
+        let len = u32::from_be_bytes(png[pos..pos + 4].try_into().unwrap()) as usize;
+        let body = &png[pos + 4..pos + 8 + len];
+        let crc = u32::from_be_bytes(png[pos + 8 + len..pos + 12 + len].try_into().unwrap());
+        assert_eq!(crc32(body), crc);
+
+        let kind = String::from_utf8(body[..4].to_vec()).unwrap();
+        chunks.push((kind, body[4..].to_vec()));
+        pos += len + 12;
+    }
+
+    chunks
+}
+
+fn image_stream(page: &QPDFPage, entries: Vec<(&str, QPDFModifyObjectTypes)>) -> QPDFObjectHandler {
+    let stream = page.object().set(QPDFModifyObjectTypes::Stream);
+    let dict = stream.dict();
+
+    let subtype = [
+        ("/Type", QPDFModifyObjectTypes::Name("/XObject".to_string())),
+        (
+            "/Subtype",
+            QPDFModifyObjectTypes::Name("/Image".to_string()),
+        ),
+    ];
+
+    for (key, value) in subtype.into_iter().chain(entries) {
+        dict.dict_replace_key(key.to_string(), stream.set(value));
+    }
+
+    stream
+}
+
+#[test]
+fn crc_matches_reference() {
+    assert_eq!(0xae42_6082, crc32(b"IEND"));
+    assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+}
+
+#[test]
+fn encode_rgb_png() {
+    let samples = [255, 0, 0, 0, 255, 0, 0, 0, 255, 10, 20, 30];
+    let png = encode_png(&info(2, 2, 8, QPDFImageColorSpace::DeviceRGB), &samples).unwrap();
+
+    let chunks = chunks(&png);
+    let kinds: Vec<&str> = chunks.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(vec!["IHDR", "IDAT", "IEND"], kinds);
+    assert_eq!(vec![0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0], chunks[0].1);
+
+    let scanlines = decompress_to_vec_zlib(&chunks[1].1).unwrap();
+    assert_eq!(&samples[..6], &scanlines[1..7]);
+    assert_eq!(&samples[6..], &scanlines[8..]);
+}
+
+#[test]
+fn encode_converted_pngs() {
+    let cmyk = encode_png(
+        &info(1, 1, 8, QPDFImageColorSpace::DeviceCMYK),
+        &[0, 255, 255, 0],
+    )
+    .unwrap();
+    let scanlines = decompress_to_vec_zlib(&chunks(&cmyk)[1].1).unwrap();
+    assert_eq!(vec![0, 255, 0, 0], scanlines);
+
+    let indexed = QPDFImageColorSpace::Indexed {
+        base: Box::new(QPDFImageColorSpace::DeviceGray),
+        hival: 1,
+        lookup: vec![0, 200],
+    };
+    let png = encode_png(&info(8, 1, 1, indexed), &[0b1010_0000]).unwrap();
+    let palette = &chunks(&png)[1];
+    assert_eq!("PLTE", palette.0);
+    assert_eq!(vec![0, 0, 0, 200, 200, 200], palette.1);
+
+    let mut mask = info(8, 1, 1, QPDFImageColorSpace::DeviceGray);
+    mask.image_mask = true;
+    mask.decode = vec![1.0, 0.0];
+    let png = encode_png(&mask, &[0b1111_0000]).unwrap();
+    let scanlines = decompress_to_vec_zlib(&chunks(&png)[1].1).unwrap();
+    assert_eq!(vec![0, 0b0000_1111], scanlines);
+
+    assert!(encode_png(&info(1, 1, 8, QPDFImageColorSpace::Lab), &[0, 0, 0]).is_err());
+    assert!(encode_png(&info(2, 2, 8, QPDFImageColorSpace::DeviceGray), &[0]).is_err());
+}
+
+#[test]
+fn list_and_extract_page_images() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let registry = QPDFFilterRegistry::default();
+
+    let rgb = image_stream(
+        &page,
+        vec![
+            ("/Width", QPDFModifyObjectTypes::Integer(2)),
+            ("/Height", QPDFModifyObjectTypes::Integer(1)),
+            ("/BitsPerComponent", QPDFModifyObjectTypes::Integer(8)),
+            (
+                "/ColorSpace",
+                QPDFModifyObjectTypes::Name("/DeviceRGB".to_string()),
+            ),
+        ],
+    );
+    let chain = [(QPDFFilter::FlateDecode, QPDFDecodeParms::default())];
+    rgb.encode_stream_data(&[255, 0, 0, 0, 0, 255], &chain, &registry)
+        .unwrap();
+
+    let jpeg = image_stream(
+        &page,
+        vec![
+            ("/Width", QPDFModifyObjectTypes::Integer(1)),
+            ("/Height", QPDFModifyObjectTypes::Integer(1)),
+            ("/BitsPerComponent", QPDFModifyObjectTypes::Integer(8)),
+            (
+                "/ColorSpace",
+                QPDFModifyObjectTypes::Name("/DeviceGray".to_string()),
+            ),
+        ],
+    );
+    let dct = jpeg.set(QPDFModifyObjectTypes::Name("/DCTDecode".to_string()));
+    jpeg.replace_stream_data(b"\xff\xd8\xff\xd9", Some(dct), None);
+
+    // A form XObject that draws the JPEG, so the image is only reachable through the form
+    let form = page.object().set(QPDFModifyObjectTypes::Stream);
+    let form_resources = page.object().set(QPDFModifyObjectTypes::Dictionary);
+    let mut form_content = QPDFContentBuilder::new(form_resources.clone());
+    form_content.draw_xobject(&jpeg);
+
+    form.dict().dict_replace_key(
+        "/Subtype".to_string(),
+        form.set(QPDFModifyObjectTypes::Name("/Form".to_string())),
+    );
+    form.dict()
+        .dict_replace_key("/Resources".to_string(), form_resources);
+    form.replace_stream_data(form_content.data(), None, None);
+
+    let mut content = QPDFContentBuilder::for_page(&page);
+    content
+        .draw_xobject(&rgb)
+        .draw_xobject(&form)
+        .draw_xobject(&rgb)
+        .raw(b"BI /W 1 /H 1 /CS /G /BPC 8 ID \x80 EI");
+    page.append_content(content.data());
+
+    let images = page.images().unwrap();
+    assert_eq!(3, images.len());
+
+    let info = images[0].info();
+    assert_eq!(Some("/X1".to_string()), info.name);
+    assert_eq!(rgb.object_id(), info.object_id);
+    assert_eq!(
+        (2, 1, 8),
+        (info.width, info.height, info.bits_per_component)
+    );
+    assert_eq!(QPDFImageColorSpace::DeviceRGB, info.color_space);
+    assert_eq!(vec![QPDFFilter::FlateDecode], info.filters);
+    assert_eq!(vec![255, 0, 0, 0, 0, 255], images[0].raw_samples().unwrap());
+    assert_eq!(QPDFImageFormat::Png, images[0].extract().unwrap().0);
+
+    let (format, data) = images[1].extract().unwrap();
+    assert_eq!(QPDFImageFormat::Jpeg, format);
+    assert_eq!(b"\xff\xd8\xff\xd9".to_vec(), data);
+    assert!(images[1].raw_samples().is_err());
+
+    assert!(images[2].info().inline);
+    assert!(images[2].object().is_none());
+    assert_eq!(vec![0x80], images[2].raw_samples().unwrap());
+
+    assert_eq!(3, qpdf.images().unwrap()[0].len());
+}
+
+#[test]
+fn downsample_averages_blocks() {
+    let samples = [0, 10, 20, 30, 40, 50, 60, 70, 80];
+    let (out, width, height) = downsample(&samples, 3, 3, 1, 2);
+
+    assert_eq!((2, 2), (width, height));
+    assert_eq!(vec![20, 35, 65, 80], out);
+}
+
+#[test]
+fn replace_image_data() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let stream = image_stream(
+        &page,
+        vec![
+            ("/Width", QPDFModifyObjectTypes::Integer(2)),
+            ("/Height", QPDFModifyObjectTypes::Integer(2)),
+            ("/BitsPerComponent", QPDFModifyObjectTypes::Integer(8)),
+            (
+                "/ColorSpace",
+                QPDFModifyObjectTypes::Name("/DeviceRGB".to_string()),
+            ),
+        ],
+    );
+    stream.replace_stream_data(&[0; 12], None, None);
+
+    let mut content = QPDFContentBuilder::for_page(&page);
+    content.draw_xobject(&stream);
+    page.append_content(content.data());
+
+    let mut image = page.images().unwrap().remove(0);
+    let params = QPDFImageReplaceParams::default()
+        .with_width(1)
+        .with_height(1)
+        .with_bits_per_component(8)
+        .with_color_space(QPDFImageColorSpace::DeviceGray)
+        .with_filter(QPDFFilter::FlateDecode, QPDFDecodeParms::default());
+
+    assert!(
+        image
+            .replace(&[], QPDFImageReplaceParams::default().with_width(4))
+            .is_err()
+    );
+    image.replace(&[0x7f], params).unwrap();
+
+    let dict = stream.dict();
+    let width: i64 = dict.dict_get_key("/Width".to_string()).try_into().unwrap();
+    assert_eq!(1, width);
+    assert_eq!(
+        "/DeviceGray",
+        dict.dict_get_key("/ColorSpace".to_string()).name().unwrap()
+    );
+
+    let reread = page.images().unwrap().remove(0);
+    assert_eq!(QPDFImageColorSpace::DeviceGray, reread.info().color_space);
+    assert_eq!(vec![QPDFFilter::FlateDecode], reread.info().filters);
+    assert_eq!(vec![0x7f], reread.raw_samples().unwrap());
+}
+
+#[test]
+fn optimize_uncompressed_images() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let stream = image_stream(
+        &page,
+        vec![
+            ("/Width", QPDFModifyObjectTypes::Integer(400)),
+            ("/Height", QPDFModifyObjectTypes::Integer(300)),
+            ("/BitsPerComponent", QPDFModifyObjectTypes::Integer(8)),
+            (
+                "/ColorSpace",
+                QPDFModifyObjectTypes::Name("/DeviceGray".to_string()),
+            ),
+        ],
+    );
+    let samples: Vec<u8> = (0..400 * 300).map(|i| (i % 400 / 2) as u8).collect();
+    stream.replace_stream_data(&samples, None, None);
+
+    let mut content = QPDFContentBuilder::for_page(&page);
+    content.draw_xobject(&stream);
+    page.append_content(content.data());
+
+    let params = QPDFImageOptimizeParams::default()
+        .with_threshold(1024)
+        .with_max_dimension(100);
+    let results = qpdf.optimize_images(params).unwrap();
+
+    assert_eq!(1, results.len());
+    assert_eq!(stream.object_id(), results[0].object_id);
+    assert_eq!(samples.len(), results[0].before);
+    assert!(results[0].after < results[0].before);
+
+    let image = page.images().unwrap().remove(0);
+    assert_eq!((100, 75), (image.info().width, image.info().height));
+    assert_eq!(100 * 75, image.raw_samples().unwrap().len());
+
+    // Already optimized images are left alone
+    let params = QPDFImageOptimizeParams::default().with_threshold(1024);
+    assert!(qpdf.optimize_images(params).unwrap().is_empty());
+}
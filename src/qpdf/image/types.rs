@@ -0,0 +1,177 @@
+use crate::qpdf::{
+    filters::types::{QPDFDecodeParms, QPDFFilter},
+    object::types::{Generation, ObjectId},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QPDFImageColorSpace {
+    DeviceGray,
+    DeviceRGB,
+    DeviceCMYK,
+    CalGray,
+    CalRGB,
+    Lab,
+    ICCBased(i64),
+    Indexed {
+        base: Box<QPDFImageColorSpace>,
+        hival: i64,
+        lookup: Vec<u8>,
+    },
+    Separation,
+    DeviceN(i64),
+    Other(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QPDFImageFormat {
+    Raw,
+    Jpeg,
+    Jpeg2000,
+    Png,
+}
+
+#[derive(Debug, Clone)]
+pub struct QPDFImageInfo {
+    pub name: Option<String>,
+    pub object_id: ObjectId,
+    pub generation: Generation,
+    pub width: i64,
+    pub height: i64,
+    pub bits_per_component: i64,
+    pub color_space: QPDFImageColorSpace,
+    pub filters: Vec<QPDFFilter>,
+    pub decode: Vec<f64>,
+    pub image_mask: bool,
+    pub inline: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct QPDFImageReplaceParams {
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+    pub bits_per_component: Option<i64>,
+    pub color_space: Option<QPDFImageColorSpace>,
+    pub filters: Vec<(QPDFFilter, QPDFDecodeParms)>,
+    pub pre_encoded: bool,
+}
+
+#[derive(Debug)]
+pub struct QPDFImageOptimizeParams {
+    pub threshold: usize,
+    pub max_dimension: Option<i64>,
+    pub level: u8,
+    pub predictor: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFImageOptimizeResult {
+    pub object_id: ObjectId,
+    pub generation: Generation,
+    pub before: usize,
+    pub after: usize,
+}
+
+impl QPDFImageColorSpace {
+    pub fn components(&self) -> i64 {
+        match self {
+            QPDFImageColorSpace::DeviceGray
+            | QPDFImageColorSpace::CalGray
+            | QPDFImageColorSpace::Indexed { .. }
+            | QPDFImageColorSpace::Separation => 1,
+            QPDFImageColorSpace::DeviceRGB
+            | QPDFImageColorSpace::CalRGB
+            | QPDFImageColorSpace::Lab => 3,
+            QPDFImageColorSpace::DeviceCMYK => 4,
+            QPDFImageColorSpace::ICCBased(n) | QPDFImageColorSpace::DeviceN(n) => *n,
+            QPDFImageColorSpace::Other(_) => 1,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Self {
+        match name.trim_start_matches('/') {
+            "DeviceGray" | "G" => QPDFImageColorSpace::DeviceGray,
+            "DeviceRGB" | "RGB" => QPDFImageColorSpace::DeviceRGB,
+            "DeviceCMYK" | "CMYK" => QPDFImageColorSpace::DeviceCMYK,
+            "CalGray" => QPDFImageColorSpace::CalGray,
+            "CalRGB" => QPDFImageColorSpace::CalRGB,
+            "Lab" => QPDFImageColorSpace::Lab,
+            other => QPDFImageColorSpace::Other(format!("/{other}")),
+        }
+    }
+}
+
+impl QPDFImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            QPDFImageFormat::Raw => "raw",
+            QPDFImageFormat::Jpeg => "jpg",
+            QPDFImageFormat::Jpeg2000 => "jp2",
+            QPDFImageFormat::Png => "png",
+        }
+    }
+}
+
+impl QPDFImageReplaceParams {
+    pub fn with_width(mut self, width: i64) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_height(mut self, height: i64) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    pub fn with_bits_per_component(mut self, bits_per_component: i64) -> Self {
+        self.bits_per_component = Some(bits_per_component);
+        self
+    }
+
+    pub fn with_color_space(mut self, color_space: QPDFImageColorSpace) -> Self {
+        self.color_space = Some(color_space);
+        self
+    }
+
+    pub fn with_filter(mut self, filter: QPDFFilter, parms: QPDFDecodeParms) -> Self {
+        self.filters.push((filter, parms));
+        self
+    }
+
+    pub fn with_pre_encoded(mut self, pre_encoded: bool) -> Self {
+        self.pre_encoded = pre_encoded;
+        self
+    }
+}
+
+impl Default for QPDFImageOptimizeParams {
+    fn default() -> Self {
+        Self {
+            threshold: 64 * 1024,
+            max_dimension: None,
+            level: 9,
+            predictor: true,
+        }
+    }
+}
+
+impl QPDFImageOptimizeParams {
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn with_max_dimension(mut self, max_dimension: i64) -> Self {
+        self.max_dimension = Some(max_dimension);
+        self
+    }
+
+    pub fn with_level(mut self, level: u8) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn with_predictor(mut self, predictor: bool) -> Self {
+        self.predictor = predictor;
+        self
+    }
+}
@@ -0,0 +1,613 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use types::{
+    QPDFFillRule, QPDFGraphicsState, QPDFPath, QPDFPathPaint, QPDFPathSegment, QPDFTextGlyph,
+};
+
+use super::{
+    QPDFErrors,
+    content::{
+        QPDFContentParser,
+        types::{QPDFContentOperation, QPDFContentValue},
+    },
+    font::decoder::QPDFFontDecoder,
+    geometry::{Matrix, Rect},
+    image::{QPDFImage, color_space_from_object, types::QPDFImageColorSpace},
+    object::{
+        QPDFObjectHandler,
+        types::{Generation, ObjectId, QPDFIsObjectType, QPDFModifyObjectTypes},
+    },
+    page::QPDFPage,
+    write::QPDFWriteDecodeLevel,
+};
+
+// Limits how deeply nested form XObjects are followed
+const MAX_FORM_DEPTH: usize = 32;
+
+pub trait QPDFContentHandler {
+    fn path(&mut self, _path: &QPDFPath, _paint: QPDFPathPaint, _state: &QPDFGraphicsState) {}
+
+    fn text(&mut self, _glyphs: &[QPDFTextGlyph], _state: &QPDFGraphicsState) {}
+
+    fn image(&mut self, _image: &QPDFImage, _state: &QPDFGraphicsState) {}
+
+    fn shading(&mut self, _shading: &QPDFObjectHandler, _state: &QPDFGraphicsState) {}
+
+    fn begin_marked_content(&mut self, _tag: &str, _properties: Option<&QPDFContentValue>) {}
+
+    fn end_marked_content(&mut self) {}
+
+    fn marked_point(&mut self, _tag: &str, _properties: Option<&QPDFContentValue>) {}
+}
+
+#[derive(Default)]
+pub struct QPDFContentInterpreter {
+    fonts: HashMap<(ObjectId, Generation), Rc<QPDFFontDecoder>>,
+    forms: HashSet<(ObjectId, Generation)>,
+    state: QPDFGraphicsState,
+    stack: Vec<QPDFGraphicsState>,
+    tm: Matrix,
+    tlm: Matrix,
+    path: QPDFPath,
+    current: (f64, f64),
+    clip_pending: bool,
+}
+
+// Construction
+impl QPDFContentInterpreter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_state(mut self, state: QPDFGraphicsState) -> Self {
+        self.state = state;
+        self
+    }
+
+    pub fn state(&self) -> &QPDFGraphicsState {
+        &self.state
+    }
+}
+
+// Running
+impl QPDFContentInterpreter {
+    pub fn run(
+        &mut self,
+        data: &[u8],
+        resources: &QPDFObjectHandler,
+        handler: &mut impl QPDFContentHandler,
+    ) -> Result<(), QPDFErrors> {
+        self.run_at(data, resources, handler, 0)
+    }
+
+    pub fn run_page(
+        &mut self,
+        page: &QPDFPage,
+        handler: &mut impl QPDFContentHandler,
+    ) -> Result<(), QPDFErrors> {
+        let resources = page
+            .inherited_key("/Resources")
+            .unwrap_or_else(|| page.object().set(QPDFModifyObjectTypes::Dictionary));
+
+        self.run(&page.content_data()?, &resources, handler)
+    }
+
+    fn run_at(
+        &mut self,
+        data: &[u8],
+        resources: &QPDFObjectHandler,
+        handler: &mut impl QPDFContentHandler,
+        depth: usize,
+    ) -> Result<(), QPDFErrors> {
+        for operation in QPDFContentParser::new(data) {
+            match operation? {
+                QPDFContentOperation::InlineImage(_, _) if self.state.clipped_out => (),
+                QPDFContentOperation::InlineImage(dict, data) => {
+                    handler.image(&QPDFImage::from_inline(&dict, data, resources), &self.state);
+                }
+                QPDFContentOperation::Operator(op, operands) => {
+                    self.operator(&op, &operands, resources, handler, depth)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn operator(
+        &mut self,
+        op: &str,
+        operands: &[QPDFContentValue],
+        resources: &QPDFObjectHandler,
+        handler: &mut impl QPDFContentHandler,
+        depth: usize,
+    ) -> Result<(), QPDFErrors> {
+        let n = numbers(operands);
+        let name = operands.first().and_then(|v| v.as_name());
+
+        match (op, n.as_slice()) {
+            // Graphics state
+            ("q", _) => self.stack.push(self.state.clone()),
+            ("Q", _) => {
+                if let Some(state) = self.stack.pop() {
+                    self.state = state;
+                }
+            }
+            ("cm", [a, b, c, d, e, f]) => {
+                let m = Matrix::new(*a, *b, *c, *d, *e, *f);
+                self.state.ctm = m.multiply(&self.state.ctm);
+            }
+            ("w", [v]) => self.state.line_width = *v,
+            ("J", [v]) => self.state.line_cap = *v as i64,
+            ("j", [v]) => self.state.line_join = *v as i64,
+            ("M", [v]) => self.state.miter_limit = *v,
+            ("d", [phase]) => {
+                if let Some(QPDFContentValue::Array(items)) = operands.first() {
+                    self.state.dash_array = numbers(items);
+                    self.state.dash_phase = *phase;
+                }
+            }
+            ("gs", _) => {
+                if let Some(name) = name {
+                    let ext_gstate = resources
+                        .dict_get_key("/ExtGState".to_string())
+                        .dict_get_key(name.to_string());
+                    self.apply_ext_gstate(&ext_gstate);
+                }
+            }
+
+            // Colour
+            ("CS", _) | ("cs", _) => {
+                let space = self.color_space(name.unwrap_or_default(), resources);
+                let color = initial_color(&space);
+
+                match op {
+                    "CS" => {
+                        (self.state.stroke_color_space, self.state.stroke_color) = (space, color)
+                    }
+                    _ => (self.state.fill_color_space, self.state.fill_color) = (space, color),
+                }
+            }
+            ("SC", _) | ("SCN", _) => self.state.stroke_color = n,
+            ("sc", _) | ("scn", _) => self.state.fill_color = n,
+            ("G", [_]) | ("RG", [_, _, _]) | ("K", [_, _, _, _]) => {
+                self.state.stroke_color_space = device_space(n.len());
+                self.state.stroke_color = n;
+            }
+            ("g", [_]) | ("rg", [_, _, _]) | ("k", [_, _, _, _]) => {
+                self.state.fill_color_space = device_space(n.len());
+                self.state.fill_color = n;
+            }
+
+            // Path construction
+            ("m", [x, y]) => {
+                self.path.segments.push(QPDFPathSegment::MoveTo(*x, *y));
+                self.current = (*x, *y);
+            }
+            ("l", [x, y]) => {
+                self.path.segments.push(QPDFPathSegment::LineTo(*x, *y));
+                self.current = (*x, *y);
+            }
+            ("c", [x1, y1, x2, y2, x3, y3]) => self.curve_to(*x1, *y1, *x2, *y2, *x3, *y3),
+            ("v", [x2, y2, x3, y3]) => {
+                let (x1, y1) = self.current;
+                self.curve_to(x1, y1, *x2, *y2, *x3, *y3);
+            }
+            ("y", [x1, y1, x3, y3]) => self.curve_to(*x1, *y1, *x3, *y3, *x3, *y3),
+            ("h", _) => self.path.segments.push(QPDFPathSegment::Close),
+            ("re", [x, y, w, h]) => {
+                self.path.segments.extend([
+                    QPDFPathSegment::MoveTo(*x, *y),
+                    QPDFPathSegment::LineTo(x + w, *y),
+                    QPDFPathSegment::LineTo(x + w, y + h),
+                    QPDFPathSegment::LineTo(*x, y + h),
+                    QPDFPathSegment::Close,
+                ]);
+                self.current = (*x, *y);
+            }
+
+            // Path painting and clipping
+            ("W", _) | ("W*", _) => self.clip_pending = true,
+            ("S", _) => self.paint(handler, true, None),
+            ("s", _) => {
+                self.path.segments.push(QPDFPathSegment::Close);
+                self.paint(handler, true, None);
+            }
+            ("f", _) | ("F", _) => self.paint(handler, false, Some(QPDFFillRule::NonZero)),
+            ("f*", _) => self.paint(handler, false, Some(QPDFFillRule::EvenOdd)),
+            ("B", _) => self.paint(handler, true, Some(QPDFFillRule::NonZero)),
+            ("B*", _) => self.paint(handler, true, Some(QPDFFillRule::EvenOdd)),
+            ("b", _) | ("b*", _) => {
+                let rule = match op {
+                    "b" => QPDFFillRule::NonZero,
+                    _ => QPDFFillRule::EvenOdd,
+                };
+
+                self.path.segments.push(QPDFPathSegment::Close);
+                self.paint(handler, true, Some(rule));
+            }
+            ("n", _) => self.paint(handler, false, None),
+
+            // Text
+            ("BT", _) => {
+                self.tm = Matrix::identity();
+                self.tlm = Matrix::identity();
+            }
+            ("Tc", [v]) => self.state.text.char_spacing = *v,
+            ("Tw", [v]) => self.state.text.word_spacing = *v,
+            ("Tz", [v]) => self.state.text.horizontal_scale = v / 100.0,
+            ("TL", [v]) => self.state.text.leading = *v,
+            ("Ts", [v]) => self.state.text.rise = *v,
+            ("Tr", [v]) => self.state.text.render_mode = *v as i64,
+            ("Tf", [size]) => {
+                let font = resources
+                    .dict_get_key("/Font".to_string())
+                    .dict_get_key(name.unwrap_or_default().to_string());
+
+                self.state.text.font = self.font(&font);
+                self.state.text.font_name = name.map(String::from);
+                self.state.text.size = *size;
+            }
+            ("Td", [tx, ty]) => self.next_line(*tx, *ty),
+            ("TD", [tx, ty]) => {
+                self.state.text.leading = -ty;
+                self.next_line(*tx, *ty);
+            }
+            ("Tm", [a, b, c, d, e, f]) => {
+                self.tlm = Matrix::new(*a, *b, *c, *d, *e, *f);
+                self.tm = self.tlm;
+            }
+            ("T*", _) => self.next_line(0.0, -self.state.text.leading),
+            ("Tj", _) => self.show_operand(operands.first(), handler),
+            ("'", _) => {
+                self.next_line(0.0, -self.state.text.leading);
+                self.show_operand(operands.first(), handler);
+            }
+            ("\"", [aw, ac]) => {
+                self.state.text.word_spacing = *aw;
+                self.state.text.char_spacing = *ac;
+                self.next_line(0.0, -self.state.text.leading);
+                self.show_operand(operands.get(2), handler);
+            }
+            ("TJ", _) => {
+                let Some(QPDFContentValue::Array(items)) = operands.first() else {
+                    return Ok(());
+                };
+
+                for item in items {
+                    match item {
+                        QPDFContentValue::String(bytes) => self.show(bytes, handler),
+                        item => {
+                            let ts = &self.state.text;
+                            let adjust = item.as_number().unwrap_or(0.0);
+                            let tx = -adjust / 1000.0 * ts.size * ts.horizontal_scale;
+                            self.tm = Matrix::translate(tx, 0.0).multiply(&self.tm);
+                        }
+                    }
+                }
+            }
+
+            // External objects and shadings
+            ("Do", _) => {
+                if let Some(name) = name {
+                    self.draw_xobject(resources, name, handler, depth)?;
+                }
+            }
+            ("sh", _) => {
+                let shading = resources
+                    .dict_get_key("/Shading".to_string())
+                    .dict_get_key(name.unwrap_or_default().to_string());
+
+                if !shading.is(QPDFIsObjectType::Null) && !self.state.clipped_out {
+                    handler.shading(&shading, &self.state);
+                }
+            }
+
+            // Marked content
+            ("BMC", _) => handler.begin_marked_content(name.unwrap_or_default(), None),
+            ("BDC", _) => {
+                handler.begin_marked_content(name.unwrap_or_default(), operands.get(1));
+            }
+            ("EMC", _) => handler.end_marked_content(),
+            ("MP", _) => handler.marked_point(name.unwrap_or_default(), None),
+            ("DP", _) => handler.marked_point(name.unwrap_or_default(), operands.get(1)),
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+// Graphics State
+impl QPDFContentInterpreter {
+    fn apply_ext_gstate(&mut self, ext_gstate: &QPDFObjectHandler) {
+        if ext_gstate.dict_has_key("/Font".to_string()) {
+            let value = ext_gstate.dict_get_key("/Font".to_string());
+
+            self.state.text.font = self.font(&value.array_get_at(0));
+            self.state.text.font_name = None;
+            self.state.text.size = value
+                .array_get_at(1)
+                .try_into()
+                .unwrap_or(self.state.text.size);
+        }
+
+        let state = &mut self.state;
+
+        for key in ext_gstate.dict_keys() {
+            let value = ext_gstate.dict_get_key(key.clone());
+            let number = || TryInto::<f64>::try_into(value.clone()).ok();
+
+            match key.as_str() {
+                "/LW" => state.line_width = number().unwrap_or(state.line_width),
+                "/LC" => state.line_cap = number().map_or(state.line_cap, |v| v as i64),
+                "/LJ" => state.line_join = number().map_or(state.line_join, |v| v as i64),
+                "/ML" => state.miter_limit = number().unwrap_or(state.miter_limit),
+                "/CA" => state.stroke_alpha = number().unwrap_or(state.stroke_alpha),
+                "/ca" => state.fill_alpha = number().unwrap_or(state.fill_alpha),
+                "/D" => {
+                    state.dash_array = object_numbers(&value.array_get_at(0));
+                    state.dash_phase = value.array_get_at(1).try_into().unwrap_or(0.0);
+                }
+                "/BM" => {
+                    // Arrays list blend modes in order of preference
+                    let mode = match value.is(QPDFIsObjectType::Array) {
+                        true => value.array_get_at(0).name(),
+                        _ => value.name(),
+                    };
+
+                    if let Ok(mode) = mode {
+                        state.blend_mode = mode;
+                    }
+                }
+                "/SMask" => state.soft_mask = value.is(QPDFIsObjectType::Dictionary),
+                _ => (),
+            }
+        }
+    }
+
+    fn color_space(&self, name: &str, resources: &QPDFObjectHandler) -> QPDFImageColorSpace {
+        let space = QPDFImageColorSpace::from_name(name);
+
+        if !matches!(space, QPDFImageColorSpace::Other(_)) {
+            return space;
+        }
+
+        let named = resources
+            .dict_get_key("/ColorSpace".to_string())
+            .dict_get_key(name.to_string());
+
+        match named.is(QPDFIsObjectType::Null) {
+            true => space,
+            _ => color_space_from_object(&named, Some(resources), 0),
+        }
+    }
+
+    fn font(&mut self, font: &QPDFObjectHandler) -> Option<Rc<QPDFFontDecoder>> {
+        if !font.is(QPDFIsObjectType::Dictionary) {
+            return None;
+        }
+
+        // Direct font dictionaries have no id to cache them under
+        let id = (font.object_id(), font.generation());
+        if id.0 == 0 {
+            return Some(Rc::new(QPDFFontDecoder::from_object(font)));
+        }
+
+        let decoder = self
+            .fonts
+            .entry(id)
+            .or_insert_with(|| Rc::new(QPDFFontDecoder::from_object(font)));
+
+        Some(decoder.clone())
+    }
+}
+
+// Paths
+impl QPDFContentInterpreter {
+    fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x3: f64, y3: f64) {
+        let segment = QPDFPathSegment::CurveTo(x1, y1, x2, y2, x3, y3);
+        self.path.segments.push(segment);
+        self.current = (x3, y3);
+    }
+
+    fn paint(
+        &mut self,
+        handler: &mut impl QPDFContentHandler,
+        stroke: bool,
+        fill: Option<QPDFFillRule>,
+    ) {
+        let path = std::mem::take(&mut self.path);
+
+        if (stroke || fill.is_some()) && !path.is_empty() && !self.state.clipped_out {
+            handler.path(&path, QPDFPathPaint { stroke, fill }, &self.state);
+        }
+
+        // A clipping path takes effect after the path is painted
+        if std::mem::take(&mut self.clip_pending)
+            && let Some(bbox) = path.bbox(&self.state.ctm)
+        {
+            self.clip(bbox);
+        }
+    }
+
+    fn clip(&mut self, bbox: Rect) {
+        let clip = match self.state.clip {
+            Some(clip) => clip.intersect(&bbox),
+            None => Some(bbox),
+        };
+
+        match clip {
+            Some(clip) => self.state.clip = Some(clip),
+            None => self.state.clipped_out = true,
+        }
+    }
+}
+
+// Text
+impl QPDFContentInterpreter {
+    fn next_line(&mut self, tx: f64, ty: f64) {
+        self.tlm = Matrix::translate(tx, ty).multiply(&self.tlm);
+        self.tm = self.tlm;
+    }
+
+    fn show_operand(
+        &mut self,
+        operand: Option<&QPDFContentValue>,
+        handler: &mut impl QPDFContentHandler,
+    ) {
+        if let Some(bytes) = operand.and_then(|v| v.as_bytes()) {
+            self.show(bytes, handler);
+        }
+    }
+
+    fn show(&mut self, bytes: &[u8], handler: &mut impl QPDFContentHandler) {
+        let Some(font) = self.state.text.font.clone() else {
+            return;
+        };
+
+        let ts = &self.state.text;
+        let mut glyphs = Vec::new();
+
+        for ch in font.decode(bytes) {
+            let trm = Matrix::new(
+                ts.size * ts.horizontal_scale,
+                0.0,
+                0.0,
+                ts.size,
+                0.0,
+                ts.rise,
+            )
+            .multiply(&self.tm)
+            .multiply(&self.state.ctm);
+
+            let (x, y) = trm.apply(0.0, 0.0);
+            let (end_x, _) = trm.apply(ch.width, 0.0);
+
+            // The glyph box spans the advance width and the font's ascent and descent
+            let bbox = Rect::new(0.0, font.descent(), ch.width, font.ascent()).transform(&trm);
+
+            let spacing = ts.char_spacing + if ch.is_space { ts.word_spacing } else { 0.0 };
+            let tx = (ch.width * ts.size + spacing) * ts.horizontal_scale;
+            self.tm = Matrix::translate(tx, 0.0).multiply(&self.tm);
+
+            glyphs.push(QPDFTextGlyph {
+                code: ch.code,
+                text: ch.text,
+                x,
+                y,
+                end_x,
+                size: trm.c.hypot(trm.d),
+                bbox,
+            });
+        }
+
+        // Glyphs are still laid out so that the text position keeps advancing
+        if !self.state.clipped_out {
+            handler.text(&glyphs, &self.state);
+        }
+    }
+}
+
+// External Objects
+impl QPDFContentInterpreter {
+    fn draw_xobject(
+        &mut self,
+        resources: &QPDFObjectHandler,
+        name: &str,
+        handler: &mut impl QPDFContentHandler,
+        depth: usize,
+    ) -> Result<(), QPDFErrors> {
+        let xobject = resources
+            .dict_get_key("/XObject".to_string())
+            .dict_get_key(name.to_string());
+
+        if !xobject.is(QPDFIsObjectType::Stream) {
+            return Ok(());
+        }
+
+        let dict = xobject.dict();
+        let subtype = dict.dict_get_key("/Subtype".to_string());
+
+        if subtype.is(QPDFIsObjectType::NameEquals("/Image".to_string())) {
+            if !self.state.clipped_out {
+                let image = QPDFImage::from_xobject(Some(name.to_string()), xobject)?;
+                handler.image(&image, &self.state);
+            }
+            return Ok(());
+        }
+
+        // Forms currently being drawn are skipped, so cyclic forms terminate
+        let id = (xobject.object_id(), xobject.generation());
+        if !subtype.is(QPDFIsObjectType::NameEquals("/Form".to_string()))
+            || depth >= MAX_FORM_DEPTH
+            || !self.forms.insert(id)
+        {
+            return Ok(());
+        }
+
+        let form_resources = dict.dict_get_key("/Resources".to_string());
+        let form_resources = match form_resources.is(QPDFIsObjectType::Dictionary) {
+            true => form_resources,
+            _ => resources.clone(),
+        };
+
+        let matrix: Matrix = dict
+            .dict_get_key("/Matrix".to_string())
+            .try_into()
+            .unwrap_or_default();
+
+        let saved = (self.state.clone(), self.stack.len(), self.tm, self.tlm);
+        self.state.ctm = matrix.multiply(&self.state.ctm);
+
+        let bbox: Option<Rect> = dict.dict_get_key("/BBox".to_string()).try_into().ok();
+        if let Some(bbox) = bbox {
+            self.clip(bbox.transform(&self.state.ctm));
+        }
+
+        let result = xobject
+            .stream_data(QPDFWriteDecodeLevel::Generalized)
+            .and_then(|content| self.run_at(&content.data, &form_resources, handler, depth + 1));
+
+        // Unbalanced q operators inside the form must not leak into the caller
+        (self.state, self.tm, self.tlm) = (saved.0, saved.2, saved.3);
+        self.stack.truncate(saved.1);
+        self.forms.remove(&id);
+
+        result
+    }
+}
+
+fn numbers(operands: &[QPDFContentValue]) -> Vec<f64> {
+    operands.iter().filter_map(|v| v.as_number()).collect()
+}
+
+fn object_numbers(array: &QPDFObjectHandler) -> Vec<f64> {
+    (0..array.array_len())
+        .filter_map(|at| array.array_get_at(at).try_into().ok())
+        .collect()
+}
+
+fn device_space(components: usize) -> QPDFImageColorSpace {
+    match components {
+        3 => QPDFImageColorSpace::DeviceRGB,
+        4 => QPDFImageColorSpace::DeviceCMYK,
+        _ => QPDFImageColorSpace::DeviceGray,
+    }
+}
+
+fn initial_color(space: &QPDFImageColorSpace) -> Vec<f64> {
+    match space {
+        QPDFImageColorSpace::DeviceCMYK => vec![0.0, 0.0, 0.0, 1.0],
+        QPDFImageColorSpace::Other(name) if name == "/Pattern" => Vec::new(),
+        space => vec![0.0; space.components().max(0) as usize],
+    }
+}
+
+pub mod types;
+
+#[cfg(test)]
+mod tests;
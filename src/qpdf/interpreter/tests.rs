@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+
+use super::{
+    QPDFContentHandler, QPDFContentInterpreter,
+    types::{
+        QPDFFillRule, QPDFGraphicsState, QPDFPath, QPDFPathPaint, QPDFPathSegment, QPDFTextGlyph,
+    },
+};
+use crate::qpdf::{
+    QPDF,
+    content::types::QPDFContentValue,
+    geometry::{Matrix, Rect},
+    image::types::QPDFImageColorSpace,
+    page::QPDFPage,
+    read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+#[derive(Default)]
+struct Recorder {
+    paths: Vec<(QPDFPath, QPDFPathPaint, QPDFGraphicsState)>,
+    text: Vec<(String, QPDFGraphicsState)>,
+    marked: Vec<String>,
+    depth: usize,
+}
+
+impl QPDFContentHandler for Recorder {
+    fn path(&mut self, path: &QPDFPath, paint: QPDFPathPaint, state: &QPDFGraphicsState) {
+        self.paths.push((path.clone(), paint, state.clone()));
+    }
+
+    fn text(&mut self, glyphs: &[QPDFTextGlyph], state: &QPDFGraphicsState) {
+        let text = glyphs.iter().map(|g| g.text.as_str()).collect();
+        self.text.push((text, state.clone()));
+    }
+
+    fn begin_marked_content(&mut self, tag: &str, _properties: Option<&QPDFContentValue>) {
+        self.marked.push(tag.to_string());
+        self.depth += 1;
+    }
+
+    fn end_marked_content(&mut self) {
+        self.depth -= 1;
+    }
+}
+
+#[test]
+fn path_bbox_is_transformed() {
+    let path = QPDFPath {
+        segments: vec![
+            QPDFPathSegment::MoveTo(0.0, 0.0),
+            QPDFPathSegment::CurveTo(10.0, 20.0, 30.0, 20.0, 40.0, 0.0),
+            QPDFPathSegment::Close,
+        ],
+    };
+
+    assert_eq!(
+        Some(Rect::new(5.0, 5.0, 85.0, 45.0)),
+        path.bbox(&Matrix::new(2.0, 0.0, 0.0, 2.0, 5.0, 5.0))
+    );
+    assert_eq!(None, QPDFPath::default().bbox(&Matrix::identity()));
+}
+
+#[test]
+fn track_graphics_state_for_paths() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    page.replace_content(
+        b"q 2 0 0 2 10 10 cm 1 0 0 rg 3 w 0 0 10 10 re f Q\n\
+          0 0 50 50 re W n 0 0 1 0 K [2 1] 0 d 5 5 m 100 100 l S",
+    );
+
+    let mut recorder = Recorder::default();
+    let mut interpreter = QPDFContentInterpreter::new();
+    interpreter.run_page(&page, &mut recorder).unwrap();
+
+    assert_eq!(2, recorder.paths.len());
+
+    let (path, paint, state) = &recorder.paths[0];
+    assert_eq!(5, path.segments.len());
+    assert_eq!(Some(QPDFFillRule::NonZero), paint.fill);
+    assert!(!paint.stroke);
+    assert_eq!(Matrix::new(2.0, 0.0, 0.0, 2.0, 10.0, 10.0), state.ctm);
+    assert_eq!(QPDFImageColorSpace::DeviceRGB, state.fill_color_space);
+    assert_eq!(vec![1.0, 0.0, 0.0], state.fill_color);
+    assert_eq!(3.0, state.line_width);
+    assert_eq!(None, state.clip);
+
+    let (_, paint, state) = &recorder.paths[1];
+    assert!(paint.stroke);
+    assert_eq!(Matrix::identity(), state.ctm);
+    assert_eq!(1.0, state.line_width);
+    assert_eq!(QPDFImageColorSpace::DeviceCMYK, state.stroke_color_space);
+    assert_eq!(vec![2.0, 1.0], state.dash_array);
+    assert_eq!(Some(Rect::new(0.0, 0.0, 50.0, 50.0)), state.clip);
+    assert_eq!(state.clip, interpreter.state().clip);
+}
+
+#[test]
+fn disjoint_clips_hide_painting() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    page.replace_content(
+        b"q 100 100 10 10 re W n 200 200 10 10 re W n 0 0 300 300 re f Q\n\
+          300 300 10 10 re f",
+    );
+
+    let mut recorder = Recorder::default();
+    let mut interpreter = QPDFContentInterpreter::new();
+    interpreter.run_page(&page, &mut recorder).unwrap();
+
+    // Only the path painted after the restore is reported, without a clip
+    assert_eq!(1, recorder.paths.len());
+    let (_, _, state) = &recorder.paths[0];
+    assert!(!state.clipped_out);
+    assert_eq!(None, state.clip);
+}
+
+#[test]
+fn report_text_and_marked_content() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let mut recorder = Recorder::default();
+    QPDFContentInterpreter::new()
+        .run_page(&page, &mut recorder)
+        .unwrap();
+
+    assert_eq!("THIS", recorder.text[0].0);
+    assert_eq!(12, recorder.text.len());
+    assert_eq!(vec!["/P", "/Span"], recorder.marked[..2].to_vec());
+    assert_eq!(6, recorder.marked.len());
+    assert_eq!(0, recorder.depth);
+
+    let state = &recorder.text[0].1;
+    assert_eq!(Some("/TT1".to_string()), state.text.font_name);
+    assert_eq!(1.0, state.text.size);
+    assert_eq!(vec![0.7082242, 0.09132584, 0.0], state.fill_color);
+    assert_eq!(
+        Some(Rect::new(51.62691, 56.69292, 543.65311, 790.26312)),
+        state.clip.map(|r| Rect::new(
+            (r.llx * 1e5).round() / 1e5,
+            (r.lly * 1e5).round() / 1e5,
+            (r.urx * 1e5).round() / 1e5,
+            (r.ury * 1e5).round() / 1e5
+        ))
+    );
+}
@@ -0,0 +1,141 @@
+use std::rc::Rc;
+
+use crate::qpdf::{
+    font::decoder::QPDFFontDecoder,
+    geometry::{Matrix, Rect},
+    image::types::QPDFImageColorSpace,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QPDFPathSegment {
+    MoveTo(f64, f64),
+    LineTo(f64, f64),
+    CurveTo(f64, f64, f64, f64, f64, f64),
+    Close,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QPDFFillRule {
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QPDFPathPaint {
+    pub stroke: bool,
+    pub fill: Option<QPDFFillRule>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QPDFPath {
+    pub segments: Vec<QPDFPathSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFTextGlyph {
+    pub code: u32,
+    pub text: String,
+    pub x: f64,
+    pub y: f64,
+    pub end_x: f64,
+    pub size: f64,
+    pub bbox: Rect,
+}
+
+#[derive(Debug, Clone)]
+pub struct QPDFTextState {
+    pub char_spacing: f64,
+    pub word_spacing: f64,
+    pub horizontal_scale: f64,
+    pub leading: f64,
+    pub rise: f64,
+    pub render_mode: i64,
+    pub font: Option<Rc<QPDFFontDecoder>>,
+    pub font_name: Option<String>,
+    pub size: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct QPDFGraphicsState {
+    pub ctm: Matrix,
+    // The clipping path is tracked as its bounding box in default user space
+    pub clip: Option<Rect>,
+    // Set once clipping leaves nothing visible, nothing is reported to handlers from then on
+    pub clipped_out: bool,
+    pub fill_color_space: QPDFImageColorSpace,
+    pub stroke_color_space: QPDFImageColorSpace,
+    pub fill_color: Vec<f64>,
+    pub stroke_color: Vec<f64>,
+    pub line_width: f64,
+    pub line_cap: i64,
+    pub line_join: i64,
+    pub miter_limit: f64,
+    pub dash_array: Vec<f64>,
+    pub dash_phase: f64,
+    pub fill_alpha: f64,
+    pub stroke_alpha: f64,
+    pub blend_mode: String,
+    pub soft_mask: bool,
+    pub text: QPDFTextState,
+}
+
+impl Default for QPDFTextState {
+    fn default() -> Self {
+        Self {
+            char_spacing: 0.0,
+            word_spacing: 0.0,
+            horizontal_scale: 1.0,
+            leading: 0.0,
+            rise: 0.0,
+            render_mode: 0,
+            font: None,
+            font_name: None,
+            size: 0.0,
+        }
+    }
+}
+
+impl Default for QPDFGraphicsState {
+    fn default() -> Self {
+        Self {
+            ctm: Matrix::identity(),
+            clip: None,
+            clipped_out: false,
+            fill_color_space: QPDFImageColorSpace::DeviceGray,
+            stroke_color_space: QPDFImageColorSpace::DeviceGray,
+            fill_color: vec![0.0],
+            stroke_color: vec![0.0],
+            line_width: 1.0,
+            line_cap: 0,
+            line_join: 0,
+            miter_limit: 10.0,
+            dash_array: Vec::new(),
+            dash_phase: 0.0,
+            fill_alpha: 1.0,
+            stroke_alpha: 1.0,
+            blend_mode: "/Normal".to_string(),
+            soft_mask: false,
+            text: QPDFTextState::default(),
+        }
+    }
+}
+
+impl QPDFPath {
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    // Control points are included, so curves may give a slightly larger box than their outline
+    pub fn bbox(&self, m: &Matrix) -> Option<Rect> {
+        let points = self.segments.iter().flat_map(|segment| match *segment {
+            QPDFPathSegment::MoveTo(x, y) | QPDFPathSegment::LineTo(x, y) => vec![(x, y)],
+            QPDFPathSegment::CurveTo(x1, y1, x2, y2, x3, y3) => vec![(x1, y1), (x2, y2), (x3, y3)],
+            QPDFPathSegment::Close => vec![],
+        });
+
+        points
+            .map(|(x, y)| m.apply(x, y))
+            .map(|(x, y)| Rect::new(x, y, x, y))
+            .reduce(|a, b| a.union(&b))
+    }
+}
@@ -0,0 +1,112 @@
+use types::{QPDFPageLabelRange, QPDFPageLabelStyle};
+
+use super::{QPDF, QPDFErrors, object::types::QPDFModifyObjectTypes, tree::types::QPDFNumberTree};
+
+pub(crate) fn read_label_ranges(qpdf: &QPDF) -> Vec<QPDFPageLabelRange> {
+    let Some(root) = qpdf.get_object_root() else {
+        return Vec::new();
+    };
+
+    let tree = QPDFNumberTree::new(root.dict_get_key("/PageLabels".to_string()));
+
+    let mut ranges: Vec<QPDFPageLabelRange> = tree
+        .entries()
+        .into_iter()
+        .filter(|(key, _)| *key >= 0)
+        .map(|(key, dict)| {
+            let style = dict
+                .dict_get_key("/S".to_string())
+                .name()
+                .unwrap_or_default();
+
+            QPDFPageLabelRange {
+                start_page: key as usize,
+                style: QPDFPageLabelStyle::from_name(&style),
+                prefix: dict
+                    .dict_get_key("/P".to_string())
+                    .try_into()
+                    .unwrap_or_default(),
+                first_number: dict.dict_get_key("/St".to_string()).try_into().unwrap_or(1),
+            }
+        })
+        .collect();
+
+    ranges.sort_by_key(|range| range.start_page);
+    ranges.dedup_by_key(|range| range.start_page);
+    ranges
+}
+
+// Pages before the first range, or in documents without labels, are numbered from 1
+pub(crate) fn label_pages(ranges: &[QPDFPageLabelRange], len: usize) -> Vec<String> {
+    (0..len)
+        .map(
+            |index| match ranges.iter().rev().find(|r| r.start_page <= index) {
+                Some(range) => range.label(index - range.start_page),
+                None => (index + 1).to_string(),
+            },
+        )
+        .collect()
+}
+
+// Replaces /PageLabels with a new number tree, an empty list removes it
+pub(crate) fn write_label_ranges(
+    qpdf: &QPDF,
+    ranges: &[QPDFPageLabelRange],
+) -> Result<(), QPDFErrors> {
+    let root = qpdf.get_object_root().ok_or(QPDFErrors::InvalidObject)?;
+
+    if ranges.is_empty() {
+        root.dict_remove_key("/PageLabels".to_string());
+        return Ok(());
+    }
+
+    let len = qpdf.len_pages().max(0) as usize;
+    if ranges.iter().any(|range| range.start_page >= len) {
+        return Err(QPDFErrors::InvalidPage);
+    }
+
+    let tree = QPDFNumberTree::empty(&root).ok_or(QPDFErrors::InvalidObject)?;
+
+    // The tree must cover page 0, so leading pages keep the decimal numbers they would show anyway
+    let leading = QPDFPageLabelRange::new(0, QPDFPageLabelStyle::Digits);
+    let ranges = match ranges.iter().any(|range| range.start_page == 0) {
+        true => ranges.to_vec(),
+        _ => [vec![leading], ranges.to_vec()].concat(),
+    };
+
+    // Later ranges starting on the same page replace earlier ones
+    for range in &ranges {
+        let dict = root.set(QPDFModifyObjectTypes::Dictionary);
+
+        if let Some(style) = range.style.name() {
+            dict.dict_replace_key(
+                "/S".to_string(),
+                root.set(QPDFModifyObjectTypes::Name(style.to_string())),
+            );
+        }
+
+        if !range.prefix.is_empty() {
+            dict.dict_replace_key(
+                "/P".to_string(),
+                root.set(QPDFModifyObjectTypes::String(range.prefix.clone())),
+            );
+        }
+
+        if range.first_number != 1 {
+            dict.dict_replace_key(
+                "/St".to_string(),
+                root.set(QPDFModifyObjectTypes::Integer(range.first_number)),
+            );
+        }
+
+        tree.insert(range.start_page as i64, dict);
+    }
+
+    root.dict_replace_key("/PageLabels".to_string(), tree.root().clone());
+    Ok(())
+}
+
+pub mod types;
+
+#[cfg(test)]
+mod tests;
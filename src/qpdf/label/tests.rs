@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use super::{
+    label_pages,
+    types::{QPDFPageLabelRange, QPDFPageLabelStyle},
+};
+use crate::qpdf::{QPDF, read::QPDFReadParams};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+#[test]
+fn format_labels() {
+    let roman = QPDFPageLabelRange::new(0, QPDFPageLabelStyle::RomanLower);
+    assert_eq!("i", roman.label(0));
+    assert_eq!("iv", roman.label(3));
+    assert_eq!("xiv", roman.label(13));
+
+    let roman = QPDFPageLabelRange::new(0, QPDFPageLabelStyle::RomanUpper).with_first_number(1994);
+    assert_eq!("MCMXCIV", roman.label(0));
+
+    let alpha = QPDFPageLabelRange::new(0, QPDFPageLabelStyle::AlphaUpper);
+    assert_eq!("A", alpha.label(0));
+    assert_eq!("Z", alpha.label(25));
+    assert_eq!("AA", alpha.label(26));
+    assert_eq!("bbb", alpha.label(53).to_ascii_lowercase());
+
+    let digits = QPDFPageLabelRange::new(0, QPDFPageLabelStyle::Digits)
+        .with_prefix("A-")
+        .with_first_number(8);
+    assert_eq!("A-8", digits.label(0));
+    assert_eq!("A-10", digits.label(2));
+
+    let prefix_only = QPDFPageLabelRange::new(0, QPDFPageLabelStyle::None).with_prefix("Cover");
+    assert_eq!("Cover", prefix_only.label(3));
+}
+
+#[test]
+fn label_page_ranges() {
+    let ranges = vec![
+        QPDFPageLabelRange::new(1, QPDFPageLabelStyle::RomanLower),
+        QPDFPageLabelRange::new(3, QPDFPageLabelStyle::Digits),
+    ];
+
+    assert_eq!(vec!["1", "i", "ii", "1", "2"], label_pages(&ranges, 5));
+    assert_eq!(vec!["1", "2"], label_pages(&[], 2));
+}
+
+#[test]
+fn read_write_page_labels() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+    assert!(qpdf.page_label_ranges().is_empty());
+    assert_eq!(vec!["1", "2", "3"], qpdf.page_labels());
+
+    let ranges = vec![
+        QPDFPageLabelRange::new(2, QPDFPageLabelStyle::Digits),
+        QPDFPageLabelRange::new(0, QPDFPageLabelStyle::RomanLower),
+    ];
+    qpdf.set_page_labels(&ranges).unwrap();
+
+    assert_eq!(vec!["i", "ii", "1"], qpdf.page_labels());
+    assert_eq!(0, qpdf.page_label_ranges()[0].start_page);
+
+    let ranges = vec![
+        QPDFPageLabelRange::new(0, QPDFPageLabelStyle::Digits)
+            .with_prefix("Exhibit ")
+            .with_first_number(4),
+    ];
+    qpdf.set_page_labels(&ranges).unwrap();
+    assert_eq!(ranges, qpdf.page_label_ranges());
+    assert_eq!("Exhibit 6", qpdf.page_labels()[2]);
+
+    // A leading decimal range is added when none starts on the first page
+    qpdf.set_page_labels(&[QPDFPageLabelRange::new(1, QPDFPageLabelStyle::AlphaUpper)])
+        .unwrap();
+    assert_eq!(
+        vec![
+            QPDFPageLabelRange::new(0, QPDFPageLabelStyle::Digits),
+            QPDFPageLabelRange::new(1, QPDFPageLabelStyle::AlphaUpper),
+        ],
+        qpdf.page_label_ranges()
+    );
+    assert_eq!(vec!["1", "A", "B"], qpdf.page_labels());
+
+    let out_of_range = vec![QPDFPageLabelRange::new(3, QPDFPageLabelStyle::Digits)];
+    assert!(qpdf.set_page_labels(&out_of_range).is_err());
+
+    qpdf.set_page_labels(&[]).unwrap();
+    assert!(qpdf.page_label_ranges().is_empty());
+}
@@ -0,0 +1,124 @@
+// Variants follow the order of qpdf_page_label_e
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum QPDFPageLabelStyle {
+    #[default]
+    None,
+    Digits,
+    AlphaLower,
+    AlphaUpper,
+    RomanLower,
+    RomanUpper,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFPageLabelRange {
+    pub start_page: usize,
+    pub style: QPDFPageLabelStyle,
+    pub prefix: String,
+    pub first_number: i64,
+}
+
+impl QPDFPageLabelStyle {
+    pub(crate) fn from_name(name: &str) -> Self {
+        match name {
+            "/D" => QPDFPageLabelStyle::Digits,
+            "/a" => QPDFPageLabelStyle::AlphaLower,
+            "/A" => QPDFPageLabelStyle::AlphaUpper,
+            "/r" => QPDFPageLabelStyle::RomanLower,
+            "/R" => QPDFPageLabelStyle::RomanUpper,
+            _ => QPDFPageLabelStyle::None,
+        }
+    }
+
+    pub(crate) fn name(&self) -> Option<&'static str> {
+        match self {
+            QPDFPageLabelStyle::None => None,
+            QPDFPageLabelStyle::Digits => Some("/D"),
+            QPDFPageLabelStyle::AlphaLower => Some("/a"),
+            QPDFPageLabelStyle::AlphaUpper => Some("/A"),
+            QPDFPageLabelStyle::RomanLower => Some("/r"),
+            QPDFPageLabelStyle::RomanUpper => Some("/R"),
+        }
+    }
+}
+
+// Label Range Construction
+impl QPDFPageLabelRange {
+    pub fn new(start_page: usize, style: QPDFPageLabelStyle) -> Self {
+        Self {
+            start_page,
+            style,
+            prefix: String::new(),
+            first_number: 1,
+        }
+    }
+
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    pub fn with_first_number(mut self, first_number: i64) -> Self {
+        self.first_number = first_number;
+        self
+    }
+}
+
+// Label Range Methods
+impl QPDFPageLabelRange {
+    // `offset` is the position of the page within the range
+    pub fn label(&self, offset: usize) -> String {
+        let number = self.first_number + offset as i64;
+
+        let numeral = match self.style {
+            QPDFPageLabelStyle::None => String::new(),
+            QPDFPageLabelStyle::Digits => number.to_string(),
+            QPDFPageLabelStyle::AlphaLower => alpha(number).to_ascii_lowercase(),
+            QPDFPageLabelStyle::AlphaUpper => alpha(number),
+            QPDFPageLabelStyle::RomanLower => roman(number).to_ascii_lowercase(),
+            QPDFPageLabelStyle::RomanUpper => roman(number),
+        };
+
+        format!("{}{}", self.prefix, numeral)
+    }
+}
+
+// Letters run A to Z, then AA to ZZ, then AAA to ZZZ and so on
+fn alpha(number: i64) -> String {
+    if number < 1 {
+        return String::new();
+    }
+
+    let letter = (b'A' + ((number - 1) % 26) as u8) as char;
+    letter.to_string().repeat(((number - 1) / 26 + 1) as usize)
+}
+
+fn roman(number: i64) -> String {
+    const NUMERALS: [(i64, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut rest = number.max(0);
+    let mut out = String::new();
+
+    for (value, numeral) in NUMERALS {
+        while rest >= value {
+            out.push_str(numeral);
+            rest -= value;
+        }
+    }
+
+    out
+}
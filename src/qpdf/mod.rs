@@ -1,15 +1,41 @@
 use std::{
+    collections::HashSet,
     ffi::{CStr, CString},
     io::Error,
     path::PathBuf,
 };
 
+use annotation::{
+    QPDFAnnotation, create_annotation,
+    types::{QPDFAnnotationFlattenScope, QPDFAnnotationParams},
+};
 use error::{QPDFInternalError, QPDFInternalErrorCode};
+use font::types::QPDFDocumentFont;
+use form::{
+    QPDFFormField, collect_fields, fill_field, flatten_fields, flatten_shown_fields,
+    types::QPDFFieldValue,
+};
+use geometry::Rect;
+use image::{
+    QPDFImage,
+    types::{QPDFImageOptimizeParams, QPDFImageOptimizeResult},
+};
+use label::{label_pages, read_label_ranges, types::QPDFPageLabelRange, write_label_ranges};
 use object::{
     QPDFObjectHandler,
     types::{Generation, ObjectId},
 };
+use outline::{
+    read_outlines,
+    types::{QPDFOutline, QPDFOutlineEntry},
+    update_outlines, write_outlines,
+};
+use page::{
+    QPDFPage,
+    types::{QPDFPageAutoCropParams, QPDFPageScaleParams, QPDFPageSize},
+};
 use read::QPDFReadParams;
+use text::types::QPDFTextRun;
 use write::{QPDFWriteParams, QPDFWriteVersion};
 
 use crate::libqpdf;
@@ -420,6 +446,229 @@ impl QPDF {
     }
 }
 
+// Fonts
+impl QPDF {
+    pub fn fonts(&self) -> Result<Vec<QPDFDocumentFont>, QPDFErrors> {
+        let mut fonts: Vec<QPDFDocumentFont> = Vec::new();
+
+        for at in 0..(self.len_pages().max(0) as usize) {
+            let page = QPDFPage::from(self.get_page(at).ok_or(QPDFErrors::InvalidPage)?);
+
+            for info in page.fonts() {
+                let id = (info.object_id, info.generation);
+                let existing = fonts
+                    .iter_mut()
+                    .find(|f| id.0 != 0 && (f.info.object_id, f.info.generation) == id);
+
+                match existing {
+                    Some(font) => font.pages.push(at),
+                    None => fonts.push(QPDFDocumentFont {
+                        info,
+                        pages: vec![at],
+                    }),
+                }
+            }
+        }
+
+        Ok(fonts)
+    }
+}
+
+// Annotations
+impl QPDF {
+    pub fn annotations(&self) -> Result<Vec<Vec<QPDFAnnotation>>, QPDFErrors> {
+        (0..(self.len_pages().max(0) as usize))
+            .map(|at| {
+                let page = QPDFPage::from(self.get_page(at).ok_or(QPDFErrors::InvalidPage)?);
+                Ok(page.annotations())
+            })
+            .collect()
+    }
+
+    pub fn add_annotation(
+        &self,
+        page: usize,
+        params: &QPDFAnnotationParams,
+    ) -> Result<QPDFAnnotation, QPDFErrors> {
+        let page = QPDFPage::from(self.get_page(page).ok_or(QPDFErrors::InvalidPage)?);
+        create_annotation(self, &page, params)
+    }
+
+    pub fn remove_annotations<F: Fn(&QPDFAnnotation) -> bool>(
+        &self,
+        predicate: F,
+    ) -> Result<usize, QPDFErrors> {
+        let mut removed = 0;
+        for at in 0..(self.len_pages().max(0) as usize) {
+            let page = QPDFPage::from(self.get_page(at).ok_or(QPDFErrors::InvalidPage)?);
+            removed += page.remove_annotations(&predicate);
+        }
+
+        Ok(removed)
+    }
+
+    pub fn flatten_annotations(
+        &self,
+        scope: QPDFAnnotationFlattenScope,
+    ) -> Result<usize, QPDFErrors> {
+        let mut flattened = 0;
+        for at in 0..(self.len_pages().max(0) as usize) {
+            let page = QPDFPage::from(self.get_page(at).ok_or(QPDFErrors::InvalidPage)?);
+            flattened += page.flatten_annotations(scope);
+        }
+
+        // Form fields go through the form so that the fields are removed along with their widgets
+        Ok(flattened + flatten_shown_fields(self, scope)?)
+    }
+}
+
+// Forms
+impl QPDF {
+    pub fn form_fields(&self) -> Vec<QPDFFormField> {
+        collect_fields(self)
+    }
+
+    // Sets a field by its fully qualified name and refreshes the appearance of its widgets
+    pub fn set_field_value(&self, name: &str, value: QPDFFieldValue) -> Result<(), QPDFErrors> {
+        fill_field(self, name, value)
+    }
+
+    pub fn flatten_form_fields<F: Fn(&QPDFFormField) -> bool>(
+        &self,
+        scope: QPDFAnnotationFlattenScope,
+        predicate: F,
+    ) -> Result<usize, QPDFErrors> {
+        flatten_fields(self, scope, predicate)
+    }
+}
+
+// Images
+impl QPDF {
+    pub fn images(&self) -> Result<Vec<Vec<QPDFImage>>, QPDFErrors> {
+        (0..(self.len_pages().max(0) as usize))
+            .map(|at| QPDFPage::from(self.get_page(at).ok_or(QPDFErrors::InvalidPage)?).images())
+            .collect()
+    }
+
+    pub fn optimize_images(
+        &self,
+        params: QPDFImageOptimizeParams,
+    ) -> Result<Vec<QPDFImageOptimizeResult>, QPDFErrors> {
+        let mut seen = HashSet::new();
+        let mut results = Vec::new();
+
+        for mut image in self.images()?.into_iter().flatten() {
+            // Images shared between pages are only recompressed once
+            if image.info().inline
+                || !seen.insert((image.info().object_id, image.info().generation))
+            {
+                continue;
+            }
+
+            if let Some(result) = image.optimize(&params)? {
+                results.push(result);
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+// Page Labels
+impl QPDF {
+    pub fn page_label_ranges(&self) -> Vec<QPDFPageLabelRange> {
+        read_label_ranges(self)
+    }
+
+    pub fn page_labels(&self) -> Vec<String> {
+        label_pages(&self.page_label_ranges(), self.len_pages().max(0) as usize)
+    }
+
+    pub fn set_page_labels(&self, ranges: &[QPDFPageLabelRange]) -> Result<(), QPDFErrors> {
+        write_label_ranges(self, ranges)
+    }
+}
+
+// Outlines
+impl QPDF {
+    pub fn outlines(&self) -> Vec<QPDFOutline> {
+        read_outlines(self)
+    }
+
+    pub fn set_outlines(&self, outlines: &[QPDFOutline]) -> Result<(), QPDFErrors> {
+        write_outlines(self, outlines)
+    }
+
+    // Applies edited entries to the existing items in place, the tree must keep its shape
+    pub fn update_outlines(&self, entries: &[QPDFOutlineEntry]) -> Result<(), QPDFErrors> {
+        update_outlines(self, entries)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn export_outlines(
+        &self,
+        format: outline::types::QPDFOutlineFormat,
+    ) -> Result<String, QPDFErrors> {
+        outline::serialize::export(&self.outlines(), format)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn import_outlines(
+        &self,
+        data: &str,
+        format: outline::types::QPDFOutlineFormat,
+    ) -> Result<(), QPDFErrors> {
+        self.update_outlines(&outline::serialize::import(data, format)?)
+    }
+}
+
+// Text
+impl QPDF {
+    pub fn extract_text(&self) -> Result<Vec<String>, QPDFErrors> {
+        (0..(self.len_pages().max(0) as usize))
+            .map(|at| {
+                QPDFPage::from(self.get_page(at).ok_or(QPDFErrors::InvalidPage)?).extract_text()
+            })
+            .collect()
+    }
+
+    pub fn text_runs(&self) -> Result<Vec<Vec<QPDFTextRun>>, QPDFErrors> {
+        (0..(self.len_pages().max(0) as usize))
+            .map(|at| QPDFPage::from(self.get_page(at).ok_or(QPDFErrors::InvalidPage)?).text_runs())
+            .collect()
+    }
+}
+
+// Auto-Crop
+impl QPDF {
+    pub fn auto_crop_pages(
+        &self,
+        params: QPDFPageAutoCropParams,
+    ) -> Result<Vec<Option<Rect>>, QPDFErrors> {
+        (0..(self.len_pages().max(0) as usize))
+            .map(|at| {
+                QPDFPage::from(self.get_page(at).ok_or(QPDFErrors::InvalidPage)?).auto_crop(&params)
+            })
+            .collect()
+    }
+}
+
+// Page Scaling
+impl QPDF {
+    pub fn scale_pages(
+        &self,
+        size: QPDFPageSize,
+        params: QPDFPageScaleParams,
+    ) -> Result<(), QPDFErrors> {
+        for at in 0..(self.len_pages().max(0) as usize) {
+            let page = QPDFPage::from(self.get_page(at).ok_or(QPDFErrors::InvalidPage)?);
+            page.scale(size, &params)?;
+        }
+
+        Ok(())
+    }
+}
+
 // Deconstructor
 impl Drop for QPDF {
     fn drop(&mut self) {
@@ -432,11 +681,34 @@ impl Drop for QPDF {
 #[derive(Debug)]
 pub enum QPDFErrors {
     KeyNotFound,
+    InvalidPage,
+    InvalidContent,
+    InvalidObject,
+    InvalidStreamData,
+    UnsupportedImage,
+    UnsupportedFilter(String),
+    InvalidOutline(String),
+    InvalidFieldValue(String),
+    MissingAppearance(String),
+    Internal(QPDFInternalErrorCode),
 }
 
+pub mod annotation;
+pub mod content;
 pub mod error;
+pub mod filters;
+pub mod font;
+pub mod form;
+pub mod geometry;
+pub mod image;
+pub mod interpreter;
+pub mod label;
 pub mod object;
+pub mod outline;
+pub mod page;
 pub mod read;
+pub mod text;
+pub mod tree;
 pub mod write;
 
 #[cfg(test)]
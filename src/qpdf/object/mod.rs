@@ -1,9 +1,18 @@
 use core::slice;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 
 use libc::c_char;
-use types::{Generation, ObjectId, QPDFIsObjectType, QPDFModifyObjectTypes};
-
+use types::{Generation, ObjectId, QPDFIsObjectType, QPDFModifyObjectTypes, QPDFStreamData};
+
+use super::{
+    QPDFErrors,
+    error::QPDFInternalErrorCode,
+    filters::{
+        QPDFFilterRegistry, QPDFStreamReader, filter_chain,
+        types::{QPDFDecodeParms, QPDFFilter},
+    },
+    write::QPDFWriteDecodeLevel,
+};
 use crate::libqpdf;
 
 pub struct QPDFObjectHandler {
@@ -289,6 +298,22 @@ impl QPDFObjectHandler {
         }
     }
 
+    pub fn binary_string(&self) -> Result<Vec<u8>, ()> {
+        if !self.is(QPDFIsObjectType::String) {
+            return Err(());
+        }
+
+        let mut len: usize = 0;
+        let ptr = unsafe {
+            libqpdf::qpdf_oh_get_binary_string_value(self.parent, self.handler, &raw mut len)
+        };
+
+        match ptr.is_null() {
+            true => Err(()),
+            _ => Ok(unsafe { slice::from_raw_parts(ptr.cast::<u8>(), len) }.to_vec()),
+        }
+    }
+
     pub fn dict(&self) -> QPDFObjectHandler {
         let handler = unsafe { libqpdf::qpdf_oh_get_dict(self.parent, self.handler) };
         QPDFObjectHandler::new(self.parent, handler)
@@ -342,6 +367,9 @@ impl QPDFObjectHandler {
                     let l = b.len();
                     libqpdf::qpdf_oh_new_binary_unicode_string(p, t, l)
                 }
+                QPDFModifyObjectTypes::Bytes(v) => {
+                    libqpdf::qpdf_oh_new_binary_string(p, v.as_ptr() as *const i8, v.len())
+                }
             }
         };
 
@@ -389,6 +417,21 @@ impl QPDFObjectHandler {
 
 // Dictionary Methods
 impl QPDFObjectHandler {
+    pub fn dict_keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+
+        unsafe {
+            libqpdf::qpdf_oh_begin_dict_key_iter(self.parent, self.handler);
+
+            while libqpdf::qpdf_oh_dict_more_keys(self.parent) == 1 {
+                let key = libqpdf::qpdf_oh_dict_next_key(self.parent);
+                keys.push(CStr::from_ptr(key).to_string_lossy().to_string());
+            }
+        }
+
+        keys
+    }
+
     pub fn dict_has_key(&self, key: String) -> bool {
         let key = CString::new(key)
             .expect("Key must be a valid string")
@@ -460,6 +503,137 @@ impl QPDFObjectHandler {
     }
 }
 
+// Stream Methods
+impl QPDFObjectHandler {
+    pub fn stream_data(
+        &self,
+        decode_level: QPDFWriteDecodeLevel,
+    ) -> Result<QPDFStreamData, QPDFErrors> {
+        if !self.is(QPDFIsObjectType::Stream) {
+            return Err(QPDFErrors::InvalidObject);
+        }
+
+        let mut filtered: i32 = 0;
+        let mut len: usize = 0;
+        let mut buf: *mut u8 = std::ptr::null_mut();
+
+        let status: QPDFInternalErrorCode = unsafe {
+            libqpdf::qpdf_oh_get_stream_data(
+                self.parent,
+                self.handler,
+                decode_level as u32,
+                &raw mut filtered,
+                &raw mut buf,
+                &raw mut len,
+            )
+        }
+        .into();
+
+        let data = unsafe { take_buffer(buf, len) };
+
+        match status {
+            QPDFInternalErrorCode::Errors => Err(QPDFErrors::Internal(status)),
+            _ => Ok(QPDFStreamData {
+                data,
+                filtered: filtered == 1,
+            }),
+        }
+    }
+
+    pub fn raw_stream_data(&self) -> Result<Vec<u8>, QPDFErrors> {
+        Ok(self.stream_data(QPDFWriteDecodeLevel::None)?.data)
+    }
+
+    // The C API only hands out whole buffers, so the raw encoded bytes are fetched in full and
+    // only the decoding happens incrementally as the reader is consumed
+    pub fn stream_reader(&self) -> Result<QPDFStreamReader, QPDFErrors> {
+        self.stream_reader_with(&QPDFFilterRegistry::default())
+    }
+
+    pub fn stream_reader_with(
+        &self,
+        registry: &QPDFFilterRegistry,
+    ) -> Result<QPDFStreamReader, QPDFErrors> {
+        let raw = self.raw_stream_data()?;
+        QPDFStreamReader::with_registry(raw, filter_chain(&self.dict()), registry)
+    }
+
+    pub fn encode_stream_data(
+        &self,
+        data: &[u8],
+        chain: &[(QPDFFilter, QPDFDecodeParms)],
+        registry: &QPDFFilterRegistry,
+    ) -> Result<(), QPDFErrors> {
+        if !self.is(QPDFIsObjectType::Stream) {
+            return Err(QPDFErrors::InvalidObject);
+        }
+
+        self.replace_encoded_stream_data(&registry.encode(data, chain)?, chain)
+    }
+
+    // Stores data that has already been encoded with `chain`
+    pub fn replace_encoded_stream_data(
+        &self,
+        data: &[u8],
+        chain: &[(QPDFFilter, QPDFDecodeParms)],
+    ) -> Result<(), QPDFErrors> {
+        if !self.is(QPDFIsObjectType::Stream) {
+            return Err(QPDFErrors::InvalidObject);
+        }
+
+        let filters = self.set(QPDFModifyObjectTypes::Array);
+        let parms = self.set(QPDFModifyObjectTypes::Array);
+
+        for (filter, decode_parms) in chain {
+            filters.array_append(self.set(QPDFModifyObjectTypes::Name(filter.name())));
+            parms.array_append(decode_parms.to_object(self));
+        }
+
+        let has_parms = chain.iter().any(|(_, p)| *p != QPDFDecodeParms::default());
+
+        self.replace_stream_data(
+            data,
+            (!chain.is_empty()).then_some(filters),
+            has_parms.then_some(parms),
+        );
+        Ok(())
+    }
+
+    pub fn replace_stream_data(
+        &self,
+        data: &[u8],
+        filter: Option<QPDFObjectHandler>,
+        decode_parms: Option<QPDFObjectHandler>,
+    ) {
+        let filter = filter.unwrap_or_else(|| self.set(QPDFModifyObjectTypes::Null));
+        let decode_parms = decode_parms.unwrap_or_else(|| self.set(QPDFModifyObjectTypes::Null));
+
+        unsafe {
+            libqpdf::qpdf_oh_replace_stream_data(
+                self.parent,
+                self.handler,
+                data.as_ptr(),
+                data.len(),
+                filter.handler,
+                decode_parms.handler,
+            );
+        }
+    }
+}
+
+// Copies a buffer allocated by qpdf and frees the original
+pub(crate) unsafe fn take_buffer(mut buf: *mut u8, len: usize) -> Vec<u8> {
+    if buf.is_null() {
+        return Vec::new();
+    }
+
+    unsafe {
+        let data = slice::from_raw_parts(buf, len).to_vec();
+        libqpdf::qpdf_oh_free_buffer(&raw mut buf);
+        data
+    }
+}
+
 // Other
 impl QPDFObjectHandler {
     pub fn make_direct(&self) {
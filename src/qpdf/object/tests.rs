@@ -1,6 +1,15 @@
-use std::path::PathBuf;
+use std::{io::Read, path::PathBuf};
 
-use crate::qpdf::{QPDF, read::QPDFReadParams};
+use super::types::QPDFModifyObjectTypes;
+use crate::qpdf::{
+    QPDF,
+    filters::{
+        QPDFFilterRegistry,
+        types::{QPDFDecodeParms, QPDFFilter},
+    },
+    read::QPDFReadParams,
+    write::QPDFWriteDecodeLevel,
+};
 
 fn load(qpdf: &QPDF) {
     let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
@@ -21,3 +30,104 @@ fn check_root_name() {
     assert_eq!(0, root.generation());
     assert_eq!(22, root.object_id());
 }
+
+#[test]
+fn read_filtered_stream_data() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let stream = qpdf.get_object_id(3, 0).unwrap();
+
+    let raw = stream.raw_stream_data().unwrap();
+    assert_eq!(400, raw.len());
+
+    let decoded = stream
+        .stream_data(QPDFWriteDecodeLevel::Generalized)
+        .unwrap();
+    assert!(decoded.filtered);
+    assert!(decoded.data.starts_with(b"q Q q"));
+}
+
+#[test]
+fn replace_stream_data_with_filter() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    let stream = root.set(QPDFModifyObjectTypes::Stream);
+
+    stream.replace_stream_data(b"Hello", None, None);
+    assert_eq!(b"Hello".to_vec(), stream.raw_stream_data().unwrap());
+
+    let filter = root.set(QPDFModifyObjectTypes::Name("/ASCIIHexDecode".to_string()));
+    stream.replace_stream_data(b"48656C6C6F>", Some(filter), None);
+
+    let decoded = stream
+        .stream_data(QPDFWriteDecodeLevel::Generalized)
+        .unwrap();
+    assert!(decoded.filtered);
+    assert_eq!(b"Hello".to_vec(), decoded.data);
+    assert!(stream.dict().dict_has_key("/Filter".to_string()));
+}
+
+#[test]
+fn stream_data_requires_stream() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    assert!(root.raw_stream_data().is_err());
+}
+
+#[test]
+fn stream_reader_matches_qpdf_decoding() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let stream = qpdf.get_object_id(3, 0).unwrap();
+
+    let mut data = Vec::new();
+    stream
+        .stream_reader()
+        .unwrap()
+        .read_to_end(&mut data)
+        .unwrap();
+
+    let decoded = stream
+        .stream_data(QPDFWriteDecodeLevel::Generalized)
+        .unwrap();
+    assert_eq!(decoded.data, data);
+}
+
+#[test]
+fn encode_stream_data_with_predictor() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let stream = qpdf.get_object_id(3, 0).unwrap();
+    let data: Vec<u8> = (0..=255u8).cycle().take(3000).collect();
+
+    let parms = QPDFDecodeParms::default()
+        .with_predictor(15)
+        .with_colors(3)
+        .with_columns(100);
+    let chain = [(QPDFFilter::FlateDecode, parms)];
+
+    stream
+        .encode_stream_data(&data, &chain, &QPDFFilterRegistry::default())
+        .unwrap();
+
+    let decoded = stream
+        .stream_data(QPDFWriteDecodeLevel::Generalized)
+        .unwrap();
+    assert!(decoded.filtered);
+    assert_eq!(data, decoded.data);
+
+    let mut read = Vec::new();
+    stream
+        .stream_reader()
+        .unwrap()
+        .read_to_end(&mut read)
+        .unwrap();
+    assert_eq!(data, read);
+}
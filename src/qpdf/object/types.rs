@@ -28,10 +28,18 @@ pub enum QPDFModifyObjectTypes {
     Name(String),
     String(String),
     BinaryString(String),
+    // Raw string bytes, stored without any text encoding
+    Bytes(Vec<u8>),
     Array,
     Dictionary,
     Stream,
 }
 
+#[derive(Debug)]
+pub struct QPDFStreamData {
+    pub data: Vec<u8>,
+    pub filtered: bool,
+}
+
 pub type Generation = i32;
 pub type ObjectId = i32;
@@ -0,0 +1,471 @@
+use std::collections::HashSet;
+
+use types::{QPDFDestination, QPDFDestinationFit, QPDFOutline, QPDFOutlineEntry, QPDFOutlineStyle};
+
+use super::{
+    QPDF, QPDFErrors,
+    geometry::Rect,
+    object::{
+        QPDFObjectHandler,
+        types::{Generation, ObjectId, QPDFIsObjectType, QPDFModifyObjectTypes},
+    },
+    tree::types::QPDFNameTree,
+};
+
+// Limits how deeply nested bookmarks are followed
+const MAX_OUTLINE_DEPTH: usize = 64;
+
+pub(crate) fn read_outlines(qpdf: &QPDF) -> Vec<QPDFOutline> {
+    let Some(root) = qpdf.get_object_root() else {
+        return Vec::new();
+    };
+
+    let first = root
+        .dict_get_key("/Outlines".to_string())
+        .dict_get_key("/First".to_string());
+
+    read_items(qpdf, &root, first, 0, &mut HashSet::new())
+}
+
+fn read_items(
+    qpdf: &QPDF,
+    root: &QPDFObjectHandler,
+    first: QPDFObjectHandler,
+    depth: usize,
+    seen: &mut HashSet<(ObjectId, Generation)>,
+) -> Vec<QPDFOutline> {
+    let mut items = Vec::new();
+    let mut item = first;
+
+    while item.is(QPDFIsObjectType::Dictionary) && depth < MAX_OUTLINE_DEPTH {
+        // Broken /Next chains can loop back onto earlier items
+        let id = (item.object_id(), item.generation());
+        if id.0 != 0 && !seen.insert(id) {
+            break;
+        }
+
+        items.push(read_item(qpdf, root, &item, depth, seen));
+        item = item.dict_get_key("/Next".to_string());
+    }
+
+    items
+}
+
+fn read_item(
+    qpdf: &QPDF,
+    root: &QPDFObjectHandler,
+    item: &QPDFObjectHandler,
+    depth: usize,
+    seen: &mut HashSet<(ObjectId, Generation)>,
+) -> QPDFOutline {
+    let key = |key: &str| item.dict_get_key(key.to_string());
+    let (destination, page_index) = item_target(qpdf, root, item);
+
+    let count: i64 = key("/Count").try_into().unwrap_or(0);
+    let flags: i64 = key("/F").try_into().unwrap_or(0);
+
+    let color = numbers(&key("/C"));
+    let color = match color.as_slice() {
+        [r, g, b] => Some([*r, *g, *b]),
+        _ => None,
+    };
+
+    QPDFOutline {
+        title: key("/Title").try_into().unwrap_or_default(),
+        destination,
+        page_index,
+        open: count > 0,
+        color,
+        style: QPDFOutlineStyle::from(flags),
+        children: read_items(qpdf, root, key("/First"), depth + 1, seen),
+    }
+}
+
+// Items link either through /Dest or a GoTo action, other actions have no destination here
+fn item_target(
+    qpdf: &QPDF,
+    root: &QPDFObjectHandler,
+    item: &QPDFObjectHandler,
+) -> (Option<QPDFDestination>, Option<usize>) {
+    let target = match item.dict_has_key("/Dest".to_string()) {
+        true => Some(item.dict_get_key("/Dest".to_string())),
+        _ if is_goto(item) => Some(
+            item.dict_get_key("/A".to_string())
+                .dict_get_key("/D".to_string()),
+        ),
+        _ => None,
+    };
+
+    target
+        .map(|target| resolve_destination(qpdf, root, &target))
+        .unwrap_or_default()
+}
+
+fn is_goto(item: &QPDFObjectHandler) -> bool {
+    item.dict_get_key("/A".to_string())
+        .dict_get_key("/S".to_string())
+        .is(QPDFIsObjectType::NameEquals("/GoTo".to_string()))
+}
+
+pub(crate) fn resolve_destination(
+    qpdf: &QPDF,
+    root: &QPDFObjectHandler,
+    target: &QPDFObjectHandler,
+) -> (Option<QPDFDestination>, Option<usize>) {
+    let (name, explicit) = if let Ok(name) = target.name() {
+        // PDF 1.1 named destinations live in the catalog's /Dests dictionary
+        let dests = root.dict_get_key("/Dests".to_string());
+        (Some(name.clone()), dests.dict_get_key(name))
+    } else if target.is(QPDFIsObjectType::String) {
+        let tree = QPDFNameTree::new(
+            root.dict_get_key("/Names".to_string())
+                .dict_get_key("/Dests".to_string()),
+        );
+        let found = tree.get(&target.binary_string().unwrap_or_default());
+        let name: String = target.clone().try_into().unwrap_or_default();
+
+        (Some(name), found.unwrap_or_else(|| target.clone()))
+    } else {
+        (None, target.clone())
+    };
+
+    // Named destinations may be wrapped in a dictionary holding the array under /D
+    let explicit = match explicit.is(QPDFIsObjectType::Dictionary) {
+        true => explicit.dict_get_key("/D".to_string()),
+        _ => explicit,
+    };
+
+    if !explicit.is(QPDFIsObjectType::Array) || explicit.array_len() == 0 {
+        let destination = name.map(|name| QPDFDestination {
+            name: Some(name),
+            fit: QPDFDestinationFit::default(),
+        });
+        return (destination, None);
+    }
+
+    let page = explicit.array_get_at(0);
+    let page_index = match TryInto::<i64>::try_into(page.clone()) {
+        // Integers are only valid for remote destinations but some producers use them locally
+        Ok(index) if (0..qpdf.len_pages() as i64).contains(&index) => Some(index as usize),
+        Ok(_) => None,
+        Err(_) => usize::try_from(qpdf.find_page_by_handler(page)).ok(),
+    };
+
+    let destination = QPDFDestination {
+        name,
+        fit: read_fit(&explicit),
+    };
+
+    (Some(destination), page_index)
+}
+
+fn read_fit(array: &QPDFObjectHandler) -> QPDFDestinationFit {
+    let num = |at: i32| TryInto::<f64>::try_into(array.array_get_at(at)).ok();
+
+    match array.array_get_at(1).name().unwrap_or_default().as_str() {
+        "/Fit" => QPDFDestinationFit::Fit,
+        "/FitH" => QPDFDestinationFit::FitH(num(2)),
+        "/FitV" => QPDFDestinationFit::FitV(num(2)),
+        "/FitR" => QPDFDestinationFit::FitR(Rect::new(
+            num(2).unwrap_or(0.0),
+            num(3).unwrap_or(0.0),
+            num(4).unwrap_or(0.0),
+            num(5).unwrap_or(0.0),
+        )),
+        "/FitB" => QPDFDestinationFit::FitB,
+        "/FitBH" => QPDFDestinationFit::FitBH(num(2)),
+        "/FitBV" => QPDFDestinationFit::FitBV(num(2)),
+        _ => QPDFDestinationFit::XYZ {
+            left: num(2),
+            top: num(3),
+            zoom: num(4).filter(|z| *z != 0.0),
+        },
+    }
+}
+
+// Replaces the whole outline tree, an empty list removes it from the catalog
+pub(crate) fn write_outlines(qpdf: &QPDF, outlines: &[QPDFOutline]) -> Result<(), QPDFErrors> {
+    let root = qpdf.get_object_root().ok_or(QPDFErrors::InvalidObject)?;
+
+    if outlines.is_empty() {
+        root.dict_remove_key("/Outlines".to_string());
+        return Ok(());
+    }
+
+    let parent = root
+        .set(QPDFModifyObjectTypes::Dictionary)
+        .make_indirect()
+        .ok_or(QPDFErrors::InvalidObject)?;
+    parent.dict_replace_key(
+        "/Type".to_string(),
+        root.set(QPDFModifyObjectTypes::Name("/Outlines".to_string())),
+    );
+
+    let visible = write_items(qpdf, &parent, outlines)?;
+    parent.dict_replace_key(
+        "/Count".to_string(),
+        root.set(QPDFModifyObjectTypes::Integer(visible)),
+    );
+
+    root.dict_replace_key("/Outlines".to_string(), parent);
+    Ok(())
+}
+
+// Links the items under `parent` and returns how many of them are visible with it open
+fn write_items(
+    qpdf: &QPDF,
+    parent: &QPDFObjectHandler,
+    items: &[QPDFOutline],
+) -> Result<i64, QPDFErrors> {
+    let mut nodes: Vec<QPDFObjectHandler> = Vec::new();
+    let mut visible = 0;
+
+    for item in items {
+        let (node, shown) = write_item(qpdf, parent, item)?;
+        visible += shown;
+
+        if let Some(prev) = nodes.last() {
+            prev.dict_replace_key("/Next".to_string(), node.clone());
+            node.dict_replace_key("/Prev".to_string(), prev.clone());
+        }
+
+        nodes.push(node);
+    }
+
+    if let (Some(first), Some(last)) = (nodes.first(), nodes.last()) {
+        parent.dict_replace_key("/First".to_string(), first.clone());
+        parent.dict_replace_key("/Last".to_string(), last.clone());
+    }
+
+    Ok(visible)
+}
+
+fn write_item(
+    qpdf: &QPDF,
+    parent: &QPDFObjectHandler,
+    item: &QPDFOutline,
+) -> Result<(QPDFObjectHandler, i64), QPDFErrors> {
+    let node = parent
+        .set(QPDFModifyObjectTypes::Dictionary)
+        .make_indirect()
+        .ok_or(QPDFErrors::InvalidObject)?;
+    let set = |key: &str, value: QPDFObjectHandler| node.dict_replace_key(key.to_string(), value);
+
+    set(
+        "/Title",
+        node.set(QPDFModifyObjectTypes::String(item.title.clone())),
+    );
+    set("/Parent", parent.clone());
+
+    if let Some(dest) = write_destination(qpdf, &node, item)? {
+        set("/Dest", dest);
+    }
+
+    if let Some(color) = item.color {
+        let array = node.set(QPDFModifyObjectTypes::Array);
+        for v in color {
+            array.array_append(node.set(QPDFModifyObjectTypes::Real(v, 4)));
+        }
+        set("/C", array);
+    }
+
+    let flags = i64::from(item.style);
+    if flags != 0 {
+        set("/F", node.set(QPDFModifyObjectTypes::Integer(flags)));
+    }
+
+    // Closed items store the number of descendants they would show as a negative count
+    let descendants = write_items(qpdf, &node, &item.children)?;
+    if descendants > 0 {
+        let count = match item.open {
+            true => descendants,
+            _ => -descendants,
+        };
+        set("/Count", node.set(QPDFModifyObjectTypes::Integer(count)));
+    }
+
+    let shown = match item.open {
+        true => 1 + descendants,
+        _ => 1,
+    };
+
+    Ok((node, shown))
+}
+
+fn write_destination(
+    qpdf: &QPDF,
+    factory: &QPDFObjectHandler,
+    item: &QPDFOutline,
+) -> Result<Option<QPDFObjectHandler>, QPDFErrors> {
+    let fit = item
+        .destination
+        .as_ref()
+        .map(|dest| dest.fit.clone())
+        .unwrap_or_default();
+
+    let Some(page_index) = item.page_index else {
+        // Without a page, a named destination is kept as a reference to its name
+        let name = item.destination.as_ref().and_then(|dest| dest.name.clone());
+        let dest = name.map(|name| match name.starts_with('/') {
+            true => factory.set(QPDFModifyObjectTypes::Name(name)),
+            _ => factory.set(QPDFModifyObjectTypes::String(name)),
+        });
+        return Ok(dest);
+    };
+
+    destination_array(qpdf, factory, page_index, &fit).map(Some)
+}
+
+pub(crate) fn destination_array(
+    qpdf: &QPDF,
+    factory: &QPDFObjectHandler,
+    page_index: usize,
+    fit: &QPDFDestinationFit,
+) -> Result<QPDFObjectHandler, QPDFErrors> {
+    let page = qpdf.get_page(page_index).ok_or(QPDFErrors::InvalidPage)?;
+    let (mode, operands) = fit_operands(fit);
+
+    let array = factory.set(QPDFModifyObjectTypes::Array);
+    array.array_append(page);
+    array.array_append(factory.set(QPDFModifyObjectTypes::Name(mode.to_string())));
+
+    for operand in operands {
+        array.array_append(match operand {
+            Some(v) => factory.set(QPDFModifyObjectTypes::Real(v, 4)),
+            None => factory.set(QPDFModifyObjectTypes::Null),
+        });
+    }
+
+    Ok(array)
+}
+
+fn fit_operands(fit: &QPDFDestinationFit) -> (&'static str, Vec<Option<f64>>) {
+    match fit {
+        QPDFDestinationFit::XYZ { left, top, zoom } => ("/XYZ", vec![*left, *top, *zoom]),
+        QPDFDestinationFit::Fit => ("/Fit", vec![]),
+        QPDFDestinationFit::FitH(top) => ("/FitH", vec![*top]),
+        QPDFDestinationFit::FitV(left) => ("/FitV", vec![*left]),
+        QPDFDestinationFit::FitR(rect) => (
+            "/FitR",
+            vec![
+                Some(rect.llx),
+                Some(rect.lly),
+                Some(rect.urx),
+                Some(rect.ury),
+            ],
+        ),
+        QPDFDestinationFit::FitB => ("/FitB", vec![]),
+        QPDFDestinationFit::FitBH(top) => ("/FitBH", vec![*top]),
+        QPDFDestinationFit::FitBV(left) => ("/FitBV", vec![*left]),
+    }
+}
+
+// Applies edited entries onto the existing items, leaving everything the entries do not describe
+// as it was. Entries carry no identity of their own, so they are matched by position and edits
+// that add, remove or move bookmarks are refused rather than applied to the wrong items.
+pub(crate) fn update_outlines(qpdf: &QPDF, entries: &[QPDFOutlineEntry]) -> Result<(), QPDFErrors> {
+    let root = qpdf.get_object_root().ok_or(QPDFErrors::InvalidObject)?;
+    let first = root
+        .dict_get_key("/Outlines".to_string())
+        .dict_get_key("/First".to_string());
+
+    let mut matched = Vec::new();
+    match_items(first, entries, 0, &mut HashSet::new(), &mut matched)?;
+
+    for (item, entry) in matched {
+        update_item(qpdf, &root, &item, entry)?;
+    }
+
+    Ok(())
+}
+
+// Walks the items the same way read_items() does, so exported entries line up with them
+fn match_items<'a>(
+    first: QPDFObjectHandler,
+    entries: &'a [QPDFOutlineEntry],
+    depth: usize,
+    seen: &mut HashSet<(ObjectId, Generation)>,
+    matched: &mut Vec<(QPDFObjectHandler, &'a QPDFOutlineEntry)>,
+) -> Result<(), QPDFErrors> {
+    let mismatch = || {
+        QPDFErrors::InvalidOutline(
+            "Outline entries do not match the bookmarks in the document".to_string(),
+        )
+    };
+
+    let mut entries = entries.iter();
+    let mut item = first;
+
+    while item.is(QPDFIsObjectType::Dictionary) && depth < MAX_OUTLINE_DEPTH {
+        let id = (item.object_id(), item.generation());
+        if id.0 != 0 && !seen.insert(id) {
+            break;
+        }
+
+        let entry = entries.next().ok_or_else(mismatch)?;
+        let children = item.dict_get_key("/First".to_string());
+        matched.push((item.clone(), entry));
+        match_items(children, &entry.children, depth + 1, seen, matched)?;
+
+        item = item.dict_get_key("/Next".to_string());
+    }
+
+    match entries.next() {
+        Some(_) => Err(mismatch()),
+        None => Ok(()),
+    }
+}
+
+fn update_item(
+    qpdf: &QPDF,
+    root: &QPDFObjectHandler,
+    node: &QPDFObjectHandler,
+    entry: &QPDFOutlineEntry,
+) -> Result<(), QPDFErrors> {
+    let title: Option<String> = node.dict_get_key("/Title".to_string()).try_into().ok();
+    if title.as_deref() != Some(entry.title.as_str()) {
+        node.dict_replace_key(
+            "/Title".to_string(),
+            node.set(QPDFModifyObjectTypes::String(entry.title.clone())),
+        );
+    }
+
+    let page_index = match entry.page {
+        Some(page) => Some(page.checked_sub(1).ok_or(QPDFErrors::InvalidPage)?),
+        None => None,
+    };
+
+    // Destinations are only rewritten when the page changes, keeping named ones and actions
+    let (destination, current) = item_target(qpdf, root, node);
+    if current == page_index {
+        return Ok(());
+    }
+
+    let Some(page_index) = page_index else {
+        node.dict_remove_key("/Dest".to_string());
+        if is_goto(node) {
+            node.dict_remove_key("/A".to_string());
+        }
+        return Ok(());
+    };
+
+    let fit = destination.map(|dest| dest.fit).unwrap_or_default();
+    let dest = destination_array(qpdf, node, page_index, &fit)?;
+
+    node.dict_remove_key("/A".to_string());
+    node.dict_replace_key("/Dest".to_string(), dest);
+    Ok(())
+}
+
+fn numbers(array: &QPDFObjectHandler) -> Vec<f64> {
+    (0..array.array_len())
+        .filter_map(|at| array.array_get_at(at).try_into().ok())
+        .collect()
+}
+
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
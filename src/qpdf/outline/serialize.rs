@@ -0,0 +1,31 @@
+use super::types::{QPDFOutline, QPDFOutlineEntry, QPDFOutlineFormat};
+use crate::qpdf::QPDFErrors;
+
+pub(crate) fn export(
+    outlines: &[QPDFOutline],
+    format: QPDFOutlineFormat,
+) -> Result<String, QPDFErrors> {
+    let entries: Vec<QPDFOutlineEntry> = outlines.iter().map(QPDFOutlineEntry::from).collect();
+
+    match format {
+        QPDFOutlineFormat::Json => serde_json::to_string_pretty(&entries)
+            .map_err(|e| QPDFErrors::InvalidOutline(e.to_string())),
+        QPDFOutlineFormat::Yaml => {
+            serde_yaml::to_string(&entries).map_err(|e| QPDFErrors::InvalidOutline(e.to_string()))
+        }
+    }
+}
+
+pub(crate) fn import(
+    data: &str,
+    format: QPDFOutlineFormat,
+) -> Result<Vec<QPDFOutlineEntry>, QPDFErrors> {
+    match format {
+        QPDFOutlineFormat::Json => {
+            serde_json::from_str(data).map_err(|e| QPDFErrors::InvalidOutline(e.to_string()))
+        }
+        QPDFOutlineFormat::Yaml => {
+            serde_yaml::from_str(data).map_err(|e| QPDFErrors::InvalidOutline(e.to_string()))
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use std::path::PathBuf;
+
+use super::{
+    fit_operands,
+    types::{QPDFDestination, QPDFDestinationFit, QPDFOutline, QPDFOutlineEntry, QPDFOutlineStyle},
+};
+use crate::qpdf::{
+    QPDF, QPDFErrors,
+    geometry::Rect,
+    object::{QPDFObjectHandler, types::QPDFModifyObjectTypes},
+    read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+fn dict(factory: &QPDFObjectHandler, entries: Vec<(&str, QPDFObjectHandler)>) -> QPDFObjectHandler {
+    let dict = factory.set(QPDFModifyObjectTypes::Dictionary);
+    for (key, value) in entries {
+        dict.dict_replace_key(key.to_string(), value);
+    }
+
+    dict.make_indirect().unwrap()
+}
+
+fn array(factory: &QPDFObjectHandler, items: Vec<QPDFObjectHandler>) -> QPDFObjectHandler {
+    let array = factory.set(QPDFModifyObjectTypes::Array);
+    for item in items {
+        array.array_append(item);
+    }
+
+    array
+}
+
+#[test]
+fn read_outline_tree() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+    assert!(qpdf.outlines().is_empty());
+
+    let root = qpdf.get_object_root().unwrap();
+    let name = |n: &str| root.set(QPDFModifyObjectTypes::Name(n.to_string()));
+    let string = |s: &str| root.set(QPDFModifyObjectTypes::String(s.to_string()));
+    let int = |v: i64| root.set(QPDFModifyObjectTypes::Integer(v));
+    let null = || root.set(QPDFModifyObjectTypes::Null);
+    let page = |at: usize| qpdf.get_page(at).unwrap();
+
+    let goto = dict(
+        &root,
+        vec![
+            ("/S", name("/GoTo")),
+            (
+                "/D",
+                array(&root, vec![page(2), name("/XYZ"), int(0), int(500), null()]),
+            ),
+        ],
+    );
+    let child = dict(&root, vec![("/Title", string("Child")), ("/A", goto)]);
+
+    let second = dict(
+        &root,
+        vec![
+            ("/Title", string("Named")),
+            ("/Dest", string("chap2")),
+            ("/C", array(&root, vec![int(1), int(0), int(0)])),
+            ("/F", int(2)),
+            ("/Count", int(1)),
+            ("/First", child.clone()),
+            ("/Last", child),
+        ],
+    );
+    let first = dict(
+        &root,
+        vec![
+            ("/Title", string("Intro")),
+            ("/Dest", array(&root, vec![page(0), name("/Fit")])),
+            ("/Next", second.clone()),
+        ],
+    );
+    let outlines = dict(
+        &root,
+        vec![("/First", first), ("/Last", second), ("/Count", int(3))],
+    );
+    root.dict_replace_key("/Outlines".to_string(), outlines);
+
+    let tree = dict(
+        &root,
+        vec![(
+            "/Names",
+            array(
+                &root,
+                vec![
+                    string("chap2"),
+                    array(&root, vec![page(1), name("/FitH"), int(700)]),
+                ],
+            ),
+        )],
+    );
+    let names = dict(&root, vec![("/Dests", tree)]);
+    root.dict_replace_key("/Names".to_string(), names);
+
+    let outlines = qpdf.outlines();
+    assert_eq!(2, outlines.len());
+
+    assert_eq!("Intro", outlines[0].title);
+    assert_eq!(Some(0), outlines[0].page_index);
+    assert_eq!(
+        Some(QPDFDestination {
+            name: None,
+            fit: QPDFDestinationFit::Fit
+        }),
+        outlines[0].destination
+    );
+    assert!(!outlines[0].open);
+
+    let named = &outlines[1];
+    assert_eq!(Some(1), named.page_index);
+    assert_eq!(
+        Some(QPDFDestination {
+            name: Some("chap2".to_string()),
+            fit: QPDFDestinationFit::FitH(Some(700.0))
+        }),
+        named.destination
+    );
+    assert_eq!(Some([1.0, 0.0, 0.0]), named.color);
+    assert_eq!(
+        QPDFOutlineStyle {
+            italic: false,
+            bold: true
+        },
+        named.style
+    );
+    assert!(named.open);
+
+    assert_eq!(1, named.children.len());
+    assert_eq!("Child", named.children[0].title);
+    assert_eq!(Some(2), named.children[0].page_index);
+    assert_eq!(
+        QPDFDestinationFit::XYZ {
+            left: Some(0.0),
+            top: Some(500.0),
+            zoom: None
+        },
+        named.children[0].destination.as_ref().unwrap().fit
+    );
+}
+
+#[test]
+fn write_outline_tree() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let mut outlines = vec![
+        QPDFOutline::new("Summary").with_page(0, QPDFDestinationFit::Fit),
+        QPDFOutline::new("Details")
+            .with_page(1, QPDFDestinationFit::FitH(Some(700.0)))
+            .with_open()
+            .with_color([0.0, 0.0, 1.0])
+            .with_child(QPDFOutline::new("Appendix").with_page(
+                2,
+                QPDFDestinationFit::FitR(Rect::new(10.0, 20.0, 300.0, 400.0)),
+            )),
+    ];
+
+    // Rename and reorder before writing
+    outlines[0].title = "Overview".to_string();
+    outlines.swap(0, 1);
+
+    qpdf.set_outlines(&outlines).unwrap();
+    assert_eq!(outlines, qpdf.outlines());
+
+    let root = qpdf.get_object_root().unwrap();
+    let tree = root.dict_get_key("/Outlines".to_string());
+    let count: i64 = tree.dict_get_key("/Count".to_string()).try_into().unwrap();
+    assert_eq!(3, count);
+
+    let first = tree.dict_get_key("/First".to_string());
+    let last = tree.dict_get_key("/Last".to_string());
+    let prev: String = last
+        .dict_get_key("/Prev".to_string())
+        .dict_get_key("/Title".to_string())
+        .try_into()
+        .unwrap();
+    assert_eq!("Details", prev);
+    assert!(!first.dict_has_key("/Prev".to_string()));
+
+    let mut closed = outlines[0].clone();
+    closed.open = false;
+    qpdf.set_outlines(&[closed]).unwrap();
+
+    let tree = root.dict_get_key("/Outlines".to_string());
+    let item_count: i64 = tree
+        .dict_get_key("/First".to_string())
+        .dict_get_key("/Count".to_string())
+        .try_into()
+        .unwrap();
+    assert_eq!(-1, item_count);
+
+    assert!(
+        qpdf.set_outlines(&[QPDFOutline::new("Missing").with_page(9, QPDFDestinationFit::Fit)])
+            .is_err()
+    );
+
+    qpdf.set_outlines(&[]).unwrap();
+    assert!(!root.dict_has_key("/Outlines".to_string()));
+}
+
+#[test]
+fn outline_style_flags() {
+    for flags in 0..4 {
+        assert_eq!(flags, i64::from(QPDFOutlineStyle::from(flags)));
+    }
+
+    let (mode, operands) = fit_operands(&QPDFDestinationFit::default());
+    assert_eq!("/XYZ", mode);
+    assert_eq!(vec![None, None, None], operands);
+}
+
+#[test]
+fn update_outline_items_in_place() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    let name = |n: &str| root.set(QPDFModifyObjectTypes::Name(n.to_string()));
+    let string = |s: &str| root.set(QPDFModifyObjectTypes::String(s.to_string()));
+    let int = |v: i64| root.set(QPDFModifyObjectTypes::Integer(v));
+    let page = |at: usize| qpdf.get_page(at).unwrap();
+
+    let uri = dict(
+        &root,
+        vec![
+            ("/S", name("/URI")),
+            ("/URI", string("https://example.com")),
+        ],
+    );
+    let goto = dict(
+        &root,
+        vec![
+            ("/S", name("/GoTo")),
+            ("/D", array(&root, vec![page(2), name("/FitH"), int(600)])),
+        ],
+    );
+
+    let link = dict(
+        &root,
+        vec![
+            ("/Title", string("Website")),
+            ("/A", uri),
+            ("/SE", dict(&root, vec![])),
+            ("/Custom", int(7)),
+        ],
+    );
+    let named = dict(
+        &root,
+        vec![("/Title", string("Chapter")), ("/Dest", name("/chap"))],
+    );
+    let moved = dict(&root, vec![("/Title", string("Moved")), ("/A", goto)]);
+
+    link.dict_replace_key("/Next".to_string(), named.clone());
+    named.dict_replace_key("/Next".to_string(), moved.clone());
+    let outlines = dict(
+        &root,
+        vec![("/First", link.clone()), ("/Last", moved.clone())],
+    );
+    for item in [&link, &named, &moved] {
+        item.dict_replace_key("/Parent".to_string(), outlines.clone());
+    }
+    root.dict_replace_key("/Outlines".to_string(), outlines.clone());
+
+    let dests = dict(
+        &root,
+        vec![("/chap", array(&root, vec![page(1), name("/Fit")]))],
+    );
+    root.dict_replace_key("/Dests".to_string(), dests);
+
+    let mut entries: Vec<QPDFOutlineEntry> =
+        qpdf.outlines().iter().map(QPDFOutlineEntry::from).collect();
+    assert_eq!(Some(2), entries[1].page);
+
+    entries[0].title = "Our website".to_string();
+    entries[2].page = Some(1);
+    qpdf.update_outlines(&entries).unwrap();
+
+    // Keys the entries do not describe survive, as do actions and names that still apply
+    assert!(link.dict_has_key("/A".to_string()));
+    assert!(link.dict_has_key("/SE".to_string()));
+    assert!(link.dict_has_key("/Custom".to_string()));
+    assert_eq!(
+        Ok("/chap".to_string()),
+        named.dict_get_key("/Dest".to_string()).name()
+    );
+
+    assert!(!moved.dict_has_key("/A".to_string()));
+    let outlines_read = qpdf.outlines();
+    assert_eq!(
+        Some(QPDFDestinationFit::FitH(Some(600.0))),
+        outlines_read[2].destination.as_ref().map(|d| d.fit.clone())
+    );
+    assert_eq!(Some(0), outlines_read[2].page_index);
+
+    assert_eq!(
+        entries,
+        outlines_read
+            .iter()
+            .map(QPDFOutlineEntry::from)
+            .collect::<Vec<_>>()
+    );
+    assert!(!outlines.dict_has_key("/Count".to_string()));
+
+    entries[0].page = Some(0);
+    assert!(matches!(
+        qpdf.update_outlines(&entries),
+        Err(QPDFErrors::InvalidPage)
+    ));
+
+    // Added or removed entries cannot be matched to items, so nothing is changed
+    let mut added = entries.clone();
+    added[1].children.push(QPDFOutlineEntry {
+        title: "Added".to_string(),
+        page: Some(3),
+        children: vec![],
+    });
+    assert!(matches!(
+        qpdf.update_outlines(&added),
+        Err(QPDFErrors::InvalidOutline(_))
+    ));
+    assert!(matches!(
+        qpdf.update_outlines(&entries[1..]),
+        Err(QPDFErrors::InvalidOutline(_))
+    ));
+    assert!(link.dict_has_key("/A".to_string()));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn outline_entries_round_trip() {
+    use super::{serialize, types::QPDFOutlineFormat};
+
+    let outlines = vec![
+        QPDFOutline::new("Intro").with_page(0, QPDFDestinationFit::Fit),
+        QPDFOutline::new("Body")
+            .with_page(1, QPDFDestinationFit::FitH(Some(700.0)))
+            .with_child(QPDFOutline::new("Part").with_page(2, QPDFDestinationFit::Fit)),
+    ];
+    let entries: Vec<QPDFOutlineEntry> = outlines.iter().map(QPDFOutlineEntry::from).collect();
+
+    for format in [QPDFOutlineFormat::Json, QPDFOutlineFormat::Yaml] {
+        let data = serialize::export(&outlines, format).unwrap();
+        assert_eq!(entries, serialize::import(&data, format).unwrap());
+    }
+
+    let data = r#"[{"title": "Introduction", "page": 1}, {"title": "New"}]"#;
+    let imported = serialize::import(data, QPDFOutlineFormat::Json).unwrap();
+    assert_eq!(None, imported[1].page);
+    assert!(imported[1].children.is_empty());
+    assert!(serialize::import("{", QPDFOutlineFormat::Json).is_err());
+}
@@ -0,0 +1,139 @@
+use crate::qpdf::geometry::Rect;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QPDFDestinationFit {
+    XYZ {
+        left: Option<f64>,
+        top: Option<f64>,
+        zoom: Option<f64>,
+    },
+    Fit,
+    FitH(Option<f64>),
+    FitV(Option<f64>),
+    FitR(Rect),
+    FitB,
+    FitBH(Option<f64>),
+    FitBV(Option<f64>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFDestination {
+    pub name: Option<String>,
+    pub fit: QPDFDestinationFit,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QPDFOutlineStyle {
+    pub italic: bool,
+    pub bold: bool,
+}
+
+// Editable form of an outline item, with one-based page numbers
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QPDFOutlineEntry {
+    pub title: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub page: Option<usize>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub children: Vec<QPDFOutlineEntry>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum QPDFOutlineFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QPDFOutline {
+    pub title: String,
+    pub destination: Option<QPDFDestination>,
+    pub page_index: Option<usize>,
+    pub open: bool,
+    pub color: Option<[f64; 3]>,
+    pub style: QPDFOutlineStyle,
+    pub children: Vec<QPDFOutline>,
+}
+
+impl Default for QPDFDestinationFit {
+    fn default() -> Self {
+        QPDFDestinationFit::XYZ {
+            left: None,
+            top: None,
+            zoom: None,
+        }
+    }
+}
+
+impl From<i64> for QPDFOutlineStyle {
+    fn from(flags: i64) -> Self {
+        Self {
+            italic: flags & 1 != 0,
+            bold: flags & 2 != 0,
+        }
+    }
+}
+
+impl From<&QPDFOutline> for QPDFOutlineEntry {
+    fn from(outline: &QPDFOutline) -> Self {
+        Self {
+            title: outline.title.clone(),
+            page: outline.page_index.map(|index| index + 1),
+            children: outline
+                .children
+                .iter()
+                .map(QPDFOutlineEntry::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<QPDFOutlineStyle> for i64 {
+    fn from(style: QPDFOutlineStyle) -> Self {
+        style.italic as i64 | (style.bold as i64) << 1
+    }
+}
+
+// Outline Construction
+impl QPDFOutline {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_page(mut self, page_index: usize, fit: QPDFDestinationFit) -> Self {
+        self.page_index = Some(page_index);
+        self.destination = Some(QPDFDestination { name: None, fit });
+        self
+    }
+
+    pub fn with_open(mut self) -> Self {
+        self.open = true;
+        self
+    }
+
+    pub fn with_color(mut self, color: [f64; 3]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_style(mut self, style: QPDFOutlineStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_child(mut self, child: QPDFOutline) -> Self {
+        self.children.push(child);
+        self
+    }
+}
@@ -0,0 +1,514 @@
+use std::collections::HashSet;
+
+use types::{QPDFPageAutoCropParams, QPDFPageScaleMode, QPDFPageScaleParams, QPDFPageSize};
+
+use super::{
+    QPDFErrors,
+    annotation::{
+        QPDFAnnotation, collect_annotations, flatten_annotations, remove_annotations,
+        types::QPDFAnnotationFlattenScope,
+    },
+    content::{QPDFContentParser, types::QPDFContentOperation},
+    error::QPDFInternalErrorCode,
+    font::{collect_fonts, types::QPDFFontInfo},
+    geometry::{Matrix, Rect},
+    image::{QPDFImage, collect_images, types::QPDFImageColorSpace},
+    interpreter::{
+        QPDFContentHandler, QPDFContentInterpreter,
+        types::{QPDFGraphicsState, QPDFPath, QPDFPathPaint, QPDFTextGlyph},
+    },
+    object::{
+        QPDFObjectHandler, take_buffer,
+        types::{QPDFIsObjectType, QPDFModifyObjectTypes},
+    },
+    text::{extract_glyphs, extract_runs, layout_text, types::QPDFTextRun},
+};
+use crate::libqpdf;
+
+// Limits how far inherited attributes are looked up the page tree
+const MAX_TREE_DEPTH: usize = 64;
+
+pub struct QPDFPage {
+    pub(crate) object: QPDFObjectHandler,
+}
+
+// Construction
+impl From<QPDFObjectHandler> for QPDFPage {
+    fn from(object: QPDFObjectHandler) -> Self {
+        Self { object }
+    }
+}
+
+impl QPDFPage {
+    pub fn object(&self) -> &QPDFObjectHandler {
+        &self.object
+    }
+
+    pub(crate) fn inherited_key(&self, key: &str) -> Option<QPDFObjectHandler> {
+        let mut node = self.object.clone();
+
+        for _ in 0..MAX_TREE_DEPTH {
+            if node.dict_has_key(key.to_string()) {
+                return Some(node.dict_get_key(key.to_string()));
+            }
+
+            if !node.dict_has_key("/Parent".to_string()) {
+                break;
+            }
+
+            node = node.dict_get_key("/Parent".to_string());
+        }
+
+        None
+    }
+}
+
+// Resources
+impl QPDFPage {
+    pub fn resources(&self) -> Option<QPDFObjectHandler> {
+        self.inherited_key("/Resources")
+            .filter(|resources| resources.is(QPDFIsObjectType::Dictionary))
+    }
+
+    // Resources shared with other pages, directly or through /Parent, are copied down to the
+    // category dictionaries so that anything added only shows up on this page
+    pub fn resources_mut(&self) -> QPDFObjectHandler {
+        let own = self.object.dict_get_key("/Resources".to_string());
+        let shared = !own.is(QPDFIsObjectType::Dictionary) || own.object_id() != 0;
+
+        if shared {
+            let copy = match self.resources() {
+                Some(inherited) => direct_copy(&self.object, &inherited),
+                None => self.object.set(QPDFModifyObjectTypes::Dictionary),
+            };
+            self.object.dict_replace_key("/Resources".to_string(), copy);
+        }
+
+        let resources = self.object.dict_get_key("/Resources".to_string());
+        for key in resources.dict_keys() {
+            let category = resources.dict_get_key(key.clone());
+
+            if category.is(QPDFIsObjectType::Dictionary) && (shared || category.object_id() != 0) {
+                resources.dict_replace_key(key, direct_copy(&self.object, &category));
+            }
+        }
+
+        resources
+    }
+}
+
+fn direct_copy(factory: &QPDFObjectHandler, dict: &QPDFObjectHandler) -> QPDFObjectHandler {
+    let copy = factory.set(QPDFModifyObjectTypes::Dictionary);
+    for key in dict.dict_keys() {
+        copy.dict_replace_key(key.clone(), dict.dict_get_key(key));
+    }
+
+    copy
+}
+
+// Page Boxes
+impl QPDFPage {
+    pub fn media_box(&self) -> Option<Rect> {
+        self.inherited_key("/MediaBox")?.try_into().ok()
+    }
+
+    pub fn crop_box(&self) -> Option<Rect> {
+        let media = self.media_box()?;
+
+        let crop: Option<Rect> = self
+            .inherited_key("/CropBox")
+            .and_then(|oh| oh.try_into().ok());
+
+        match crop {
+            Some(crop) => crop.intersect(&media),
+            None => Some(media),
+        }
+    }
+
+    pub fn set_media_box(&self, rect: Rect) {
+        self.object
+            .dict_replace_key("/MediaBox".to_string(), rect.to_object(&self.object));
+    }
+
+    pub fn set_crop_box(&self, rect: Rect) {
+        self.object
+            .dict_replace_key("/CropBox".to_string(), rect.to_object(&self.object));
+    }
+
+    pub fn rotation(&self) -> i32 {
+        let rotate: i32 = self
+            .inherited_key("/Rotate")
+            .and_then(|oh| oh.try_into().ok())
+            .unwrap_or(0);
+
+        rotate.rem_euclid(360)
+    }
+}
+
+// Content Streams
+impl QPDFPage {
+    pub fn content_data(&self) -> Result<Vec<u8>, QPDFErrors> {
+        let mut len: usize = 0;
+        let mut buf: *mut u8 = std::ptr::null_mut();
+
+        let status: QPDFInternalErrorCode = unsafe {
+            libqpdf::qpdf_oh_get_page_content_data(
+                self.object.parent,
+                self.object.handler,
+                &raw mut buf,
+                &raw mut len,
+            )
+        }
+        .into();
+
+        let data = unsafe { take_buffer(buf, len) };
+
+        match status {
+            QPDFInternalErrorCode::Errors => Err(QPDFErrors::Internal(status)),
+            _ => Ok(data),
+        }
+    }
+
+    pub fn content_operations(&self) -> Result<Vec<QPDFContentOperation>, QPDFErrors> {
+        let data = self.content_data()?;
+        QPDFContentParser::new(&data).collect()
+    }
+
+    pub fn replace_content(&self, data: &[u8]) {
+        self.set_contents(vec![self.new_content_stream(data)]);
+    }
+
+    pub fn prepend_content(&self, data: &[u8]) {
+        let mut wrapped = b"q\n".to_vec();
+        wrapped.extend(data);
+        wrapped.extend(b"\nQ\n");
+
+        let mut streams = vec![self.new_content_stream(&wrapped)];
+        streams.extend(self.contents());
+
+        self.set_contents(streams);
+    }
+
+    pub fn append_content(&self, data: &[u8]) {
+        let original = self.contents();
+        let mut streams = Vec::new();
+
+        // The original content may leave the graphics state modified, so it is isolated first
+        if !original.is_empty() {
+            streams.push(self.new_content_stream(b"q\n"));
+            streams.extend(original);
+            streams.push(self.new_content_stream(b"\nQ\n"));
+        }
+
+        streams.push(self.new_content_stream(data));
+        self.set_contents(streams);
+    }
+
+    pub(crate) fn contents(&self) -> Vec<QPDFObjectHandler> {
+        let contents = self.object.dict_get_key("/Contents".to_string());
+
+        if contents.is(QPDFIsObjectType::Stream) {
+            return vec![contents];
+        }
+
+        if !contents.is(QPDFIsObjectType::Array) {
+            return Vec::new();
+        }
+
+        (0..contents.array_len())
+            .map(|i| contents.array_get_at(i))
+            .filter(|oh| oh.is(QPDFIsObjectType::Stream))
+            .collect()
+    }
+
+    pub(crate) fn set_contents(&self, mut streams: Vec<QPDFObjectHandler>) {
+        if streams.len() == 1 {
+            let stream = streams.remove(0);
+            self.object
+                .dict_replace_key("/Contents".to_string(), stream);
+            return;
+        }
+
+        let array = self.object.set(QPDFModifyObjectTypes::Array);
+        for stream in streams {
+            array.array_append(stream);
+        }
+
+        self.object.dict_replace_key("/Contents".to_string(), array);
+    }
+
+    pub(crate) fn new_content_stream(&self, data: &[u8]) -> QPDFObjectHandler {
+        let stream = self.object.set(QPDFModifyObjectTypes::Stream);
+        stream.replace_stream_data(data, None, None);
+        stream
+    }
+
+    pub(crate) fn wrap_contents(&self, prefix: &[u8], suffix: &[u8]) {
+        let mut streams = vec![self.new_content_stream(prefix)];
+        streams.extend(self.contents());
+        streams.push(self.new_content_stream(suffix));
+
+        self.set_contents(streams);
+    }
+}
+
+// Fonts
+impl QPDFPage {
+    pub fn fonts(&self) -> Vec<QPDFFontInfo> {
+        let mut fonts = Vec::new();
+
+        if let Some(resources) = self.inherited_key("/Resources") {
+            collect_fonts(&resources, 0, &mut HashSet::new(), &mut fonts);
+        }
+
+        fonts
+    }
+}
+
+// Images
+impl QPDFPage {
+    pub fn images(&self) -> Result<Vec<QPDFImage>, QPDFErrors> {
+        let resources = self
+            .inherited_key("/Resources")
+            .unwrap_or_else(|| self.object.set(QPDFModifyObjectTypes::Dictionary));
+
+        let mut images = Vec::new();
+        collect_images(
+            &self.content_data()?,
+            &resources,
+            0,
+            &mut HashSet::new(),
+            &mut images,
+        )?;
+
+        Ok(images)
+    }
+}
+
+// Annotations
+impl QPDFPage {
+    pub fn annotations(&self) -> Vec<QPDFAnnotation> {
+        collect_annotations(&self.object)
+    }
+
+    pub fn remove_annotations<F: Fn(&QPDFAnnotation) -> bool>(&self, predicate: F) -> usize {
+        remove_annotations(&self.object, predicate)
+    }
+
+    pub fn flatten_annotations(&self, scope: QPDFAnnotationFlattenScope) -> usize {
+        flatten_annotations(self, scope)
+    }
+}
+
+// Text
+impl QPDFPage {
+    pub fn extract_text(&self) -> Result<String, QPDFErrors> {
+        Ok(layout_text(extract_glyphs(self)?))
+    }
+
+    pub fn text_runs(&self) -> Result<Vec<QPDFTextRun>, QPDFErrors> {
+        extract_runs(self)
+    }
+}
+
+// Content Bounds
+impl QPDFPage {
+    pub fn content_bbox(&self, include_white: bool) -> Result<Option<Rect>, QPDFErrors> {
+        let Some(crop_box) = self.crop_box() else {
+            return Ok(None);
+        };
+
+        let mut bounds = ContentBounds {
+            page: crop_box,
+            include_white,
+            bbox: None,
+        };
+        QPDFContentInterpreter::new().run_page(self, &mut bounds)?;
+
+        Ok(bounds.bbox.and_then(|bbox| bbox.intersect(&crop_box)))
+    }
+
+    pub fn auto_crop(&self, params: &QPDFPageAutoCropParams) -> Result<Option<Rect>, QPDFErrors> {
+        let media_box = self.media_box().ok_or(QPDFErrors::InvalidPage)?;
+
+        // Blank pages are left as they are
+        let Some(bbox) = self.content_bbox(params.include_white)? else {
+            return Ok(None);
+        };
+
+        let crop = bbox.expand(params.margin).intersect(&media_box);
+        if let Some(crop) = crop {
+            self.set_crop_box(crop);
+        }
+
+        Ok(crop)
+    }
+}
+
+struct ContentBounds {
+    page: Rect,
+    include_white: bool,
+    bbox: Option<Rect>,
+}
+
+impl ContentBounds {
+    fn add(&mut self, bbox: Rect, state: &QPDFGraphicsState) {
+        let visible = match state.clip {
+            Some(clip) => clip.intersect(&bbox),
+            None => Some(bbox),
+        };
+
+        if let Some(visible) = visible {
+            self.bbox = Some(self.bbox.map_or(visible, |b| b.union(&visible)));
+        }
+    }
+
+    fn paints(&self, space: &QPDFImageColorSpace, color: &[f64]) -> bool {
+        self.include_white || !is_white(space, color)
+    }
+}
+
+impl QPDFContentHandler for ContentBounds {
+    fn path(&mut self, path: &QPDFPath, paint: QPDFPathPaint, state: &QPDFGraphicsState) {
+        let fill = paint.fill.is_some() && self.paints(&state.fill_color_space, &state.fill_color);
+        let stroke = paint.stroke && self.paints(&state.stroke_color_space, &state.stroke_color);
+
+        let Some(bbox) = path.bbox(&state.ctm).filter(|_| fill || stroke) else {
+            return;
+        };
+
+        // Strokes extend half the line width beyond the path, scaled to page space
+        let m = &state.ctm;
+        let half_width = match stroke {
+            true => state.line_width * (m.a * m.d - m.b * m.c).abs().sqrt() / 2.0,
+            _ => 0.0,
+        };
+
+        self.add(bbox.expand(half_width), state);
+    }
+
+    fn text(&mut self, glyphs: &[QPDFTextGlyph], state: &QPDFGraphicsState) {
+        // Render modes 3 and 7 draw nothing
+        if matches!(state.text.render_mode, 3 | 7) {
+            return;
+        }
+
+        for glyph in glyphs.iter().filter(|g| !g.text.trim().is_empty()) {
+            self.add(glyph.bbox, state);
+        }
+    }
+
+    fn image(&mut self, _image: &QPDFImage, state: &QPDFGraphicsState) {
+        self.add(Rect::new(0.0, 0.0, 1.0, 1.0).transform(&state.ctm), state);
+    }
+
+    fn shading(&mut self, _shading: &QPDFObjectHandler, state: &QPDFGraphicsState) {
+        // Shadings fill the whole clipping region
+        self.add(self.page, state);
+    }
+}
+
+fn is_white(space: &QPDFImageColorSpace, color: &[f64]) -> bool {
+    match space {
+        QPDFImageColorSpace::DeviceCMYK | QPDFImageColorSpace::ICCBased(4) => {
+            color.iter().all(|c| *c <= 0.0)
+        }
+        QPDFImageColorSpace::DeviceGray
+        | QPDFImageColorSpace::CalGray
+        | QPDFImageColorSpace::DeviceRGB
+        | QPDFImageColorSpace::CalRGB
+        | QPDFImageColorSpace::ICCBased(1 | 3) => color.iter().all(|c| *c >= 1.0),
+        _ => false,
+    }
+}
+
+// Scaling
+impl QPDFPage {
+    pub fn scale(
+        &self,
+        size: QPDFPageSize,
+        params: &QPDFPageScaleParams,
+    ) -> Result<(), QPDFErrors> {
+        let source = self.crop_box().ok_or(QPDFErrors::InvalidPage)?;
+        let mut target = size.rect();
+
+        if source.is_empty() || target.is_empty() {
+            return Err(QPDFErrors::InvalidPage);
+        }
+
+        // Rotated pages are displayed sideways, so fit them to the sideways target
+        if self.rotation() % 180 == 90 {
+            target = Rect::new(
+                target.llx,
+                target.lly,
+                target.llx + target.height(),
+                target.lly + target.width(),
+            );
+        }
+
+        let matrix = scale_matrix(&source, &target, params);
+
+        let prefix = format!("q\n{matrix} cm\n");
+        self.wrap_contents(prefix.as_bytes(), b"\nQ\n");
+
+        for key in ["/TrimBox", "/BleedBox", "/ArtBox"] {
+            let current: Option<Rect> = self.object.dict_get_key(key.to_string()).try_into().ok();
+
+            if let Some(rect) = current.and_then(|r| r.transform(&matrix).intersect(&target)) {
+                self.object
+                    .dict_replace_key(key.to_string(), rect.to_object(&self.object));
+            }
+        }
+
+        self.scale_annotations(&matrix);
+        self.set_media_box(target);
+        self.set_crop_box(target);
+
+        Ok(())
+    }
+
+    fn scale_annotations(&self, matrix: &Matrix) {
+        let annots = self.object.dict_get_key("/Annots".to_string());
+
+        if !annots.is(QPDFIsObjectType::Array) {
+            return;
+        }
+
+        for i in 0..annots.array_len() {
+            let annot = annots.array_get_at(i);
+            let rect: Option<Rect> = annot.dict_get_key("/Rect".to_string()).try_into().ok();
+
+            if let Some(rect) = rect {
+                annot.dict_replace_key(
+                    "/Rect".to_string(),
+                    rect.transform(matrix).to_object(&annot),
+                );
+            }
+        }
+    }
+}
+
+fn scale_matrix(source: &Rect, target: &Rect, params: &QPDFPageScaleParams) -> Matrix {
+    let sx = target.width() / source.width();
+    let sy = target.height() / source.height();
+
+    let (sx, sy) = match params.mode {
+        QPDFPageScaleMode::Fit => (sx.min(sy), sx.min(sy)),
+        QPDFPageScaleMode::Fill => (sx.max(sy), sx.max(sy)),
+        QPDFPageScaleMode::Stretch => (sx, sy),
+    };
+
+    let (mut dx, mut dy) = (target.llx - sx * source.llx, target.lly - sy * source.lly);
+
+    if params.center {
+        dx += (target.width() - sx * source.width()) / 2.0;
+        dy += (target.height() - sy * source.height()) / 2.0;
+    }
+
+    Matrix::new(sx, 0.0, 0.0, sy, dx, dy)
+}
+
+pub mod types;
+
+#[cfg(test)]
+mod tests;
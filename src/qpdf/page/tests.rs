@@ -0,0 +1,218 @@
+use std::path::PathBuf;
+
+use super::types::{QPDFPageAutoCropParams, QPDFPageScaleMode, QPDFPageScaleParams, QPDFPageSize};
+use crate::qpdf::{
+    QPDF, content::builder::QPDFContentBuilder, geometry::Rect,
+    object::types::QPDFModifyObjectTypes, page::QPDFPage, read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+fn assert_rect_eq(a: Rect, b: Rect) {
+    assert!((a.llx - b.llx).abs() < 0.01);
+    assert!((a.lly - b.lly).abs() < 0.01);
+    assert!((a.urx - b.urx).abs() < 0.01);
+    assert!((a.ury - b.ury).abs() < 0.01);
+}
+
+#[test]
+fn read_page_boxes() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+
+    assert_rect_eq(
+        Rect::new(0.0, 0.0, 595.28, 841.89),
+        page.media_box().unwrap(),
+    );
+    assert_rect_eq(page.media_box().unwrap(), page.crop_box().unwrap());
+    assert_eq!(0, page.rotation());
+}
+
+#[test]
+fn scale_page_to_letter() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let params = QPDFPageScaleParams::default()
+        .with_mode(QPDFPageScaleMode::Fit)
+        .with_center();
+
+    page.scale(QPDFPageSize::Letter, &params).unwrap();
+
+    assert_rect_eq(QPDFPageSize::Letter.rect(), page.media_box().unwrap());
+    assert_rect_eq(QPDFPageSize::Letter.rect(), page.crop_box().unwrap());
+    assert_eq!(3, page.contents().len());
+}
+
+#[test]
+fn scale_all_pages_to_a3() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    qpdf.scale_pages(QPDFPageSize::A3, QPDFPageScaleParams::default())
+        .unwrap();
+
+    for at in 0..3 {
+        let page = QPDFPage::from(qpdf.get_page(at).unwrap());
+        assert_rect_eq(QPDFPageSize::A3.rect(), page.media_box().unwrap());
+    }
+}
+
+#[test]
+fn scale_matrix_modes() {
+    let source = Rect::from_size(100.0, 200.0);
+    let target = Rect::from_size(200.0, 200.0);
+
+    let fit = QPDFPageScaleParams::default().with_center();
+    let m = super::scale_matrix(&source, &target, &fit);
+    assert_eq!(Rect::new(50.0, 0.0, 150.0, 200.0), source.transform(&m));
+
+    let fill = QPDFPageScaleParams::default().with_mode(QPDFPageScaleMode::Fill);
+    let m = super::scale_matrix(&source, &target, &fill);
+    assert_eq!((200.0, 400.0), m.apply(100.0, 200.0));
+
+    let stretch = QPDFPageScaleParams::default().with_mode(QPDFPageScaleMode::Stretch);
+    let m = super::scale_matrix(&source, &target, &stretch);
+    assert_eq!((200.0, 200.0), m.apply(100.0, 200.0));
+}
+
+#[test]
+fn append_content_isolates_original() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    page.append_content(b"0 0 1 rg 0 0 10 10 re f\n");
+
+    let data = page.content_data().unwrap();
+
+    assert_eq!(4, page.contents().len());
+    assert!(data.starts_with(b"q\n"));
+    assert!(data.ends_with(b"Q\n0 0 1 rg 0 0 10 10 re f\n"));
+}
+
+#[test]
+fn prepend_content_is_wrapped() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(1).unwrap());
+    page.prepend_content(b"1 0 0 rg");
+
+    let data = page.content_data().unwrap();
+
+    assert_eq!(2, page.contents().len());
+    assert!(data.starts_with(b"q\n1 0 0 rg\nQ\n"));
+}
+
+#[test]
+fn content_bbox_skips_white_background() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+
+    assert_rect_eq(
+        Rect::new(56.69, 764.69, 281.82, 784.95),
+        page.content_bbox(false).unwrap().unwrap(),
+    );
+    assert_rect_eq(
+        page.media_box().unwrap(),
+        page.content_bbox(true).unwrap().unwrap(),
+    );
+}
+
+#[test]
+fn content_bbox_covers_paths_and_clipping() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    page.replace_content(
+        b"q 100 100 50 50 re W n 0 0 1 rg 0 0 300 300 re f Q\n\
+          4 w 200 400 m 300 400 l S\n\
+          q 1 g 0 0 600 800 re f Q",
+    );
+
+    assert_rect_eq(
+        Rect::new(100.0, 100.0, 302.0, 402.0),
+        page.content_bbox(false).unwrap().unwrap(),
+    );
+}
+
+#[test]
+fn auto_crop_with_margin() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let crops = qpdf
+        .auto_crop_pages(QPDFPageAutoCropParams::default().with_margin(10.0))
+        .unwrap();
+    assert_eq!(3, crops.len());
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    assert_rect_eq(
+        Rect::new(46.69, 754.69, 291.82, 794.95),
+        page.crop_box().unwrap(),
+    );
+    assert_eq!(crops[0], page.crop_box());
+
+    let blank = QPDFPage::from(qpdf.get_page(1).unwrap());
+    blank.replace_content(b"");
+    assert_eq!(
+        None,
+        blank.auto_crop(&QPDFPageAutoCropParams::default()).unwrap()
+    );
+}
+
+#[test]
+fn resources_mut_leaves_shared_resources_alone() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let first = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let second = QPDFPage::from(qpdf.get_page(1).unwrap());
+    let parent = first.object().dict_get_key("/Parent".to_string());
+
+    let fonts = parent
+        .set(QPDFModifyObjectTypes::Dictionary)
+        .make_indirect()
+        .unwrap();
+    let resources = parent.set(QPDFModifyObjectTypes::Dictionary);
+    resources.dict_replace_key("/Font".to_string(), fonts.clone());
+    parent.dict_replace_key("/Resources".to_string(), resources);
+    for page in [&first, &second] {
+        page.object().dict_remove_key("/Resources".to_string());
+    }
+
+    // Reading inherited resources leaves the page untouched
+    assert!(first.resources().is_some());
+    assert!(!first.object().dict_has_key("/Resources".to_string()));
+
+    // Builders that register nothing leave the shared resources where they are
+    QPDFContentBuilder::for_page(&first).fill_gray(0.5).fill();
+    assert!(!first.object().dict_has_key("/Resources".to_string()));
+
+    QPDFContentBuilder::for_page(&first).standard_font("Helvetica", 12.0);
+    let mut builder = QPDFContentBuilder::for_page(&first);
+    builder.standard_font("Helvetica", 10.0);
+    assert_eq!(b"/F1 10 Tf\n".to_vec(), builder.into_data());
+
+    let font_names = |page: &QPDFPage| {
+        page.resources()
+            .unwrap()
+            .dict_get_key("/Font".to_string())
+            .dict_keys()
+    };
+    assert_eq!(vec!["/F1".to_string()], font_names(&first));
+    assert!(font_names(&second).is_empty());
+    assert!(fonts.dict_keys().is_empty());
+}
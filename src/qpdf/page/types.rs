@@ -0,0 +1,70 @@
+use crate::qpdf::geometry::Rect;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QPDFPageSize {
+    A4,
+    A3,
+    Letter,
+    Legal,
+    Custom(Rect),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum QPDFPageScaleMode {
+    #[default]
+    Fit,
+    Fill,
+    Stretch,
+}
+
+#[derive(Debug, Default)]
+pub struct QPDFPageScaleParams {
+    pub(crate) mode: QPDFPageScaleMode,
+    pub(crate) center: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct QPDFPageAutoCropParams {
+    pub(crate) margin: f64,
+    pub(crate) include_white: bool,
+}
+
+impl QPDFPageSize {
+    pub fn rect(&self) -> Rect {
+        match self {
+            QPDFPageSize::A4 => Rect::from_size(mm(210.0), mm(297.0)),
+            QPDFPageSize::A3 => Rect::from_size(mm(297.0), mm(420.0)),
+            QPDFPageSize::Letter => Rect::from_size(612.0, 792.0),
+            QPDFPageSize::Legal => Rect::from_size(612.0, 1008.0),
+            QPDFPageSize::Custom(r) => *r,
+        }
+    }
+}
+
+impl QPDFPageScaleParams {
+    pub fn with_mode(mut self, mode: QPDFPageScaleMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_center(mut self) -> Self {
+        self.center = true;
+        self
+    }
+}
+
+impl QPDFPageAutoCropParams {
+    pub fn with_margin(mut self, margin: f64) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn with_include_white(mut self) -> Self {
+        self.include_white = true;
+        self
+    }
+}
+
+fn mm(v: f64) -> f64 {
+    v / 25.4 * 72.0
+}
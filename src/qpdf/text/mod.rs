@@ -0,0 +1,167 @@
+use types::QPDFTextRun;
+
+use super::{
+    QPDFErrors,
+    interpreter::{
+        QPDFContentHandler, QPDFContentInterpreter,
+        types::{QPDFGraphicsState, QPDFTextGlyph},
+    },
+    page::QPDFPage,
+};
+
+// Fractions of the font size used to group glyphs into lines, words and columns
+const LINE_TOLERANCE: f64 = 0.5;
+const WORD_GAP: f64 = 0.2;
+const COLUMN_GAP: f64 = 1.5;
+
+#[derive(Default)]
+struct TextCollector {
+    glyphs: Vec<QPDFTextGlyph>,
+    runs: Vec<QPDFTextRun>,
+}
+
+impl QPDFContentHandler for TextCollector {
+    fn text(&mut self, glyphs: &[QPDFTextGlyph], state: &QPDFGraphicsState) {
+        let Some(first) = glyphs.first() else {
+            return;
+        };
+
+        self.runs.push(QPDFTextRun {
+            text: glyphs.iter().map(|g| g.text.as_str()).collect(),
+            font: state.text.font_name.clone(),
+            base_font: state
+                .text
+                .font
+                .as_ref()
+                .and_then(|f| f.base_font())
+                .map(String::from),
+            size: first.size,
+            x: first.x,
+            y: first.y,
+            bbox: glyphs
+                .iter()
+                .fold(first.bbox, |bbox, glyph| bbox.union(&glyph.bbox)),
+        });
+
+        self.glyphs.extend_from_slice(glyphs);
+    }
+}
+
+fn collect(page: &QPDFPage) -> Result<TextCollector, QPDFErrors> {
+    let mut collector = TextCollector::default();
+    QPDFContentInterpreter::new().run_page(page, &mut collector)?;
+
+    Ok(collector)
+}
+
+pub(crate) fn extract_glyphs(page: &QPDFPage) -> Result<Vec<QPDFTextGlyph>, QPDFErrors> {
+    Ok(collect(page)?.glyphs)
+}
+
+pub(crate) fn extract_runs(page: &QPDFPage) -> Result<Vec<QPDFTextRun>, QPDFErrors> {
+    Ok(collect(page)?.runs)
+}
+
+// Orders glyphs top to bottom and left to right, inserting spaces and line breaks from their gaps.
+// Runs of lines split into the same aligned columns are read one column at a time.
+pub(crate) fn layout_text(mut glyphs: Vec<QPDFTextGlyph>) -> String {
+    glyphs.sort_by(|a, b| b.y.total_cmp(&a.y));
+
+    let mut lines: Vec<Vec<QPDFTextGlyph>> = Vec::new();
+    for glyph in glyphs {
+        match lines.last_mut() {
+            Some(line)
+                if (line[0].y - glyph.y).abs() <= LINE_TOLERANCE * line[0].size.max(glyph.size) =>
+            {
+                line.push(glyph)
+            }
+            _ => lines.push(vec![glyph]),
+        }
+    }
+
+    let mut text = Vec::new();
+    let mut block: Vec<Vec<Vec<QPDFTextGlyph>>> = Vec::new();
+
+    for mut line in lines {
+        line.sort_by(|a, b| a.x.total_cmp(&b.x));
+        let segments = split_columns(line);
+
+        if block.last().is_some_and(|last| !aligned(last, &segments)) {
+            flush_block(&mut block, &mut text);
+        }
+        block.push(segments);
+    }
+    flush_block(&mut block, &mut text);
+
+    text.join("\n")
+}
+
+// Splits a line wherever the gap between glyphs is wide enough to be a column gutter
+fn split_columns(line: Vec<QPDFTextGlyph>) -> Vec<Vec<QPDFTextGlyph>> {
+    let mut segments: Vec<Vec<QPDFTextGlyph>> = Vec::new();
+    let mut end: Option<f64> = None;
+
+    for glyph in line {
+        let gap = end.map(|end| glyph.x - end).unwrap_or(0.0);
+        end = Some(end.map_or(glyph.end_x, |end| end.max(glyph.end_x)));
+
+        match segments.last_mut() {
+            Some(segment) if gap <= COLUMN_GAP * glyph.size => segment.push(glyph),
+            _ => segments.push(vec![glyph]),
+        }
+    }
+
+    segments
+}
+
+fn aligned(a: &[Vec<QPDFTextGlyph>], b: &[Vec<QPDFTextGlyph>]) -> bool {
+    let span = |segment: &[QPDFTextGlyph]| {
+        let end = segment.iter().map(|g| g.end_x).fold(f64::MIN, f64::max);
+        (segment[0].x, end)
+    };
+
+    a.len() > 1
+        && a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            let (a, b) = (span(a), span(b));
+            a.0 < b.1 && b.0 < a.1
+        })
+}
+
+// A single line with a wide gap is not enough to call it columns, so it stays one line
+fn flush_block(block: &mut Vec<Vec<Vec<QPDFTextGlyph>>>, text: &mut Vec<String>) {
+    let lines = std::mem::take(block);
+
+    if lines.len() > 1 {
+        for column in 0..lines[0].len() {
+            text.extend(lines.iter().map(|line| line_text(&line[column])));
+        }
+    } else {
+        text.extend(lines.iter().map(|line| line_text(&line.concat())));
+    }
+}
+
+fn line_text(glyphs: &[QPDFTextGlyph]) -> String {
+    let mut text = String::new();
+    let mut end: Option<f64> = None;
+
+    for glyph in glyphs {
+        let gap = end.map(|end| glyph.x - end).unwrap_or(0.0);
+        let spaced =
+            text.ends_with(char::is_whitespace) || glyph.text.starts_with(char::is_whitespace);
+
+        if gap > WORD_GAP * glyph.size && !spaced && !text.is_empty() {
+            text.push(' ');
+        }
+
+        text.push_str(&glyph.text);
+        end = Some(end.map_or(glyph.end_x, |end| end.max(glyph.end_x)));
+    }
+
+    text.trim_end().to_string()
+}
+
+pub mod types;
+
+#[cfg(test)]
+mod tests;
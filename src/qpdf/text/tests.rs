@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+use super::layout_text;
+use crate::qpdf::{
+    QPDF, content::builder::QPDFContentBuilder, geometry::Rect, interpreter::types::QPDFTextGlyph,
+    page::QPDFPage, read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+fn glyph(text: &str, x: f64, y: f64) -> QPDFTextGlyph {
+    QPDFTextGlyph {
+        code: 0,
+        text: text.to_string(),
+        x,
+        y,
+        end_x: x + 6.0,
+        size: 10.0,
+        bbox: Rect::new(x, y - 2.0, x + 6.0, y + 8.0),
+    }
+}
+
+#[test]
+fn layout_orders_lines_and_words() {
+    let glyphs = vec![
+        glyph("d", 50.0, 680.0),
+        glyph("b", 6.0, 700.0),
+        glyph("a", 0.0, 700.0),
+        glyph("c", 20.0, 701.0),
+        glyph(" ", 56.0, 680.0),
+        glyph("e", 62.0, 680.0),
+    ];
+
+    assert_eq!("ab c\nd e", layout_text(glyphs));
+}
+
+#[test]
+fn layout_reads_columns_in_turn() {
+    let word = |text: &str, x: f64, y: f64| {
+        text.chars()
+            .enumerate()
+            .map(|(at, c)| glyph(&c.to_string(), x + at as f64 * 6.0, y))
+            .collect::<Vec<_>>()
+    };
+
+    let glyphs = [
+        word("title", 0.0, 720.0),
+        word("left1", 0.0, 700.0),
+        word("right1", 100.0, 700.0),
+        word("left2", 0.0, 685.0),
+        word("right2", 100.0, 685.0),
+        word("key", 0.0, 660.0),
+        word("value", 200.0, 660.0),
+    ]
+    .concat();
+
+    assert_eq!(
+        "title\nleft1\nleft2\nright1\nright2\nkey value",
+        layout_text(glyphs)
+    );
+}
+
+#[test]
+fn extract_page_text() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let text = QPDFPage::from(qpdf.get_page(0).unwrap())
+        .extract_text()
+        .unwrap();
+    assert!(text.contains("THIS IS A TEST (1/3 PAGES)"));
+
+    let pages = qpdf.extract_text().unwrap();
+    assert_eq!(3, pages.len());
+    assert!(pages[2].contains("(3/3 PAGES)"));
+}
+
+#[test]
+fn extract_text_with_positioning() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let mut content = QPDFContentBuilder::new(page.resources_mut());
+    content
+        .begin_text()
+        .standard_font("Helvetica", 10.0)
+        .text_leading(12.0)
+        .text_position(72.0, 72.0)
+        .show_text("Hello ")
+        .raw(b"[(Wor) 1000 (ld)] TJ")
+        .next_line()
+        .show_text("Second line")
+        .end_text();
+    page.replace_content(content.data());
+
+    assert_eq!("Hello Wor ld\nSecond line", page.extract_text().unwrap());
+}
+
+fn assert_near(expected: f64, actual: f64) {
+    assert!((expected - actual).abs() < 0.001, "{expected} != {actual}");
+}
+
+#[test]
+fn page_text_runs() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let runs = QPDFPage::from(qpdf.get_page(0).unwrap())
+        .text_runs()
+        .unwrap();
+
+    let run = &runs[0];
+    assert_eq!("THIS", run.text);
+    assert_eq!(Some("/TT1".to_string()), run.font);
+    assert_eq!(Some("AAAAAB+HelveticaNeue-Bold".to_string()), run.base_font);
+    assert_near(17.0, run.size);
+    assert_near(56.69292, run.x);
+    assert_near(768.3791, run.y);
+
+    // Widths from /Widths, ascent and descent from the font descriptor
+    assert_near(56.69292 + 39.032, run.bbox.urx);
+    assert_near(768.3791 - 0.217 * 17.0, run.bbox.lly);
+    assert_near(768.3791 + 0.975 * 17.0, run.bbox.ury);
+
+    assert_eq!(3, qpdf.text_runs().unwrap().len());
+}
+
+#[test]
+fn text_runs_use_standard_metrics() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let page = QPDFPage::from(qpdf.get_page(0).unwrap());
+    let mut content = QPDFContentBuilder::new(page.resources_mut());
+    content
+        .raw(b"1 0 0 1 100 0 cm")
+        .begin_text()
+        .standard_font("Helvetica", 10.0)
+        .text_position(-110.0, 72.0)
+        .show_text("Hello")
+        .end_text();
+    page.replace_content(content.data());
+
+    let runs = page.text_runs().unwrap();
+    assert_eq!(1, runs.len());
+    assert_eq!(Some("Helvetica".to_string()), runs[0].base_font);
+
+    let bbox = runs[0].bbox;
+    assert_near(-10.0, bbox.llx);
+    assert_near(-10.0 + 22.78, bbox.urx);
+    assert_near(72.0 - 2.07, bbox.lly);
+    assert_near(72.0 + 7.18, bbox.ury);
+
+    // The run starts left of the page, so it is only partly inside the CropBox
+    let crop_box = page.crop_box().unwrap();
+    assert_ne!(Some(bbox), bbox.intersect(&crop_box));
+    assert!(crop_box.contains(bbox.urx, bbox.ury));
+    assert!(!crop_box.contains(bbox.llx, bbox.lly));
+}
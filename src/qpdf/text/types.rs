@@ -0,0 +1,12 @@
+use crate::qpdf::geometry::Rect;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPDFTextRun {
+    pub text: String,
+    pub font: Option<String>,
+    pub base_font: Option<String>,
+    pub size: f64,
+    pub x: f64,
+    pub y: f64,
+    pub bbox: Rect,
+}
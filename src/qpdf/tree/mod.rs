@@ -0,0 +1,387 @@
+use std::{collections::HashSet, marker::PhantomData};
+
+use types::QPDFTreeKey;
+
+use super::object::{
+    QPDFObjectHandler,
+    types::{Generation, ObjectId, QPDFIsObjectType, QPDFModifyObjectTypes},
+};
+
+// Limits how deeply nested tree nodes are followed
+const MAX_TREE_DEPTH: usize = 32;
+// Nodes holding more entries or kids than this are split in two
+const MAX_NODE_SIZE: i32 = 32;
+
+// A name or number tree, rooted at a dictionary that keeps its identity as the tree changes
+#[derive(Clone)]
+pub struct QPDFTree<K> {
+    root: QPDFObjectHandler,
+    key: PhantomData<K>,
+}
+
+// Each step of a path records a node and its index in the parent's /Kids
+type TreePath = Vec<(QPDFObjectHandler, i32)>;
+
+// Construction
+impl<K: QPDFTreeKey> QPDFTree<K> {
+    pub fn new(root: QPDFObjectHandler) -> Self {
+        Self {
+            root,
+            key: PhantomData,
+        }
+    }
+
+    pub fn empty(factory: &QPDFObjectHandler) -> Option<Self> {
+        let root = factory
+            .set(QPDFModifyObjectTypes::Dictionary)
+            .make_indirect()?;
+        root.dict_replace_key(
+            K::ENTRIES.to_string(),
+            factory.set(QPDFModifyObjectTypes::Array),
+        );
+
+        Some(Self::new(root))
+    }
+
+    pub fn root(&self) -> &QPDFObjectHandler {
+        &self.root
+    }
+}
+
+// Lookup
+impl<K: QPDFTreeKey> QPDFTree<K> {
+    pub fn entries(&self) -> Vec<(K, QPDFObjectHandler)> {
+        let mut entries = Vec::new();
+        collect(&self.root, 0, &mut HashSet::new(), &mut entries);
+        entries
+    }
+
+    pub fn keys(&self) -> Vec<K> {
+        self.entries().into_iter().map(|(key, _)| key).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, key: &K) -> Option<QPDFObjectHandler> {
+        let mut path = vec![(self.root.clone(), -1)];
+        let at = locate(key, &mut path)?;
+
+        let (leaf, _) = path.last()?;
+        Some(
+            leaf.dict_get_key(K::ENTRIES.to_string())
+                .array_get_at(at + 1),
+        )
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+// Modification
+impl<K: QPDFTreeKey> QPDFTree<K> {
+    // Replaces the value of an existing key, otherwise adds it in order
+    pub fn insert(&self, key: K, value: QPDFObjectHandler) {
+        let mut path = vec![(self.root.clone(), -1)];
+
+        while path.len() <= MAX_TREE_DEPTH {
+            let (node, _) = path.last().expect("Path to start at the root");
+            let kids = node.dict_get_key("/Kids".to_string());
+            if !kids.is(QPDFIsObjectType::Array) || kids.array_len() == 0 {
+                break;
+            }
+
+            // Keys past every range go into the last kid, widening its limits
+            let at = (0..kids.array_len())
+                .find(|&at| {
+                    limits::<K>(&kids.array_get_at(at)).is_some_and(|(_, high)| key <= high)
+                })
+                .unwrap_or(kids.array_len() - 1);
+
+            path.push((kids.array_get_at(at), at));
+        }
+
+        let (leaf, _) = path.last().expect("Path to start at the root");
+        let mut entries = leaf.dict_get_key(K::ENTRIES.to_string());
+        if !entries.is(QPDFIsObjectType::Array) {
+            entries = leaf.set(QPDFModifyObjectTypes::Array);
+            leaf.dict_replace_key(K::ENTRIES.to_string(), entries.clone());
+        }
+
+        let mut at = 0;
+        while at < entries.array_len() {
+            match K::from_object(&entries.array_get_at(at)) {
+                Some(existing) if existing == key => {
+                    entries.array_set_at(at + 1, value);
+                    return;
+                }
+                Some(existing) if existing < key => at += 2,
+                _ => break,
+            }
+        }
+
+        entries.array_insert_at(at, key.to_object(leaf));
+        entries.array_insert_at(at + 1, value);
+
+        split::<K>(&path);
+        update_limits::<K>(&path);
+    }
+
+    pub fn remove(&self, key: &K) -> Option<QPDFObjectHandler> {
+        let mut path = vec![(self.root.clone(), -1)];
+        let at = locate(key, &mut path)?;
+
+        let (leaf, _) = path.last()?;
+        let entries = leaf.dict_get_key(K::ENTRIES.to_string());
+        let value = entries.array_get_at(at + 1);
+        entries.array_erase_at(at + 1);
+        entries.array_erase_at(at);
+
+        // Emptied nodes are dropped from their parents, all the way up if needed
+        while path.len() > 1 {
+            let (node, index) = path.last().expect("Path to have a leaf");
+            if !is_empty_node::<K>(node) {
+                break;
+            }
+
+            let (parent, _) = &path[path.len() - 2];
+            parent
+                .dict_get_key("/Kids".to_string())
+                .array_erase_at(*index);
+            path.pop();
+        }
+
+        update_limits::<K>(&path);
+        collapse_root::<K>(&self.root);
+
+        if is_empty_node::<K>(&self.root) {
+            self.root.dict_remove_key("/Kids".to_string());
+            self.root.dict_replace_key(
+                K::ENTRIES.to_string(),
+                self.root.set(QPDFModifyObjectTypes::Array),
+            );
+        }
+
+        Some(value)
+    }
+}
+
+// Text Keys
+impl QPDFTree<Vec<u8>> {
+    // Text is used as its UTF-8 bytes, which only matches other producers' keys for ASCII
+    pub fn get_str(&self, key: &str) -> Option<QPDFObjectHandler> {
+        self.get(&key.as_bytes().to_vec())
+    }
+
+    pub fn insert_str(&self, key: &str, value: QPDFObjectHandler) {
+        self.insert(key.as_bytes().to_vec(), value)
+    }
+}
+
+fn collect<K: QPDFTreeKey>(
+    node: &QPDFObjectHandler,
+    depth: usize,
+    seen: &mut HashSet<(ObjectId, Generation)>,
+    entries: &mut Vec<(K, QPDFObjectHandler)>,
+) {
+    if depth > MAX_TREE_DEPTH || !node.is(QPDFIsObjectType::Dictionary) {
+        return;
+    }
+
+    // Broken trees can list the same node more than once
+    let id = (node.object_id(), node.generation());
+    if id.0 != 0 && !seen.insert(id) {
+        return;
+    }
+
+    let list = node.dict_get_key(K::ENTRIES.to_string());
+    for at in (0..list.array_len()).step_by(2) {
+        if let Some(key) = K::from_object(&list.array_get_at(at)) {
+            entries.push((key, list.array_get_at(at + 1)));
+        }
+    }
+
+    let kids = node.dict_get_key("/Kids".to_string());
+    for at in 0..kids.array_len() {
+        collect(&kids.array_get_at(at), depth + 1, seen, entries);
+    }
+}
+
+// Extends `path` down to the leaf holding `key`, returning the key's index in that leaf
+fn locate<K: QPDFTreeKey>(key: &K, path: &mut TreePath) -> Option<i32> {
+    let (node, _) = path.last()?.clone();
+    if path.len() > MAX_TREE_DEPTH || !node.is(QPDFIsObjectType::Dictionary) {
+        return None;
+    }
+
+    let list = node.dict_get_key(K::ENTRIES.to_string());
+    for at in (0..list.array_len()).step_by(2) {
+        if K::from_object(&list.array_get_at(at)).as_ref() == Some(key) {
+            return Some(at);
+        }
+    }
+
+    let kids = node.dict_get_key("/Kids".to_string());
+    for at in 0..kids.array_len() {
+        let kid = kids.array_get_at(at);
+
+        // Limits let whole subtrees be skipped without visiting them
+        if let Some((low, high)) = limits::<K>(&kid)
+            && (*key < low || *key > high)
+        {
+            continue;
+        }
+
+        path.push((kid, at));
+        if let Some(found) = locate(key, path) {
+            return Some(found);
+        }
+        path.pop();
+    }
+
+    None
+}
+
+fn limits<K: QPDFTreeKey>(node: &QPDFObjectHandler) -> Option<(K, K)> {
+    let limits = node.dict_get_key("/Limits".to_string());
+
+    Some((
+        K::from_object(&limits.array_get_at(0))?,
+        K::from_object(&limits.array_get_at(1))?,
+    ))
+}
+
+fn is_empty_node<K: QPDFTreeKey>(node: &QPDFObjectHandler) -> bool {
+    node.dict_get_key("/Kids".to_string()).array_len() == 0
+        && node.dict_get_key(K::ENTRIES.to_string()).array_len() == 0
+}
+
+// A root left with a single kid takes over its contents, keeping its own identity
+fn collapse_root<K: QPDFTreeKey>(root: &QPDFObjectHandler) {
+    for _ in 0..MAX_TREE_DEPTH {
+        let kids = root.dict_get_key("/Kids".to_string());
+        if kids.array_len() != 1 {
+            break;
+        }
+
+        let kid = kids.array_get_at(0);
+        root.dict_remove_key("/Kids".to_string());
+        for key in ["/Kids", K::ENTRIES] {
+            if kid.dict_has_key(key.to_string()) {
+                root.dict_replace_key(key.to_string(), kid.dict_get_key(key.to_string()));
+            }
+        }
+    }
+}
+
+// Splits oversized nodes along the path, from the leaf upwards
+fn split<K: QPDFTreeKey>(path: &TreePath) {
+    for depth in (0..path.len()).rev() {
+        let (node, index) = &path[depth];
+
+        let (key, step) = match node.dict_has_key("/Kids".to_string()) {
+            true => ("/Kids", 1),
+            _ => (K::ENTRIES, 2),
+        };
+
+        let list = node.dict_get_key(key.to_string());
+        if list.array_len() <= MAX_NODE_SIZE * step {
+            continue;
+        }
+
+        let half = list.array_len() / step / 2 * step;
+        let upper = node.set(QPDFModifyObjectTypes::Array);
+        while list.array_len() > half {
+            upper.array_append(list.array_get_at(half));
+            list.array_erase_at(half);
+        }
+
+        let Some(sibling) = new_node(node, key, upper) else {
+            return;
+        };
+
+        if depth == 0 {
+            // The root keeps its identity, so both halves move into new kids below it
+            let Some(lower) = new_node(node, key, list) else {
+                return;
+            };
+            set_limits::<K>(&lower);
+            set_limits::<K>(&sibling);
+
+            let kids = node.set(QPDFModifyObjectTypes::Array);
+            kids.array_append(lower);
+            kids.array_append(sibling);
+
+            node.dict_remove_key(key.to_string());
+            node.dict_replace_key("/Kids".to_string(), kids);
+        } else {
+            set_limits::<K>(node);
+            set_limits::<K>(&sibling);
+
+            let (parent, _) = &path[depth - 1];
+            parent
+                .dict_get_key("/Kids".to_string())
+                .array_insert_at(index + 1, sibling);
+        }
+    }
+}
+
+fn new_node(
+    factory: &QPDFObjectHandler,
+    key: &str,
+    list: QPDFObjectHandler,
+) -> Option<QPDFObjectHandler> {
+    let node = factory
+        .set(QPDFModifyObjectTypes::Dictionary)
+        .make_indirect()?;
+    node.dict_replace_key(key.to_string(), list);
+
+    Some(node)
+}
+
+// The root carries no limits, every other node on the path is refreshed from the leaf up
+fn update_limits<K: QPDFTreeKey>(path: &TreePath) {
+    for (node, _) in path.iter().skip(1).rev() {
+        set_limits::<K>(node);
+    }
+}
+
+fn set_limits<K: QPDFTreeKey>(node: &QPDFObjectHandler) {
+    let bounds = match node.dict_has_key("/Kids".to_string()) {
+        true => {
+            let kids = node.dict_get_key("/Kids".to_string());
+            let first = limits::<K>(&kids.array_get_at(0));
+            let last = limits::<K>(&kids.array_get_at(kids.array_len() - 1));
+
+            first.zip(last).map(|((low, _), (_, high))| (low, high))
+        }
+        _ => {
+            let list = node.dict_get_key(K::ENTRIES.to_string());
+            let first = K::from_object(&list.array_get_at(0));
+            let last = K::from_object(&list.array_get_at(list.array_len() - 2));
+
+            first.zip(last)
+        }
+    };
+
+    match bounds {
+        Some((low, high)) => {
+            let array = node.set(QPDFModifyObjectTypes::Array);
+            array.array_append(low.to_object(node));
+            array.array_append(high.to_object(node));
+            node.dict_replace_key("/Limits".to_string(), array);
+        }
+        None => node.dict_remove_key("/Limits".to_string()),
+    }
+}
+
+pub mod types;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,170 @@
+use std::path::PathBuf;
+
+use super::types::{QPDFNameTree, QPDFNumberTree};
+use crate::qpdf::{
+    QPDF,
+    object::types::{QPDFIsObjectType, QPDFModifyObjectTypes},
+    read::QPDFReadParams,
+};
+
+fn load(qpdf: &QPDF) {
+    let pdf = PathBuf::from(".").join("assets").join("testpdf1.pdf");
+    qpdf.enable_warning_supression();
+    qpdf.process_file(pdf, QPDFReadParams::default(), None)
+        .unwrap();
+}
+
+#[test]
+fn number_tree_insert_and_remove() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    let tree = QPDFNumberTree::empty(&root).unwrap();
+    assert!(tree.is_empty());
+
+    // Inserted out of order and enough to force several splits
+    for key in (0..200).rev() {
+        tree.insert(key, root.set(QPDFModifyObjectTypes::Integer(key * 10)));
+    }
+
+    assert_eq!((0..200).collect::<Vec<i64>>(), tree.keys());
+    assert!(tree.root().dict_has_key("/Kids".to_string()));
+    assert!(!tree.root().dict_has_key("/Limits".to_string()));
+
+    let value: i64 = tree.get(&123).unwrap().try_into().unwrap();
+    assert_eq!(1230, value);
+    assert!(tree.get(&200).is_none());
+
+    tree.insert(123, root.set(QPDFModifyObjectTypes::Integer(-1)));
+    let value: i64 = tree.get(&123).unwrap().try_into().unwrap();
+    assert_eq!(-1, value);
+    assert_eq!(200, tree.len());
+
+    for key in 0..150 {
+        assert!(tree.remove(&key).is_some());
+    }
+    assert!(tree.remove(&0).is_none());
+    assert_eq!((150..200).collect::<Vec<i64>>(), tree.keys());
+    assert!(tree.contains_key(&175));
+
+    for key in 150..200 {
+        tree.remove(&key);
+    }
+    assert!(tree.is_empty());
+    assert!(
+        tree.root()
+            .dict_get_key("/Nums".to_string())
+            .is(QPDFIsObjectType::Array)
+    );
+}
+
+#[test]
+fn number_tree_prunes_emptied_leaves() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    let tree = QPDFNumberTree::empty(&root).unwrap();
+    for key in 0..100 {
+        tree.insert(key, root.set(QPDFModifyObjectTypes::Integer(key)));
+    }
+
+    let kids = tree.root().dict_get_key("/Kids".to_string());
+    let leaves = kids.array_len();
+    assert!(leaves > 2);
+
+    let leaf_keys = |at: i32| -> Vec<i64> {
+        let nums = kids.array_get_at(at).dict_get_key("/Nums".to_string());
+        (0..nums.array_len())
+            .step_by(2)
+            .map(|at| nums.array_get_at(at).try_into().unwrap())
+            .collect()
+    };
+
+    // Emptying the first leaf drops it, and the next leaf's limits start the tree
+    let first = leaf_keys(0);
+    for key in &first {
+        tree.remove(key);
+    }
+    assert_eq!(leaves - 1, kids.array_len());
+
+    let low: i64 = kids
+        .array_get_at(0)
+        .dict_get_key("/Limits".to_string())
+        .array_get_at(0)
+        .try_into()
+        .unwrap();
+    assert_eq!(first.last().unwrap() + 1, low);
+
+    // Down to one leaf, the root holds its entries itself
+    while kids.array_len() > 1 {
+        for key in leaf_keys(1) {
+            tree.remove(&key);
+        }
+    }
+    let remaining = tree.keys();
+    assert!(!tree.root().dict_has_key("/Kids".to_string()));
+    assert!(!tree.root().dict_has_key("/Limits".to_string()));
+    assert_eq!(
+        remaining.len() as i32 * 2,
+        tree.root().dict_get_key("/Nums".to_string()).array_len()
+    );
+}
+
+#[test]
+fn name_tree_limits() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    let tree = QPDFNameTree::empty(&root).unwrap();
+
+    for at in 0..40 {
+        tree.insert_str(
+            &format!("dest{at:03}"),
+            root.set(QPDFModifyObjectTypes::Integer(at)),
+        );
+    }
+
+    let kids = tree.root().dict_get_key("/Kids".to_string());
+    assert_eq!(2, kids.array_len());
+
+    let limits = kids.array_get_at(1).dict_get_key("/Limits".to_string());
+    let high: String = limits.array_get_at(1).try_into().unwrap();
+    assert_eq!("dest039", high);
+
+    tree.insert_str("zzz", root.set(QPDFModifyObjectTypes::Null));
+    let limits = kids.array_get_at(1).dict_get_key("/Limits".to_string());
+    let high: String = limits.array_get_at(1).try_into().unwrap();
+    assert_eq!("zzz", high);
+
+    assert!(tree.get_str("dest007").is_some());
+    assert!(tree.get_str("dest").is_none());
+}
+
+#[test]
+fn name_tree_keys_are_raw_bytes() {
+    let qpdf = QPDF::default();
+    load(&qpdf);
+
+    let root = qpdf.get_object_root().unwrap();
+    let tree = QPDFNameTree::empty(&root).unwrap();
+
+    // Keys that are not valid text keep their bytes and sort by them
+    for key in [b"\xe9t\xe9".to_vec(), b"a".to_vec(), b"Z".to_vec()] {
+        tree.insert(key, root.set(QPDFModifyObjectTypes::Null));
+    }
+
+    assert_eq!(
+        vec![b"Z".to_vec(), b"a".to_vec(), b"\xe9t\xe9".to_vec()],
+        tree.keys()
+    );
+
+    let names = tree.root().dict_get_key("/Names".to_string());
+    assert_eq!(
+        b"\xe9t\xe9".to_vec(),
+        names.array_get_at(4).binary_string().unwrap()
+    );
+    assert!(tree.get(&b"\xe9t\xe9".to_vec()).is_some());
+}
@@ -0,0 +1,44 @@
+use super::QPDFTree;
+use crate::qpdf::object::{
+    QPDFObjectHandler,
+    types::{QPDFIsObjectType, QPDFModifyObjectTypes},
+};
+
+// Name tree keys are byte strings ordered by their raw bytes, whatever text they hold
+pub type QPDFNameTree = QPDFTree<Vec<u8>>;
+pub type QPDFNumberTree = QPDFTree<i64>;
+
+pub trait QPDFTreeKey: Ord + Clone {
+    // Dictionary key holding the key/value pairs of a leaf node
+    const ENTRIES: &'static str;
+
+    fn from_object(oh: &QPDFObjectHandler) -> Option<Self>;
+    fn to_object(&self, factory: &QPDFObjectHandler) -> QPDFObjectHandler;
+}
+
+impl QPDFTreeKey for Vec<u8> {
+    const ENTRIES: &'static str = "/Names";
+
+    fn from_object(oh: &QPDFObjectHandler) -> Option<Self> {
+        oh.binary_string().ok()
+    }
+
+    fn to_object(&self, factory: &QPDFObjectHandler) -> QPDFObjectHandler {
+        factory.set(QPDFModifyObjectTypes::Bytes(self.clone()))
+    }
+}
+
+impl QPDFTreeKey for i64 {
+    const ENTRIES: &'static str = "/Nums";
+
+    fn from_object(oh: &QPDFObjectHandler) -> Option<Self> {
+        match oh.is(QPDFIsObjectType::Integer) {
+            true => oh.clone().try_into().ok(),
+            _ => None,
+        }
+    }
+
+    fn to_object(&self, factory: &QPDFObjectHandler) -> QPDFObjectHandler {
+        factory.set(QPDFModifyObjectTypes::Integer(*self))
+    }
+}